@@ -1,8 +1,8 @@
 use cargo_cyclonedx::{
     config::{
-        Describe, Features, FilenameOverride, FilenameOverrideError, FilenamePattern,
-        IncludedDependencies, LicenseParserOptions, OutputOptions, ParseMode, PlatformSuffix,
-        SbomConfig, Target,
+        CrateSpec, Describe, Features, FilenameOverride, FilenameOverrideError, FilenamePattern,
+        IncludedDependencies, LicenseParserOptions, MergeOptions, MergeStrategy, OutputOptions,
+        ParseMode, PlatformSuffix, SbomConfig, Target,
     },
     format::Format,
     platform::host_platform,
@@ -38,6 +38,14 @@ pub struct Args {
     #[clap(long = "describe")]
     pub describe: Option<Describe>,
 
+    /// Merge an existing SBOM (JSON or XML) into the generated one, can be given multiple times
+    #[clap(long = "merge", value_name = "PATH", action = clap::ArgAction::Append, value_hint = clap::ValueHint::FilePath)]
+    pub merge: Vec<path::PathBuf>,
+
+    // the ValueEnum derive provides ample help text
+    #[clap(long = "merge-strategy", value_name = "STRATEGY", requires = "merge")]
+    pub merge_strategy: Option<MergeStrategy>,
+
     /// Use verbose output (-vv for debug logging, -vvv for tracing)
     #[clap(long = "verbose", short = 'v', action = clap::ArgAction::Count)]
     pub verbose: u8,
@@ -78,10 +86,16 @@ Defaults to the host target, as printed by 'rustc -vV'"
     pub all: bool,
 
     /// List only top-level dependencies
-    #[clap(name = "top-level", long = "top-level", conflicts_with = "all")]
+    #[clap(
+        name = "top-level",
+        long = "top-level",
+        visible_alias = "top-level-only",
+        conflicts_with = "all"
+    )]
     pub top_level: bool,
 
-    /// Custom string to use for the output filename
+    /// Custom string to use for the output filename.
+    /// A `.json` or `.xml` extension selects the output format unless --format is given
     #[clap(
         long = "override-filename",
         value_name = "FILENAME",
@@ -89,6 +103,21 @@ Defaults to the host target, as printed by 'rustc -vV'"
     )]
     pub filename_override: Option<String>,
 
+    /// Directory to write the SBOM files into, created if it does not exist.
+    /// Cannot be combined with --override-filename for a workspace with several members
+    #[clap(long = "output-dir", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    pub output_dir: Option<path::PathBuf>,
+
+    /// File to write the SBOM to instead of deriving a filename, or `-` for stdout.
+    /// A `.json` or `.xml` extension selects the output format unless --format is given
+    #[clap(
+        long = "output-file",
+        value_name = "PATH",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["describe", "filename_override", "output_dir", "target_in_filename"]
+    )]
+    pub output_file: Option<path::PathBuf>,
+
     /// Reject the deprecated '/' separator for licenses, treating 'MIT/Apache-2.0' as an error
     #[clap(long = "license-strict")]
     pub license_strict: bool,
@@ -98,12 +127,29 @@ Defaults to the host target, as printed by 'rustc -vV'"
     pub license_accept_named: Vec<String>,
 
     /// The CycloneDX specification version to output: `1.3`, `1.4` or `1.5`. Defaults to 1.3
-    #[clap(long = "spec-version")]
+    #[clap(long = "spec-version", env = "CYCLONEDX_SPEC_VERSION")]
     pub spec_version: Option<SpecVersion>,
 
     /// Do not include build-time dependencies in the SBOM
     #[clap(long = "no-build-deps")]
     pub no_build_deps: bool,
+
+    /// Record the git commit and `git describe` output of the crate on the root component
+    #[clap(long = "include-vcs")]
+    pub include_vcs: bool,
+
+    /// Sort components and dependencies and leave out the serial number so that repeated runs produce identical output.
+    /// The timestamp is taken from SOURCE_DATE_EPOCH, or left out if that is unset
+    #[clap(long = "deterministic")]
+    pub deterministic: bool,
+
+    /// Leave a crate out of the SBOM, given by name or as `name@version`, can be given multiple times
+    #[clap(long = "exclude", value_name = "CRATE", action = clap::ArgAction::Append)]
+    pub exclude: Vec<CrateSpec>,
+
+    /// Record the homepage as the supplier URL of crates that have no repository
+    #[clap(long = "homepage-as-supplier")]
+    pub homepage_as_supplier: bool,
 }
 
 impl Args {
@@ -151,17 +197,32 @@ impl Args {
             false => PlatformSuffix::NotIncluded,
         };
 
+        let mut format = self.format;
         let filename_pattern = match &self.filename_override {
             Some(string) => {
-                let name_override = FilenameOverride::new(string)?;
+                // The format extension is appended again when writing the file
+                let name = match split_format_extension(string) {
+                    Some((name, inferred)) if format.unwrap_or(inferred) == inferred => {
+                        format = Some(inferred);
+                        name
+                    }
+                    _ => string,
+                };
+                let name_override = FilenameOverride::new(name)?;
                 FilenamePattern::Custom(name_override)
             }
             None => FilenamePattern::CrateName,
         };
 
+        let output_file_name = self.output_file.as_deref().and_then(path::Path::to_str);
+        if let Some((_, inferred)) = output_file_name.and_then(split_format_extension) {
+            format = format.or(Some(inferred));
+        }
+
         let output_options = Some(OutputOptions {
             filename: filename_pattern,
             platform_suffix,
+            output_dir: self.output_dir.clone(),
         });
 
         let license_parser = Some(LicenseParserOptions {
@@ -175,9 +236,25 @@ impl Args {
         let describe = self.describe;
         let spec_version = self.spec_version;
         let only_normal_deps = Some(self.no_build_deps);
+        let output_file = self.output_file.clone();
+        let include_vcs = Some(self.include_vcs);
+        let deterministic = Some(self.deterministic);
+        let homepage_as_supplier = Some(self.homepage_as_supplier);
+        let exclude = match self.exclude.is_empty() {
+            true => None,
+            false => Some(self.exclude.clone()),
+        };
+
+        let merge = match self.merge.is_empty() {
+            true => None,
+            false => Some(MergeOptions {
+                files: self.merge.clone(),
+                strategy: self.merge_strategy.unwrap_or_default(),
+            }),
+        };
 
         Ok(SbomConfig {
-            format: self.format,
+            format,
             included_dependencies,
             output_options,
             features,
@@ -186,10 +263,28 @@ impl Args {
             describe,
             spec_version,
             only_normal_deps,
+            output_file,
+            merge,
+            include_vcs,
+            deterministic,
+            exclude,
+            homepage_as_supplier,
         })
     }
 }
 
+/// Splits a `.json` or `.xml` extension off a filename, returning the remaining name and the
+/// format the extension stands for.
+fn split_format_extension(filename: &str) -> Option<(&str, Format)> {
+    if let Some(name) = filename.strip_suffix(".json") {
+        Some((name, Format::Json))
+    } else {
+        filename
+            .strip_suffix(".xml")
+            .map(|name| (name, Format::Xml))
+    }
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum ArgsError {
     #[error("Invalid filename")]
@@ -233,6 +328,92 @@ mod tests {
         assert!(!contains_feature(&config, ""));
     }
 
+    #[test]
+    fn parse_format_from_filename_extension() {
+        let args = vec!["cyclonedx", "--override-filename=foo.cdx.xml"];
+        let config = parse_to_config(&args);
+        assert_eq!(config.format, Some(Format::Xml));
+        assert_eq!(custom_filename(&config), "foo.cdx");
+
+        let args = vec!["cyclonedx", "--override-filename=bom.json"];
+        let config = parse_to_config(&args);
+        assert_eq!(config.format, Some(Format::Json));
+        assert_eq!(custom_filename(&config), "bom");
+
+        let args = vec!["cyclonedx", "--override-filename=bom"];
+        let config = parse_to_config(&args);
+        assert_eq!(config.format, None);
+        assert_eq!(custom_filename(&config), "bom");
+
+        // an explicit format takes precedence over the extension
+        let args = vec!["cyclonedx", "--format=json", "--override-filename=bom.xml"];
+        let config = parse_to_config(&args);
+        assert_eq!(config.format, Some(Format::Json));
+        assert_eq!(custom_filename(&config), "bom.xml");
+    }
+
+    #[test]
+    fn parse_output_file() {
+        let args = vec!["cyclonedx", "--output-file=target/sbom.cdx.xml"];
+        let config = parse_to_config(&args);
+        assert_eq!(config.output_file, Some("target/sbom.cdx.xml".into()));
+        assert_eq!(config.format, Some(Format::Xml));
+
+        let args = vec!["cyclonedx", "--format=json", "--output-file=-"];
+        let config = parse_to_config(&args);
+        assert_eq!(config.output_file, Some("-".into()));
+        assert_eq!(config.format, Some(Format::Json));
+
+        let args = ["cyclonedx", "--output-file=bom.json", "--output-dir=target"];
+        assert!(Args::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn parse_deterministic() {
+        let config = parse_to_config(&["cyclonedx"]);
+        assert_eq!(config.deterministic, Some(false));
+
+        let config = parse_to_config(&["cyclonedx", "--deterministic"]);
+        assert_eq!(config.deterministic, Some(true));
+    }
+
+    #[test]
+    fn parse_homepage_as_supplier() {
+        let config = parse_to_config(&["cyclonedx"]);
+        assert_eq!(config.homepage_as_supplier, Some(false));
+
+        let config = parse_to_config(&["cyclonedx", "--homepage-as-supplier"]);
+        assert_eq!(config.homepage_as_supplier, Some(true));
+    }
+
+    #[test]
+    fn parse_exclude() {
+        let config = parse_to_config(&["cyclonedx"]);
+        assert_eq!(config.exclude, None);
+
+        let config = parse_to_config(&["cyclonedx", "--exclude=foo", "--exclude", "bar@1.2.3"]);
+        let excluded: Vec<_> = config
+            .exclude
+            .unwrap()
+            .into_iter()
+            .map(|spec| (spec.name, spec.version.map(|version| version.to_string())))
+            .collect();
+        assert_eq!(
+            excluded,
+            vec![
+                ("foo".to_string(), None),
+                ("bar".to_string(), Some("1.2.3".to_string()))
+            ]
+        );
+    }
+
+    fn custom_filename(config: &SbomConfig) -> String {
+        match &config.output_options.as_ref().unwrap().filename {
+            FilenamePattern::Custom(name) => name.to_string(),
+            FilenamePattern::CrateName => panic!("expected a custom filename"),
+        }
+    }
+
     fn contains_feature(config: &SbomConfig, feature: &str) -> bool {
         config
             .features
@@ -1,6 +1,8 @@
+use cargo_metadata::{semver::Version, Package};
 use cyclonedx_bom::models::bom::SpecVersion;
 use serde::Deserialize;
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::str::FromStr;
 use thiserror::Error;
 
@@ -34,6 +36,15 @@ pub struct SbomConfig {
     pub describe: Option<Describe>,
     pub spec_version: Option<SpecVersion>,
     pub only_normal_deps: Option<bool>,
+    /// Writes the SBOM of the crate to this file instead of deriving a filename, `-` writes it
+    /// to stdout
+    pub output_file: Option<PathBuf>,
+    pub merge: Option<MergeOptions>,
+    pub include_vcs: Option<bool>,
+    pub deterministic: Option<bool>,
+    pub exclude: Option<Vec<CrateSpec>>,
+    /// Records the homepage as the supplier URL of crates without a repository
+    pub homepage_as_supplier: Option<bool>,
 }
 
 impl SbomConfig {
@@ -59,6 +70,15 @@ impl SbomConfig {
             describe: other.describe.or(self.describe),
             spec_version: other.spec_version.or(self.spec_version),
             only_normal_deps: other.only_normal_deps.or(self.only_normal_deps),
+            output_file: other
+                .output_file
+                .clone()
+                .or_else(|| self.output_file.clone()),
+            merge: other.merge.clone().or_else(|| self.merge.clone()),
+            include_vcs: other.include_vcs.or(self.include_vcs),
+            deterministic: other.deterministic.or(self.deterministic),
+            exclude: other.exclude.clone().or_else(|| self.exclude.clone()),
+            homepage_as_supplier: other.homepage_as_supplier.or(self.homepage_as_supplier),
         }
     }
 
@@ -102,6 +122,8 @@ impl FromStr for IncludedDependencies {
 pub struct OutputOptions {
     pub filename: FilenamePattern,
     pub platform_suffix: PlatformSuffix,
+    /// Directory to write the SBOM files into, defaults to the directory of the manifest
+    pub output_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -245,6 +267,72 @@ pub enum Describe {
     AllCargoTargets,
 }
 
+/// A crate given by its name, or as `name@version` to match a single version of it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateSpec {
+    pub name: String,
+    pub version: Option<Version>,
+}
+
+impl std::fmt::Display for CrateSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{}@{}", self.name, version),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+impl CrateSpec {
+    pub fn matches(&self, package: &Package) -> bool {
+        package.name == self.name
+            && self
+                .version
+                .as_ref()
+                .map_or(true, |version| &package.version == version)
+    }
+}
+
+impl FromStr for CrateSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, version) = match s.split_once('@') {
+            Some((name, version)) => {
+                let version = Version::parse(version)
+                    .map_err(|error| format!("Invalid version in `{}`: {}", s, error))?;
+                (name, Some(version))
+            }
+            None => (s, None),
+        };
+        if name.is_empty() {
+            return Err(format!("Expected a crate name, got `{}`", s));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            version,
+        })
+    }
+}
+
+/// Existing SBOMs to combine with the generated one
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MergeOptions {
+    pub files: Vec<PathBuf>,
+    pub strategy: MergeStrategy,
+}
+
+/// Which SBOM wins when a component is present in both the generated and an existing SBOM?
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MergeStrategy {
+    /// Keep the generated components and drop the matching ones from the existing SBOMs. (default)
+    #[default]
+    PreferGenerated,
+    /// Keep the components from the existing SBOMs and drop the matching generated ones
+    PreferExisting,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -328,4 +416,31 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn it_should_parse_crate_specs() {
+        assert_eq!(
+            CrateSpec::from_str("serde"),
+            Ok(CrateSpec {
+                name: "serde".to_string(),
+                version: None,
+            })
+        );
+        assert_eq!(
+            CrateSpec::from_str("serde@1.0.193"),
+            Ok(CrateSpec {
+                name: "serde".to_string(),
+                version: Some(Version::new(1, 0, 193)),
+            })
+        );
+        assert!(CrateSpec::from_str("serde@1.0").is_err());
+        assert!(CrateSpec::from_str("@1.0.0").is_err());
+    }
+
+    #[test]
+    fn it_should_display_crate_specs_as_parsed() {
+        for spec in ["serde", "serde@1.0.193"] {
+            assert_eq!(CrateSpec::from_str(spec).unwrap().to_string(), spec);
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::config::CrateSpec;
 use crate::config::Describe;
 use std::cmp::min;
 use std::collections::HashSet;
@@ -19,6 +20,7 @@ use std::collections::HashSet;
  * SPDX-License-Identifier: Apache-2.0
  */
 use crate::config::FilenamePattern;
+use crate::config::MergeStrategy;
 use crate::config::PlatformSuffix;
 use crate::config::SbomConfig;
 use crate::config::{IncludedDependencies, ParseMode};
@@ -35,13 +37,15 @@ use cargo_metadata::PackageId;
 
 use cargo_lock::package::Checksum;
 use cargo_lock::Lockfile;
-use cargo_metadata::camino::Utf8PathBuf;
+use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use cyclonedx_bom::external_models::date_time::DateTime;
 use cyclonedx_bom::external_models::normalized_string::NormalizedString;
 use cyclonedx_bom::external_models::spdx::SpdxExpression;
 use cyclonedx_bom::external_models::uri::Uri;
 use cyclonedx_bom::models::attached_text::AttachedText;
-use cyclonedx_bom::models::bom::Bom;
+use cyclonedx_bom::models::bom::{Bom, BomReference, Format as BomFormat};
 use cyclonedx_bom::models::component::{Classification, Component, Components, Scope};
+use cyclonedx_bom::models::composition::{AggregateType, Composition, Compositions};
 use cyclonedx_bom::models::dependency::{Dependencies, Dependency};
 use cyclonedx_bom::models::external_reference::{
     ExternalReference, ExternalReferenceType, ExternalReferences,
@@ -49,7 +53,7 @@ use cyclonedx_bom::models::external_reference::{
 use cyclonedx_bom::models::license::{License, LicenseChoice, Licenses};
 use cyclonedx_bom::models::metadata::Metadata;
 use cyclonedx_bom::models::metadata::MetadataError;
-use cyclonedx_bom::models::organization::OrganizationalContact;
+use cyclonedx_bom::models::organization::{OrganizationalContact, OrganizationalEntity};
 use cyclonedx_bom::models::property::{Properties, Property};
 use cyclonedx_bom::models::tool::{Tool, Tools};
 use cyclonedx_bom::validation::Validate;
@@ -126,19 +130,53 @@ impl SbomGenerator {
         let packages = index_packages(meta.packages);
         let resolve = index_resolve(meta.resolve.unwrap().nodes);
 
+        if config.output_file.is_some() && members.len() > 1 {
+            return Err(GeneratorError::OutputFileError {
+                members: members.len(),
+            });
+        }
+        let output_options = config.output_options();
+        if output_options.output_dir.is_some()
+            && matches!(output_options.filename, FilenamePattern::Custom(_))
+            && members.len() > 1
+        {
+            return Err(GeneratorError::OutputDirFilenameError {
+                members: members.len(),
+            });
+        }
+
         let mut result = Vec::with_capacity(members.len());
         for member in members.iter() {
             log::trace!("Processing the package {}", member);
 
             let dep_kinds = index_dep_kinds(member, &resolve);
 
-            let (dependencies, pruned_resolve) =
+            let (mut dependencies, mut pruned_resolve) =
                 if config.included_dependencies() == IncludedDependencies::AllDependencies {
                     all_dependencies(member, &packages, &resolve, config)
                 } else {
                     top_level_dependencies(member, &packages, &resolve, config)
                 };
 
+            let mut optional_dependencies = if config.included_dependencies()
+                == IncludedDependencies::AllDependencies
+            {
+                inactive_optional_dependencies(dependencies.values(), &packages, &dep_kinds, config)
+            } else {
+                inactive_optional_dependencies([&packages[member]], &packages, &dep_kinds, config)
+            };
+
+            let excluded_any = match &config.exclude {
+                Some(excluded) => exclude_packages(
+                    member,
+                    excluded,
+                    &mut dependencies,
+                    &mut optional_dependencies,
+                    &mut pruned_resolve,
+                ),
+                None => false,
+            };
+
             let manifest_path = packages[member].manifest_path.clone().into_std_path_buf();
 
             let mut crate_hashes = HashMap::new();
@@ -161,8 +199,35 @@ impl SbomGenerator {
                 workspace_root: meta.workspace_root.to_owned(),
                 crate_hashes,
             };
-            let (bom, target_kinds) =
-                generator.create_bom(member, &dependencies, &pruned_resolve, &dep_kinds)?;
+            let (mut bom, target_kinds) = generator.create_bom(
+                member,
+                &dependencies,
+                &optional_dependencies,
+                &pruned_resolve,
+                &dep_kinds,
+                excluded_any,
+            )?;
+
+            if let Some(merge) = &config.merge {
+                for path in &merge.files {
+                    let existing = read_bom(path).map_err(|error| GeneratorError::MergeError {
+                        path: path.display().to_string(),
+                        error,
+                    })?;
+                    bom = merge_boms(bom, existing, merge.strategy);
+                }
+            }
+
+            if let Some(true) = config.deterministic {
+                bom.sort_components();
+                bom.sort_dependencies();
+                // A random serial number and the current time would differ between runs.
+                bom.serial_number = None;
+                let timestamp = pinned_timestamp(std::env::var("SOURCE_DATE_EPOCH").ok())?;
+                if let Some(metadata) = &mut bom.metadata {
+                    metadata.timestamp = timestamp;
+                }
+            }
 
             let generated = GeneratedSbom {
                 bom,
@@ -182,34 +247,78 @@ impl SbomGenerator {
         &self,
         package: &PackageId,
         packages: &PackageMap,
+        optional_packages: &PackageMap,
         resolve: &ResolveMap,
         dep_kinds: &DependencyKindMap,
+        excluded_any: bool,
     ) -> Result<(Bom, TargetKinds), GeneratorError> {
         let mut bom = Bom::default();
         let root_package = &packages[package];
 
+        let optional_components = optional_packages.values().map(|optional| {
+            let mut component = self.create_component(optional, root_package, dep_kinds, &[]);
+            component.scope = Some(Scope::Optional);
+            component
+        });
         let components: Vec<_> = packages
             .values()
             .filter(|p| &p.id != package)
-            .map(|component| self.create_component(component, root_package, dep_kinds))
+            .map(|component| {
+                let features = resolve
+                    .get(&component.id)
+                    .map_or(&[][..], |node| &node.features);
+                self.create_component(component, root_package, dep_kinds, features)
+            })
+            .chain(optional_components)
             .collect();
 
         bom.components = Some(Components(components));
 
-        let (metadata, target_kinds) = self.create_metadata(&packages[package])?;
+        let features = resolve.get(package).map_or(&[][..], |node| &node.features);
+        let (metadata, target_kinds) = self.create_metadata(&packages[package], features)?;
 
         bom.metadata = Some(metadata);
 
         bom.dependencies = Some(create_dependencies(resolve));
+        bom.compositions = Some(Compositions(vec![
+            self.create_composition(package, excluded_any)
+        ]));
 
         Ok((bom, target_kinds))
     }
 
+    /// Describes how complete the dependency graph of the root package is. The resolved graph is
+    /// complete unless it was cut off at the top level or `excluded_any` crates were dropped from
+    /// it. Leaving out build and dev-dependencies is reported as `incomplete_first_party_only`.
+    fn create_composition(&self, package: &PackageId, excluded_any: bool) -> Composition {
+        let trimmed = self.config.included_dependencies()
+            == IncludedDependencies::TopLevelDependencies
+            || excluded_any;
+        let aggregate = if trimmed {
+            AggregateType::Incomplete
+        } else if self.config.only_normal_deps == Some(true) {
+            AggregateType::IncompleteFirstPartyOnly
+        } else {
+            AggregateType::Complete
+        };
+
+        let root = BomReference::new(package);
+        Composition {
+            bom_ref: None,
+            aggregate,
+            assemblies: Some(vec![root.clone()]),
+            dependencies: Some(vec![root]),
+            vulnerabilities: None,
+            signature: None,
+        }
+    }
+
     fn create_component(
         &self,
         package: &Package,
         root_package: &Package,
         dep_kinds: &DependencyKindMap,
+        features: &[String],
     ) -> Component {
         let name = package.name.to_owned().trim().to_string();
         let version = package.version.to_string();
@@ -229,18 +338,29 @@ impl SbomGenerator {
             Some(package.id.to_string()),
         );
 
+        let dep_kind = dep_kinds.get(&package.id);
+
         component.purl = purl;
-        component.scope = match dep_kinds
-            .get(&package.id)
-            .unwrap_or(&DependencyKind::Normal)
-        {
+        component.scope = match dep_kind.unwrap_or(&DependencyKind::Normal) {
             DependencyKind::Normal => Some(Scope::Required),
             _ => Some(Scope::Excluded),
         };
         component.external_references = Self::get_external_references(package);
+        if let Some(true) = self.config.homepage_as_supplier {
+            component.supplier = Self::get_supplier(package);
+        }
         component.licenses = self.get_licenses(package);
         component.hashes = self.get_hashes(package);
 
+        let mut properties = cargo_properties(package, features);
+        if let Some(dep_kind) = dep_kind {
+            properties.push(Property::new(
+                "cdx:cargo:dependency_kind",
+                &dep_kind.to_string(),
+            ));
+        }
+        component.properties = Some(Properties(properties));
+
         component.description = package
             .description
             .as_ref()
@@ -256,8 +376,13 @@ impl SbomGenerator {
 
     /// Same as [Self::create_component] but also includes information
     /// on binaries and libraries comprising it as subcomponents
-    fn create_toplevel_component(&self, package: &Package) -> (Component, TargetKinds) {
-        let mut top_component = self.create_component(package, package, &DependencyKindMap::new());
+    fn create_toplevel_component(
+        &self,
+        package: &Package,
+        features: &[String],
+    ) -> (Component, TargetKinds) {
+        let mut top_component =
+            self.create_component(package, package, &DependencyKindMap::new(), features);
         let mut subcomponents: Vec<Component> = Vec::new();
         let mut target_kinds = HashMap::new();
         for tgt in filter_targets(&package.targets) {
@@ -343,11 +468,27 @@ impl SbomGenerator {
         Classification::Library
     }
 
+    /// Without a repository the homepage is the best hint of who publishes the crate, so it can
+    /// be recorded as the URL of the supplier, see `--homepage-as-supplier`.
+    fn get_supplier(package: &Package) -> Option<OrganizationalEntity> {
+        if package.repository.is_some() {
+            return None;
+        }
+        let homepage = Uri::try_from(package.homepage.as_ref()?.trim().to_string()).ok()?;
+
+        Some(OrganizationalEntity {
+            bom_ref: None,
+            name: None,
+            url: Some(vec![homepage]),
+            contact: None,
+        })
+    }
+
     fn get_external_references(package: &Package) -> Option<ExternalReferences> {
         let mut references = Vec::new();
 
         if let Some(documentation) = &package.documentation {
-            match Uri::try_from(documentation.to_string()) {
+            match Uri::try_from(documentation.trim().to_string()) {
                 Ok(uri) => references.push(ExternalReference::new(
                     ExternalReferenceType::Documentation,
                     uri,
@@ -362,7 +503,7 @@ impl SbomGenerator {
         }
 
         if let Some(website) = &package.homepage {
-            match Uri::try_from(website.to_string()) {
+            match Uri::try_from(website.trim().to_string()) {
                 Ok(uri) => {
                     references.push(ExternalReference::new(ExternalReferenceType::Website, uri))
                 }
@@ -376,7 +517,7 @@ impl SbomGenerator {
         }
 
         if let Some(other) = &package.links {
-            match Uri::try_from(other.to_string()) {
+            match Uri::try_from(other.trim().to_string()) {
                 Ok(uri) => {
                     references.push(ExternalReference::new(ExternalReferenceType::Other, uri))
                 }
@@ -390,7 +531,7 @@ impl SbomGenerator {
         }
 
         if let Some(vcs) = &package.repository {
-            match Uri::try_from(vcs.to_string()) {
+            match Uri::try_from(vcs.trim().to_string()) {
                 Ok(uri) => references.push(ExternalReference::new(ExternalReferenceType::Vcs, uri)),
                 Err(e) => log::warn!(
                     "Package {} has an invalid repository URI ({}): {} ",
@@ -504,6 +645,7 @@ impl SbomGenerator {
     fn create_metadata(
         &self,
         package: &Package,
+        features: &[String],
     ) -> Result<(Metadata, TargetKinds), GeneratorError> {
         let authors = Self::create_authors(package);
 
@@ -512,10 +654,21 @@ impl SbomGenerator {
             metadata.authors = Some(authors);
         }
 
-        let (mut component, target_kinds) = self.create_toplevel_component(package);
+        let (mut component, target_kinds) = self.create_toplevel_component(package, features);
 
         component.component_type = Self::get_classification(package);
 
+        if let Some(true) = self.config.include_vcs {
+            let vcs_properties = vcs_properties(package.manifest_path.parent().unwrap());
+            if !vcs_properties.is_empty() {
+                component
+                    .properties
+                    .get_or_insert_with(|| Properties(Vec::new()))
+                    .0
+                    .extend(vcs_properties);
+            }
+        }
+
         metadata.component = Some(component);
 
         let tool = Tool::new("CycloneDX", "cargo-cyclonedx", env!("CARGO_PKG_VERSION"));
@@ -675,6 +828,124 @@ pub enum GeneratorError {
 
     #[error("Could not parse author string: {}", .0)]
     AuthorParseError(String),
+
+    #[error("Cannot write the SBOMs of {members} workspace members to a single output file")]
+    OutputFileError { members: usize },
+
+    #[error(
+        "Cannot write the SBOMs of {members} workspace members to the same filename in one output directory"
+    )]
+    OutputDirFilenameError { members: usize },
+
+    #[error("SOURCE_DATE_EPOCH must be a number of seconds since the Unix epoch, got {0:?}")]
+    SourceDateEpochError(String),
+
+    #[error("Could not merge the existing SBOM: {path}")]
+    MergeError {
+        path: String,
+        #[source]
+        error: anyhow::Error,
+    },
+}
+
+/// The timestamp of a deterministic SBOM: the time given by `SOURCE_DATE_EPOCH`, see
+/// <https://reproducible-builds.org/specs/source-date-epoch/>, or none at all.
+fn pinned_timestamp(source_date_epoch: Option<String>) -> Result<Option<DateTime>, GeneratorError> {
+    let Some(source_date_epoch) = source_date_epoch else {
+        return Ok(None);
+    };
+    source_date_epoch
+        .trim()
+        .parse()
+        .ok()
+        .and_then(|seconds| DateTime::from_unix_timestamp(seconds).ok())
+        .map(Some)
+        .ok_or(GeneratorError::SourceDateEpochError(source_date_epoch))
+}
+
+/// Reads an existing SBOM, picking JSON or XML based on the file extension and detecting the
+/// format of other files from their content.
+fn read_bom(path: &Path) -> anyhow::Result<Bom> {
+    let format = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("xml") => Some(BomFormat::Xml),
+        Some("json") => Some(BomFormat::Json),
+        _ => None,
+    };
+    Ok(Bom::parse_from_reader(File::open(path)?, format)?)
+}
+
+/// Combines the generated SBOM with an existing one. The metadata and serial number of the
+/// generated SBOM are always kept.
+fn merge_boms(mut generated: Bom, mut existing: Bom, strategy: MergeStrategy) -> Bom {
+    match strategy {
+        MergeStrategy::PreferGenerated => {
+            generated.merge(existing);
+            generated
+        }
+        MergeStrategy::PreferExisting => {
+            existing.metadata = generated.metadata.take();
+            existing.serial_number = generated.serial_number.take();
+            existing.version = generated.version;
+            existing.merge(generated);
+            existing
+        }
+    }
+}
+
+/// Describes where `package` comes from as `cdx:cargo:source` property, i.e. `crates.io`, the
+/// URL of another registry or git repository or `path` for local packages. The enabled features
+/// are added as comma-separated `cdx:cargo:features` property. A `license-file` given instead of
+/// a license expression is recorded as `cdx:cargo:licenseFile` property.
+fn cargo_properties(package: &Package, features: &[String]) -> Vec<Property> {
+    let source = match &package.source {
+        Some(source) if source.is_crates_io() => "crates.io",
+        Some(source) => match source.repr.split_once('+') {
+            Some(("git" | "registry" | "sparse", url)) => url,
+            _ => source.repr.as_str(),
+        },
+        None => "path",
+    };
+
+    let mut properties = vec![Property::new("cdx:cargo:source", source)];
+    if !features.is_empty() {
+        properties.push(Property::new("cdx:cargo:features", &features.join(",")));
+    }
+    if let (None, Some(license_file)) = (&package.license, &package.license_file) {
+        properties.push(Property::new(
+            "cdx:cargo:licenseFile",
+            license_file.as_str(),
+        ));
+    }
+    properties
+}
+
+/// Describes the git checkout containing `directory` as `cdx:vcs:commit` and `cdx:vcs:describe`
+/// properties. Returns no properties if `directory` is not part of a git repository.
+fn vcs_properties(directory: &Utf8Path) -> Vec<Property> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(directory)
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        output.status.success().then(|| stdout.trim().to_string())
+    };
+
+    let Some(commit) = git(&["rev-parse", "HEAD"]) else {
+        log::warn!(
+            "Could not determine the git commit of {}, is it a git repository?",
+            directory
+        );
+        return Vec::new();
+    };
+
+    let mut properties = vec![Property::new("cdx:vcs:commit", &commit)];
+    match git(&["describe", "--tags"]) {
+        Some(describe) => properties.push(Property::new("cdx:vcs:describe", &describe)),
+        None => log::debug!("No git tag found for {}", directory),
+    }
+    properties
 }
 
 /// Generates the `Dependencies` field in the final SBOM
@@ -767,6 +1038,104 @@ fn all_dependencies(
     (out_packages, out_resolve)
 }
 
+/// Finds the optional dependencies declared by `declaring` packages whose feature is not enabled.
+/// `cargo metadata` still lists these packages, but no resolved dependency edge leads to them.
+/// Packages `reachable` through another dependency are built anyway and thus never optional,
+/// even when they are left out of the SBOM with `--top-level`.
+fn inactive_optional_dependencies<'a>(
+    declaring: impl IntoIterator<Item = &'a Package>,
+    packages: &PackageMap,
+    reachable: &DependencyKindMap,
+    config: &SbomConfig,
+) -> PackageMap {
+    let mut optional = PackageMap::new();
+    for dependency in declaring
+        .into_iter()
+        .flat_map(|package| &package.dependencies)
+        .filter(|dependency| dependency.optional)
+    {
+        let kind_included = if let Some(true) = config.only_normal_deps {
+            dependency.kind == DependencyKind::Normal
+        } else {
+            dependency.kind != DependencyKind::Development
+        };
+        if !kind_included {
+            continue;
+        }
+
+        for package in packages.values().filter(|package| {
+            package.name == dependency.name
+                && dependency.req.matches(&package.version)
+                && !reachable.contains_key(&package.id)
+        }) {
+            optional.insert(package.id.to_owned(), package.to_owned());
+        }
+    }
+    optional
+}
+
+/// Drops the packages matching one of the `excluded` crates, along with the dependency edges
+/// leading to them. The root package is always kept. Returns whether any package was dropped
+/// from the dependency graph.
+fn exclude_packages(
+    root: &PackageId,
+    excluded: &[CrateSpec],
+    packages: &mut PackageMap,
+    optional_packages: &mut PackageMap,
+    resolve: &mut ResolveMap,
+) -> bool {
+    let is_excluded = |package: &Package| {
+        &package.id != root && excluded.iter().any(|spec| spec.matches(package))
+    };
+    for spec in excluded {
+        let matched = packages
+            .values()
+            .chain(optional_packages.values())
+            .any(|package| &package.id != root && spec.matches(package));
+        if !matched {
+            log::warn!("Cannot exclude {}, no dependency matches it", spec);
+        }
+    }
+    optional_packages.retain(|_, package| !is_excluded(package));
+
+    let excluded_ids: HashSet<PackageId> = packages
+        .values()
+        .filter(|package| is_excluded(package))
+        .map(|package| package.id.to_owned())
+        .collect();
+    if excluded_ids.is_empty() {
+        return false;
+    }
+
+    for id in &excluded_ids {
+        log::debug!("Excluding {} from the SBOM", id);
+        let has_dependencies = resolve
+            .get(id)
+            .is_some_and(|node| !node.dependencies.is_empty());
+        let dependents: Vec<_> = resolve
+            .values()
+            .filter(|node| !excluded_ids.contains(&node.id) && node.dependencies.contains(id))
+            .map(|node| packages[&node.id].name.as_str())
+            .collect();
+        if has_dependencies && !dependents.is_empty() {
+            log::warn!(
+                "Excluding {} {} breaks the dependency chain from {} to its own dependencies",
+                packages[id].name,
+                packages[id].version,
+                dependents.join(", ")
+            );
+        }
+    }
+
+    packages.retain(|id, _| !excluded_ids.contains(id));
+    resolve.retain(|id, _| !excluded_ids.contains(id));
+    for node in resolve.values_mut() {
+        node.deps.retain(|dep| !excluded_ids.contains(&dep.pkg));
+        node.dependencies.retain(|id| !excluded_ids.contains(id));
+    }
+    true
+}
+
 fn add_filtered_dependencies(node: &Node, config: &SbomConfig) -> Node {
     let mut node = node.clone();
     node.deps = filtered_dependencies(&node.deps, config).cloned().collect();
@@ -808,11 +1177,24 @@ pub struct GeneratedSbom {
 }
 
 impl GeneratedSbom {
-    /// Writes SBOM to either a JSON or XML file in the same folder as `Cargo.toml` manifest
+    /// Writes SBOM to either a JSON or XML file in the same folder as `Cargo.toml` manifest,
+    /// or to the configured output file
     pub fn write_to_files(self) -> Result<(), SbomWriterError> {
+        match &self.sbom_config.output_file {
+            Some(path) if path.as_os_str() == "-" => {
+                return Self::write_to_stdout(self.bom, &self.sbom_config)
+            }
+            Some(path) => return Self::write_to_file(self.bom, path, &self.sbom_config),
+            None => {}
+        }
+
+        if let Some(output_dir) = &self.sbom_config.output_options().output_dir {
+            std::fs::create_dir_all(output_dir)?;
+        }
+
         match self.sbom_config.describe.unwrap_or_default() {
             Describe::Crate => {
-                let path = self.manifest_path.with_file_name(self.filename(None, &[]));
+                let path = self.output_path(self.filename(None, &[]));
                 Self::write_to_file(self.bom, &path, &self.sbom_config)
             }
             pattern @ (Describe::Binaries | Describe::AllCargoTargets) => {
@@ -821,9 +1203,7 @@ impl GeneratedSbom {
                 {
                     let meta = sbom.metadata.as_ref().unwrap();
                     let name = meta.component.as_ref().unwrap().name.as_ref();
-                    let path = self
-                        .manifest_path
-                        .with_file_name(self.filename(Some(name), &target_kind));
+                    let path = self.output_path(self.filename(Some(name), &target_kind));
                     Self::write_to_file(sbom, &path, &self.sbom_config)?;
                 }
                 Ok(())
@@ -831,7 +1211,33 @@ impl GeneratedSbom {
         }
     }
 
+    /// Places the file in the configured output directory, or next to the manifest by default.
+    fn output_path(&self, filename: String) -> PathBuf {
+        match &self.sbom_config.output_options().output_dir {
+            Some(output_dir) => output_dir.join(filename),
+            None => self.manifest_path.with_file_name(filename),
+        }
+    }
+
     fn write_to_file(bom: Bom, path: &Path, config: &SbomConfig) -> Result<(), SbomWriterError> {
+        log::info!("Outputting {}", path.display());
+        write_atomically(path, |writer| Self::write_bom(bom, writer, config))
+    }
+
+    fn write_to_stdout(bom: Bom, config: &SbomConfig) -> Result<(), SbomWriterError> {
+        log::info!("Outputting to stdout");
+        let mut writer = BufWriter::new(std::io::stdout().lock());
+        Self::write_bom(bom, &mut writer, config)?;
+        // Flush the writer explicitly to catch and report any I/O errors
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn write_bom(
+        bom: Bom,
+        writer: &mut impl Write,
+        config: &SbomConfig,
+    ) -> Result<(), SbomWriterError> {
         // If running in debug mode, validate that the SBOM is self-consistent and well-formed
         if cfg!(debug_assertions) {
             let result = bom.validate();
@@ -846,24 +1252,14 @@ impl GeneratedSbom {
         use cyclonedx_bom::models::bom::SpecVersion::*;
         let spec_version = config.spec_version.unwrap_or(V1_3);
 
-        log::info!("Outputting {}", path.display());
-        let file = File::create(path)?;
-        let mut writer = BufWriter::new(file);
         match config.format() {
-            Format::Json => {
-                bom.output_as_json(&mut writer, spec_version)
-                    .map_err(SbomWriterError::JsonWriteError)?;
-            }
-            Format::Xml => {
-                bom.output_as_xml(&mut writer, spec_version)
-                    .map_err(SbomWriterError::XmlWriteError)?;
-            }
+            Format::Json => bom
+                .output_as_json(writer, spec_version)
+                .map_err(SbomWriterError::JsonWriteError),
+            Format::Xml => bom
+                .output_as_xml(writer, spec_version)
+                .map_err(SbomWriterError::XmlWriteError),
         }
-
-        // Flush the writer explicitly to catch and report any I/O errors
-        writer.flush()?;
-
-        Ok(())
     }
 
     /// Returns an iterator over SBOMs and their associated target kinds
@@ -1016,6 +1412,34 @@ fn to_bom_hash(hash: &Checksum) -> cyclonedx_bom::models::hash::Hash {
     }
 }
 
+/// Writes the file next to `path` under a temporary name and renames it once `write` succeeded,
+/// so that a failure never leaves a partially written SBOM behind or clobbers an existing one.
+fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> Result<(), SbomWriterError>,
+) -> Result<(), SbomWriterError> {
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(path.file_name().unwrap_or_default());
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let result = File::create(&temp_path)
+        .map_err(SbomWriterError::from)
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            write(&mut writer)?;
+            // Flush the writer explicitly to catch and report any I/O errors
+            writer.flush()?;
+            Ok(())
+        })
+        .and_then(|()| Ok(std::fs::rename(&temp_path, path)?));
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
 #[derive(Error, Debug)]
 pub enum SbomWriterError {
     #[error("I/O error")]
@@ -1040,6 +1464,155 @@ impl From<std::io::Error> for SbomWriterError {
 #[cfg(test)]
 mod test {
     use super::*;
+    use cyclonedx_bom::external_models::uri::Purl;
+    use cyclonedx_bom::models::bom::SpecVersion;
+
+    #[test]
+    fn it_should_not_leave_a_partial_file_behind_when_writing_fails() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("bom.json");
+        std::fs::write(&path, "previous").unwrap();
+
+        let result = write_atomically(&path, |writer| {
+            writer.write_all(b"{\"bomFormat\":")?;
+            writer.flush()?;
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "simulated failure").into())
+        });
+
+        assert!(matches!(result, Err(SbomWriterError::IoError(_))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        write_atomically(&path, |writer| Ok(writer.write_all(b"{}")?)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{}");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn it_should_pin_the_timestamp_to_source_date_epoch() {
+        assert_eq!(pinned_timestamp(None).unwrap(), None);
+        assert_eq!(
+            pinned_timestamp(Some("1700000000".to_string()))
+                .unwrap()
+                .map(|timestamp| timestamp.to_string()),
+            Some("2023-11-14T22:13:20Z".to_string())
+        );
+        assert!(matches!(
+            pinned_timestamp(Some("yesterday".to_string())),
+            Err(GeneratorError::SourceDateEpochError(value)) if value == "yesterday"
+        ));
+    }
+
+    #[test]
+    fn it_should_describe_the_source_and_features_of_a_package() {
+        let properties = |json: &str, features: &[String]| {
+            let package: Package = serde_json::from_str(json).unwrap();
+            cargo_properties(&package, features)
+                .into_iter()
+                .map(|property| (property.name, property.value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            properties(
+                include_str!("../tests/fixtures/crates_io_package.json"),
+                &["default".to_string(), "std".to_string()]
+            ),
+            [
+                ("cdx:cargo:source".to_string(), "crates.io".to_string()),
+                ("cdx:cargo:features".to_string(), "default,std".to_string()),
+            ]
+        );
+        assert_eq!(
+            properties(include_str!("../tests/fixtures/git_package.json"), &[]),
+            [(
+                "cdx:cargo:source".to_string(),
+                "https://github.com/rust-secure-code/cargo-auditable.git#da85607fb1a09435d77288ccf05a92b2e8ec3f71".to_string()
+            )]
+        );
+        assert_eq!(
+            properties(
+                include_str!("../tests/fixtures/workspace_package.json"),
+                &[]
+            ),
+            [("cdx:cargo:source".to_string(), "path".to_string())]
+        );
+    }
+
+    #[test]
+    fn it_should_detect_the_spec_version_of_an_xml_sbom_from_its_namespace() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("existing.cdx.xml");
+        std::fs::write(
+            &path,
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+                "<!-- converted from http://cyclonedx.org/schema/bom/1.3 -->\n",
+                "<bom xmlns=\"http://cyclonedx.org/schema/bom/1.5\" version=\"1\" />",
+            ),
+        )
+        .unwrap();
+
+        let bom = read_bom(&path).unwrap();
+
+        assert_eq!(bom.spec_version, SpecVersion::V1_5);
+    }
+
+    #[test]
+    fn it_should_keep_the_existing_components_when_preferring_the_existing_sbom() {
+        let component = |name: &str, description: &str| {
+            let mut component = Component::new(
+                Classification::Library,
+                name,
+                "1.0.0",
+                Some(format!("{name}-ref")),
+            );
+            component.purl = Some(Purl::cargo(name, "1.0.0").unwrap());
+            component.description = Some(NormalizedString::new(description));
+            component
+        };
+        let generated = Bom {
+            metadata: Some(Metadata {
+                component: Some(component("app", "generated")),
+                ..Metadata::default()
+            }),
+            components: Some(Components(vec![component("shared", "generated")])),
+            ..Bom::default()
+        };
+        let serial_number = generated.serial_number.clone();
+        let existing = Bom {
+            components: Some(Components(vec![
+                component("shared", "existing"),
+                component("other", "existing"),
+            ])),
+            ..Bom::default()
+        };
+
+        let merged = merge_boms(generated, existing, MergeStrategy::PreferExisting);
+
+        let components = merged
+            .components
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|component| {
+                (
+                    component.name.to_string(),
+                    component.description.unwrap().to_string(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            components,
+            [
+                ("shared".to_string(), "existing".to_string()),
+                ("other".to_string(), "existing".to_string()),
+            ]
+        );
+        let metadata_component = merged.metadata.unwrap().component.unwrap();
+        assert_eq!(metadata_component.name.to_string(), "app");
+        assert_eq!(merged.serial_number, serial_number);
+    }
 
     #[test]
     fn it_should_parse_author_and_email() {
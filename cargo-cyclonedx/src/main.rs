@@ -100,7 +100,15 @@ fn main() -> anyhow::Result<()> {
 fn setup_logging(args: &Args) -> anyhow::Result<()> {
     let mut builder = Builder::new();
 
-    let level_filter = if args.quiet >= 2 {
+    builder.filter_level(log_level_filter(args));
+    builder.parse_default_env(); // allow overriding CLI arguments
+    builder.try_init()?;
+
+    Ok(())
+}
+
+fn log_level_filter(args: &Args) -> LevelFilter {
+    if args.quiet >= 2 {
         LevelFilter::Off
     } else {
         match args.verbose {
@@ -109,12 +117,12 @@ fn setup_logging(args: &Args) -> anyhow::Result<()> {
             2 => LevelFilter::Debug,
             _ => LevelFilter::Trace,
         }
-    };
-    builder.filter_level(level_filter);
-    builder.parse_default_env(); // allow overriding CLI arguments
-    builder.try_init()?;
+    }
+}
 
-    Ok(())
+/// Whether the progress reports of `cargo metadata` are shown, any `-q` disables them.
+fn forward_cargo_progress(args: &Args) -> bool {
+    args.quiet == 0
 }
 
 fn locate_manifest(args: &Args) -> Result<PathBuf, io::Error> {
@@ -157,9 +165,9 @@ fn get_metadata(
         }
     }
 
-    if args.quiet == 0 {
+    if forward_cargo_progress(args) {
         // Contrary to the name, this does not enable verbose output.
-        // It merely forwards the cargo stdout to our stdout,
+        // It merely forwards the cargo stderr to our stderr,
         // so that `cargo metadata` can show a progressbar on long-running operations.
         cmd.verbose(true);
     }
@@ -175,6 +183,24 @@ fn get_metadata(
 mod tests {
     use cyclonedx_bom::prelude::NormalizedString;
 
+    #[test]
+    fn quiet_and_verbose_flags_set_the_log_level() {
+        use crate::{cli, forward_cargo_progress, log_level_filter};
+        use clap::Parser;
+        use log::LevelFilter;
+
+        let args = |flags: &[&str]| cli::Args::parse_from(["cyclonedx"].iter().chain(flags));
+
+        assert_eq!(log_level_filter(&args(&[])), LevelFilter::Warn);
+        assert_eq!(log_level_filter(&args(&["-vv"])), LevelFilter::Debug);
+        assert_eq!(log_level_filter(&args(&["-q"])), LevelFilter::Warn);
+        assert_eq!(log_level_filter(&args(&["-qq", "-vvv"])), LevelFilter::Off);
+
+        assert!(forward_cargo_progress(&args(&[])));
+        assert!(!forward_cargo_progress(&args(&["-q"])));
+        assert!(!forward_cargo_progress(&args(&["-qq"])));
+    }
+
     #[test]
     fn parse_toml_only_normal() {
         use crate::cli;
@@ -199,6 +225,46 @@ mod tests {
             .all(|f| f.scope == Some(Scope::Required)));
     }
 
+    #[test]
+    fn parse_toml_compositions() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use cyclonedx_bom::models::composition::AggregateType;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/build_then_runtime_dep/Cargo.toml");
+
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let aggregate = |args: &[&str]| {
+            let args = ["cyclonedx", path_arg.as_str()]
+                .into_iter()
+                .chain(args.iter().copied());
+            let sboms = generate_sboms(&cli::Args::parse_from(args)).unwrap();
+
+            let bom = &sboms[0].bom;
+            let root_ref = bom.metadata.as_ref().unwrap().component.as_ref().unwrap();
+            let root_ref = root_ref.bom_ref.as_ref().unwrap();
+            let compositions = &bom.compositions.as_ref().unwrap().0;
+            assert_eq!(compositions.len(), 1);
+            let assemblies = compositions[0].assemblies.as_ref().unwrap();
+            assert_eq!(assemblies[0].0, *root_ref);
+            compositions[0].aggregate.clone()
+        };
+
+        assert_eq!(aggregate(&[]), AggregateType::Complete);
+        assert_eq!(
+            aggregate(&["--no-build-deps"]),
+            AggregateType::IncompleteFirstPartyOnly
+        );
+        // Excluding a crate only cuts the graph if a dependency matches it
+        assert_eq!(
+            aggregate(&["--exclude=not_a_dependency"]),
+            AggregateType::Complete
+        );
+    }
+
     #[test]
     fn parse_toml_with_excluded() {
         use crate::cli;
@@ -228,4 +294,505 @@ mod tests {
             != NormalizedString::new("runtime_dep_of_build_dep")
             || c.scope == Some(Scope::Excluded)));
     }
+
+    #[test]
+    fn parse_toml_optional_dependency() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use cyclonedx_bom::models::component::Scope;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/optional_dep/Cargo.toml");
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+
+        let optional_lib_scope = |args: &[&str]| {
+            let args_parsed = cli::Args::parse_from(args.iter());
+            let sboms = generate_sboms(&args_parsed).unwrap();
+            let components = sboms[0].bom.components.as_ref().unwrap();
+            assert_eq!(components.0.len(), 1);
+            assert_eq!(components.0[0].name.to_string(), "optional_lib");
+            components.0[0].scope.clone()
+        };
+
+        assert_eq!(
+            optional_lib_scope(&["cyclonedx", path_arg]),
+            Some(Scope::Optional)
+        );
+        assert_eq!(
+            optional_lib_scope(&["cyclonedx", path_arg, "--features=optional_lib"]),
+            Some(Scope::Required)
+        );
+        assert_eq!(
+            optional_lib_scope(&["cyclonedx", path_arg, "--all-features"]),
+            Some(Scope::Required)
+        );
+    }
+
+    #[test]
+    fn parse_toml_optional_dependency_that_is_also_required() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use cyclonedx_bom::models::component::Scope;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/optional_and_required_dep/Cargo.toml");
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+
+        let scopes = |args: &[&str]| {
+            let args_parsed = cli::Args::parse_from(args.iter());
+            let sboms = generate_sboms(&args_parsed).unwrap();
+            sboms[0]
+                .bom
+                .components
+                .as_ref()
+                .unwrap()
+                .0
+                .iter()
+                .map(|component| (component.name.to_string(), component.scope.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let required = |name: &str| (name.to_string(), Some(Scope::Required));
+        assert_eq!(
+            scopes(&["cyclonedx", path_arg]),
+            [required("hard_lib"), required("shared_lib")]
+        );
+        assert_eq!(
+            scopes(&["cyclonedx", path_arg, "--all-features"]),
+            [required("hard_lib"), required("shared_lib")]
+        );
+        assert_eq!(
+            scopes(&["cyclonedx", path_arg, "--top-level"]),
+            [required("hard_lib")]
+        );
+    }
+
+    #[test]
+    fn parse_toml_with_mixed_dependency_kinds() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/mixed_dep_kinds/Cargo.toml");
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        let dep_kinds: Vec<_> = sboms[0]
+            .bom
+            .components
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .map(|component| {
+                let dep_kind = component
+                    .properties
+                    .iter()
+                    .flat_map(|properties| &properties.0)
+                    .find(|property| property.name == "cdx:cargo:dependency_kind")
+                    .map(|property| property.value.to_string());
+                (component.name.to_string(), dep_kind)
+            })
+            .collect();
+        // dev-dependencies are not part of the SBOM at all
+        let dep_kind = |name: &str, kind: &str| (name.to_string(), Some(kind.to_string()));
+        assert_eq!(
+            dep_kinds,
+            [
+                dep_kind("build_lib", "build"),
+                dep_kind("normal_lib", "normal"),
+            ]
+        );
+
+        let top_level = sboms[0].bom.metadata.as_ref().unwrap().component.as_ref();
+        let top_level_properties = top_level.unwrap().properties.as_ref().unwrap();
+        assert!(top_level_properties
+            .0
+            .iter()
+            .all(|property| property.name != "cdx:cargo:dependency_kind"));
+    }
+
+    #[test]
+    fn parse_toml_with_licensed_dependencies() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use cyclonedx_bom::external_models::spdx::SpdxExpression;
+        use cyclonedx_bom::models::license::{LicenseChoice, LicenseIdentifier};
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/licensed_deps/Cargo.toml");
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        let components = &sboms[0].bom.components.as_ref().unwrap().0;
+        let dual_licensed = components
+            .iter()
+            .find(|component| component.name.to_string() == "dual_licensed")
+            .unwrap();
+        assert_eq!(
+            dual_licensed.licenses.as_ref().unwrap().0,
+            [LicenseChoice::Expression(SpdxExpression::new(
+                "MIT OR Apache-2.0"
+            ))]
+        );
+
+        let file_licensed = components
+            .iter()
+            .find(|component| component.name.to_string() == "file_licensed")
+            .unwrap();
+        let license_file = file_licensed
+            .properties
+            .iter()
+            .flat_map(|properties| &properties.0)
+            .find(|property| property.name == "cdx:cargo:licenseFile")
+            .map(|property| property.value.to_string());
+        assert_eq!(license_file.as_deref(), Some("LICENSE.txt"));
+        match &file_licensed.licenses.as_ref().unwrap().0[..] {
+            [LicenseChoice::License(license)] => {
+                assert_eq!(
+                    license.license_identifier,
+                    LicenseIdentifier::Name("Unknown".into())
+                );
+                assert!(license.text.is_some());
+            }
+            other => panic!("Expected the license text, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_toml_package_description_and_repository() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use cyclonedx_bom::models::external_reference::ExternalReferenceType;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/package_metadata/Cargo.toml");
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        let components = sboms[0].bom.components.as_ref().unwrap();
+        assert_eq!(components.0.len(), 1);
+        let component = &components.0[0];
+        assert_eq!(
+            component.description.as_ref().unwrap().to_string(),
+            "A library with package metadata"
+        );
+        let vcs = component
+            .external_references
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .find(|reference| reference.external_reference_type == ExternalReferenceType::Vcs)
+            .expect("Missing vcs external reference");
+        assert_eq!(
+            vcs.url.to_string(),
+            "https://github.com/example/described_lib"
+        );
+        let source = component
+            .properties
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .find(|property| property.name == "cdx:cargo:source")
+            .expect("Missing cdx:cargo:source property");
+        assert_eq!(source.value.to_string(), "path");
+    }
+
+    #[test]
+    fn parse_toml_package_with_only_a_homepage() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use cyclonedx_bom::models::external_reference::ExternalReferenceType;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/homepage_only/Cargo.toml");
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        let components = sboms[0].bom.components.as_ref().unwrap();
+        assert_eq!(components.0.len(), 1);
+        let component = &components.0[0];
+        assert_eq!(component.supplier, None);
+        let references = &component.external_references.as_ref().unwrap().0;
+        assert_eq!(references.len(), 1);
+        assert_eq!(
+            references[0].external_reference_type,
+            ExternalReferenceType::Website
+        );
+        assert_eq!(
+            references[0].url.to_string(),
+            "https://example.com/homepage_lib"
+        );
+
+        let args = ["cyclonedx", path_arg, "--homepage-as-supplier"];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        let component = &sboms[0].bom.components.as_ref().unwrap().0[0];
+        let supplier_urls = component.supplier.as_ref().unwrap().url.as_ref().unwrap();
+        assert_eq!(
+            supplier_urls
+                .iter()
+                .map(|url| url.to_string())
+                .collect::<Vec<_>>(),
+            ["https://example.com/homepage_lib"]
+        );
+    }
+
+    #[test]
+    fn parse_toml_merge_existing_sbom() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/transitive_deps/Cargo.toml");
+        let mut existing_sbom = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        existing_sbom.push("tests/fixtures/merge/existing.cdx.json");
+
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let merge_arg = &format!("--merge={}", existing_sbom.display());
+        let args = ["cyclonedx", path_arg, merge_arg];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        let bom = &sboms[0].bom;
+        let mut names: Vec<_> = bom
+            .components
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .map(|component| component.name.to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["direct_dep", "transitive_dep", "zlib"]);
+        assert!(bom
+            .dependencies
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .any(|dependency| dependency.dependency_ref == "pkg:generic/zlib@1.3.1"));
+        assert_eq!(
+            bom.metadata
+                .as_ref()
+                .unwrap()
+                .component
+                .as_ref()
+                .unwrap()
+                .name
+                .to_string(),
+            "app"
+        );
+    }
+
+    #[test]
+    fn parse_toml_deterministic() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/transitive_deps/Cargo.toml");
+
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg, "--deterministic"];
+        let generate = || {
+            let sboms = generate_sboms(&cli::Args::parse_from(args.iter())).unwrap();
+            let mut output = Vec::new();
+            sboms[0]
+                .bom
+                .clone()
+                .output_as_json_v1_5(&mut output)
+                .unwrap();
+            output
+        };
+
+        let first = generate();
+        let second = generate();
+
+        assert_eq!(
+            String::from_utf8(first).unwrap(),
+            String::from_utf8(second).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_toml_top_level_only() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/transitive_deps/Cargo.toml");
+
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg, "--top-level-only"];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        // transitive_dep is only reachable through direct_dep -> excluded
+        let components = sboms[0].bom.components.as_ref().unwrap();
+        let names: Vec<_> = components.0.iter().map(|c| c.name.to_string()).collect();
+        assert_eq!(names, vec!["direct_dep"]);
+
+        // The dependency graph is truncated after the first level
+        let dependencies = sboms[0].bom.dependencies.as_ref().unwrap();
+        let direct_dep_ref = components.0[0].bom_ref.as_ref().unwrap();
+        let direct_dep = dependencies
+            .0
+            .iter()
+            .find(|d| &d.dependency_ref == direct_dep_ref)
+            .unwrap();
+        assert!(direct_dep.dependencies.is_empty());
+    }
+
+    #[test]
+    fn parse_toml_exclude_leaf() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use clap::Parser;
+        use cyclonedx_bom::models::composition::AggregateType;
+        use std::path::PathBuf;
+
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/transitive_deps/Cargo.toml");
+
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg, "--exclude=transitive_dep"];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+
+        let components = sboms[0].bom.components.as_ref().unwrap();
+        let names: Vec<_> = components.0.iter().map(|c| c.name.to_string()).collect();
+        assert_eq!(names, vec!["direct_dep"]);
+
+        // No dangling references to the excluded crate are left behind
+        let root_ref = sboms[0].bom.metadata.as_ref().unwrap();
+        let root_ref = root_ref.component.as_ref().unwrap().bom_ref.as_ref();
+        let known_refs: Vec<_> = components
+            .0
+            .iter()
+            .filter_map(|c| c.bom_ref.as_ref())
+            .chain(root_ref)
+            .collect();
+        let dependencies = sboms[0].bom.dependencies.as_ref().unwrap();
+        for dependency in &dependencies.0 {
+            assert!(known_refs.contains(&&dependency.dependency_ref));
+            assert!(dependency
+                .dependencies
+                .iter()
+                .all(|d| known_refs.contains(&d)));
+        }
+        let direct_dep_ref = components.0[0].bom_ref.as_ref().unwrap();
+        let direct_dep = dependencies
+            .0
+            .iter()
+            .find(|d| &d.dependency_ref == direct_dep_ref)
+            .unwrap();
+        assert!(direct_dep.dependencies.is_empty());
+
+        let compositions = &sboms[0].bom.compositions.as_ref().unwrap().0;
+        assert_eq!(compositions[0].aggregate, AggregateType::Incomplete);
+    }
+
+    #[test]
+    fn parse_toml_output_file() {
+        use crate::cli;
+        use crate::generate_sboms;
+        use assert_fs::prelude::*;
+        use cargo_cyclonedx::generator::GeneratorError;
+        use clap::Parser;
+        use std::path::PathBuf;
+
+        let tmp_dir = assert_fs::TempDir::new().unwrap();
+        tmp_dir.child("src/main.rs").touch().unwrap();
+        tmp_dir
+            .child("Cargo.toml")
+            .write_str(r#"package = { name = "pkg", version = "0.0.0" }"#)
+            .unwrap();
+        let output_file = tmp_dir.child("custom.cdx.json");
+
+        let path_arg = &format!("--manifest-path={}/Cargo.toml", tmp_dir.path().display());
+        let output_arg = &format!("--output-file={}", output_file.path().display());
+        let args = ["cyclonedx", path_arg, output_arg];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let sboms = generate_sboms(&args_parsed).unwrap();
+        assert_eq!(sboms.len(), 1);
+        for sbom in sboms {
+            sbom.write_to_files().unwrap();
+        }
+
+        let bom: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(output_file.path()).unwrap()).unwrap();
+        assert_eq!(bom["metadata"]["component"]["name"], "pkg");
+        tmp_dir
+            .child("pkg.cdx.xml")
+            .assert(predicates::path::missing());
+
+        // A single output file cannot hold the SBOMs of several workspace members
+        let mut test_cargo_toml = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        test_cargo_toml.push("tests/fixtures/build_then_runtime_dep/Cargo.toml");
+
+        let path_arg = &format!("--manifest-path={}", test_cargo_toml.display());
+        let args = ["cyclonedx", path_arg, output_arg];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let error = generate_sboms(&args_parsed).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(GeneratorError::OutputFileError { .. })
+        ));
+
+        // Neither can one filename in a shared output directory
+        let output_dir_arg = &format!("--output-dir={}", tmp_dir.path().display());
+        let args = [
+            "cyclonedx",
+            path_arg,
+            output_dir_arg,
+            "--override-filename=sbom",
+        ];
+        let args_parsed = cli::Args::parse_from(args.iter());
+
+        let error = generate_sboms(&args_parsed).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(GeneratorError::OutputDirFilenameError { .. })
+        ));
+    }
 }
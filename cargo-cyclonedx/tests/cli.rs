@@ -75,6 +75,211 @@ fn find_content_in_bom_files() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn root_component_has_a_cargo_purl() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = make_temp_rust_project()?;
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+
+    cmd.current_dir(tmp_dir.path())
+        .arg("cyclonedx")
+        .arg("--format=json")
+        .arg("--override-filename=bom");
+
+    cmd.assert().success().stdout("");
+
+    let bom: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(tmp_dir.child("bom.json").path())?)?;
+    let purl = bom["metadata"]["component"]["purl"].as_str().unwrap();
+    assert!(
+        purl.starts_with("pkg:cargo/pkg@0.0.0"),
+        "unexpected root purl {purl}"
+    );
+
+    tmp_dir.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn root_component_has_vcs_properties_with_include_vcs() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = make_temp_rust_project()?;
+    let git = |args: &[&str]| -> Result<String, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(tmp_dir.path())
+            .output()?;
+        assert!(output.status.success(), "git {args:?} failed");
+        Ok(String::from_utf8(output.stdout)?.trim().to_string())
+    };
+    git(&["init", "--quiet"])?;
+    git(&["add", "."])?;
+    git(&["commit", "--quiet", "--message=Initial commit"])?;
+    git(&["tag", "v0.1.0"])?;
+    let commit = git(&["rev-parse", "HEAD"])?;
+
+    let vcs_properties =
+        |include_vcs: bool| -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+            let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+            cmd.current_dir(tmp_dir.path())
+                .arg("cyclonedx")
+                .arg("--format=json")
+                .arg("--override-filename=bom");
+            if include_vcs {
+                cmd.arg("--include-vcs");
+            }
+            cmd.assert().success().stdout("");
+
+            let bom: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(tmp_dir.child("bom.json").path())?)?;
+            let properties = bom["metadata"]["component"]["properties"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            Ok(properties
+                .iter()
+                .map(|property| {
+                    (
+                        property["name"].as_str().unwrap().to_string(),
+                        property["value"].as_str().unwrap().to_string(),
+                    )
+                })
+                .filter(|(name, _)| name.starts_with("cdx:vcs:"))
+                .collect())
+        };
+
+    assert_eq!(
+        vcs_properties(true)?,
+        [
+            ("cdx:vcs:commit".to_string(), commit),
+            ("cdx:vcs:describe".to_string(), "v0.1.0".to_string()),
+        ]
+    );
+    assert_eq!(vcs_properties(false)?, []);
+
+    tmp_dir.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn include_vcs_outside_of_a_git_repository() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = make_temp_rust_project()?;
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+
+    cmd.current_dir(tmp_dir.path())
+        .arg("cyclonedx")
+        .arg("--format=json")
+        .arg("--override-filename=bom")
+        .arg("--include-vcs");
+
+    cmd.assert()
+        .success()
+        .stdout("")
+        .stderr(predicate::str::contains("is it a git repository?"));
+
+    tmp_dir
+        .child("bom.json")
+        .assert(predicate::str::contains("cdx:vcs:").not());
+
+    tmp_dir.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn write_bom_files_into_output_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = make_temp_rust_project()?;
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+
+    cmd.current_dir(tmp_dir.path())
+        .arg("cyclonedx")
+        .arg("--output-dir=target/sbom")
+        .arg("--override-filename=bom");
+
+    cmd.assert().success().stdout("");
+
+    tmp_dir
+        .child("target/sbom/bom.xml")
+        .assert(predicate::path::exists());
+    tmp_dir.child("bom.xml").assert(predicate::path::missing());
+
+    tmp_dir.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn write_bom_to_stdout() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = make_temp_rust_project()?;
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+
+    cmd.current_dir(tmp_dir.path())
+        .arg("cyclonedx")
+        .arg("--format=json")
+        .arg("--output-file=-");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(r#""bomFormat": "CycloneDX""#));
+
+    tmp_dir
+        .child("pkg.cdx.json")
+        .assert(predicate::path::missing());
+
+    tmp_dir.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn spec_version_from_environment() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = make_temp_rust_project()?;
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+
+    cmd.current_dir(tmp_dir.path())
+        .env("CYCLONEDX_SPEC_VERSION", "1.5")
+        .arg("cyclonedx")
+        .arg("--format=json")
+        .arg("--override-filename=bom");
+
+    cmd.assert().success().stdout("");
+
+    let bom: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(tmp_dir.child("bom.json").path())?)?;
+    assert_eq!(bom["specVersion"], "1.5");
+
+    // an explicit argument takes precedence over the environment
+    cmd.arg("--spec-version=1.4");
+    cmd.assert().success().stdout("");
+
+    let bom: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(tmp_dir.child("bom.json").path())?)?;
+    assert_eq!(bom["specVersion"], "1.4");
+
+    tmp_dir.close()?;
+
+    Ok(())
+}
+
+#[test]
+fn invalid_spec_version_from_environment() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = make_temp_rust_project()?;
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+
+    cmd.current_dir(tmp_dir.path())
+        .env("CYCLONEDX_SPEC_VERSION", "1.0")
+        .arg("cyclonedx");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unsupported spec version '1.0'"));
+
+    tmp_dir.close()?;
+
+    Ok(())
+}
+
 #[test]
 fn find_content_in_stderr() -> Result<(), Box<dyn std::error::Error>> {
     let tmp_dir = make_temp_rust_project()?;
@@ -124,9 +329,24 @@ fn find_content_in_stderr() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
 
-    cmd.current_dir(tmp_dir.path()).arg("cyclonedx").arg("-qq");
+    cmd.current_dir(tmp_dir.path())
+        .env_remove("RUST_LOG")
+        .arg("cyclonedx")
+        .arg("-qq");
+
+    cmd.assert().success().stdout("").stderr("");
+
+    // `RUST_LOG` still takes precedence over `-qq`
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME"))?;
+
+    cmd.current_dir(tmp_dir.path())
+        .env("RUST_LOG", "warn")
+        .arg("cyclonedx")
+        .arg("-qq");
 
-    cmd.assert().success().stdout("");
+    cmd.assert().success().stderr(predicate::str::contains(
+        "has an invalid license expression",
+    ));
 
     tmp_dir.close()?;
 
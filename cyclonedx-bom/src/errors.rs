@@ -20,6 +20,7 @@ use std::convert::Infallible;
 
 use xml::name::OwnedName;
 
+use crate::external_models::normalized_string::NormalizedString;
 use crate::models::bom::SpecVersion;
 
 #[derive(Debug, thiserror::Error)]
@@ -34,10 +35,36 @@ pub enum BomError {
     #[error("Failed to serialize BOM with version {0:?}: {1}")]
     BomSerializationError(SpecVersion, String),
 
+    #[error(
+        "Failed to serialize BOM with version {spec_version:?}: {model}.{field} is not supported"
+    )]
+    UnsupportedFieldError {
+        spec_version: SpecVersion,
+        field: &'static str,
+        model: &'static str,
+    },
+
     #[error("Unsupported Spec Version '{0}'")]
     UnsupportedSpecVersion(String),
+
+    #[error("Unknown fields found: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
+}
+
+impl From<SpecVersionError> for BomError {
+    fn from(err: SpecVersionError) -> Self {
+        Self::UnsupportedSpecVersion(err.0)
+    }
 }
 
+/// Error raised when parsing a [`SpecVersion`] that is not supported.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "Unsupported spec version '{0}', supported versions are: {}",
+    SpecVersion::all().iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+)]
+pub struct SpecVersionError(pub String);
+
 // This allows to use `TryFrom` when a type only implements `From` inside a
 // `TryFrom<Error = BomError>` implementation.
 impl From<Infallible> for BomError {
@@ -46,6 +73,36 @@ impl From<Infallible> for BomError {
     }
 }
 
+/// Errors raised while converting a BOM into another SBOM format, e.g. SPDX.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConvertError {
+    #[error("Failed to convert timestamp '{0}': {1}")]
+    InvalidTimestamp(String, String),
+
+    #[error("Dependency ref '{0}' does not exist in the BOM")]
+    UnknownBomRef(String),
+}
+
+/// Raised when the dependency graph of a BOM cannot be ordered, see
+/// [`Dependencies::topological_sort`](crate::models::dependency::Dependencies::topological_sort).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Dependency cycle found between {}", .refs.join(", "))]
+pub struct CycleError {
+    /// The bom-refs taking part in the cycle.
+    pub refs: Vec<String>,
+}
+
+/// Returned by [`NormalizedString::new_checked`] when the input had to be altered.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Replaced invalid characters at positions {positions:?}")]
+pub struct NormalizationWarning {
+    /// The normalized value, as produced by [`NormalizedString::new`].
+    pub normalized: NormalizedString,
+    /// Byte offsets of the replaced characters in the input.
+    pub positions: Vec<usize>,
+}
+
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum JsonWriteError {
@@ -59,6 +116,10 @@ pub enum JsonWriteError {
         #[from]
         error: BomError,
     },
+    /// Raised when the methods of a
+    /// [`BomStreamWriter`](crate::models::stream::BomStreamWriter) are called out of order.
+    #[error("Failed to stream BOM: {message}")]
+    StreamOrderError { message: &'static str },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -75,6 +136,11 @@ pub enum XmlWriteError {
         #[from]
         error: BomError,
     },
+    #[error("Failed to parse raw XML fragment: {error}")]
+    RawXmlReadError {
+        #[source]
+        error: xml::reader::Error,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -90,6 +156,12 @@ pub enum JsonReadError {
         #[from]
         error: BomError,
     },
+    #[cfg(feature = "gzip")]
+    #[error("Failed to read gzip-compressed input: {error}")]
+    GzipReadError {
+        #[source]
+        error: std::io::Error,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -127,6 +199,16 @@ pub enum XmlReadError {
         expected_namespace: String,
         actual_namespace: Option<String>,
     },
+
+    #[cfg(feature = "gzip")]
+    #[error("Failed to read gzip-compressed input: {error}")]
+    GzipReadError {
+        #[source]
+        error: std::io::Error,
+    },
+
+    #[error("XML elements are nested deeper than the maximum depth of {max_depth}")]
+    MaxDepthExceeded { max_depth: usize },
 }
 
 impl XmlReadError {
@@ -137,3 +219,50 @@ impl XmlReadError {
         }
     }
 }
+
+/// Errors raised by [`Bom::parse_from_reader`](crate::models::bom::Bom::parse_from_reader).
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ReadError {
+    #[error("Failed to read input: {0}")]
+    IoError(#[from] std::io::Error),
+
+    #[error("Input is neither a JSON nor an XML document")]
+    UnknownFormat,
+
+    #[error("{0}")]
+    JsonReadError(#[from] JsonReadError),
+
+    #[error("{0}")]
+    XmlReadError(#[from] XmlReadError),
+}
+
+/// Errors raised when verifying a signature, requires the `signature-verify` feature.
+#[cfg(feature = "signature-verify")]
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SignatureError {
+    #[error("The document is not signed")]
+    MissingSignature,
+
+    #[error("The signature is not a valid JSF signature: {0}")]
+    InvalidSignature(serde_json::Error),
+
+    #[error("Only single signatures can be verified, not multiple signers or chains")]
+    UnsupportedSignatureType,
+
+    #[error("Verifying signatures with algorithm {0} is not supported")]
+    UnsupportedAlgorithm(String),
+
+    #[error("The public key cannot be used with algorithm {0}")]
+    KeyMismatch(String),
+
+    #[error("The signature value is not valid base64url: {0}")]
+    InvalidEncoding(#[from] base64::DecodeError),
+
+    #[error("Failed to build the signed payload: {0}")]
+    PayloadError(#[from] JsonWriteError),
+
+    #[error("The signature does not match the payload")]
+    VerificationFailed,
+}
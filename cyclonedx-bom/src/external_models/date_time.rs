@@ -19,7 +19,10 @@
 use std::convert::TryFrom;
 
 use thiserror::Error;
-use time::{format_description::well_known::Iso8601, OffsetDateTime};
+use time::{
+    format_description::well_known::{Iso8601, Rfc3339},
+    OffsetDateTime, UtcOffset,
+};
 
 use crate::validation::ValidationError;
 
@@ -55,6 +58,42 @@ impl DateTime {
             .map_err(|_| DateTimeError::FailedCurrentTime)?;
         Ok(Self(now))
     }
+
+    /// Creates the timestamp of the given number of seconds since the Unix epoch, in UTC.
+    /// ```
+    /// use cyclonedx_bom::external_models::date_time::DateTime;
+    ///
+    /// let date_time = DateTime::from_unix_timestamp(1_700_000_000).unwrap();
+    /// assert_eq!(date_time.to_string(), "2023-11-14T22:13:20Z");
+    /// ```
+    pub fn from_unix_timestamp(seconds: i64) -> Result<Self, DateTimeError> {
+        OffsetDateTime::from_unix_timestamp(seconds)
+            .ok()
+            .and_then(|date_time| date_time.format(&Rfc3339).ok())
+            .map(Self)
+            .ok_or_else(|| DateTimeError::InvalidDateTime(format!("{seconds} is out of range")))
+    }
+
+    /// Returns the same point in time with a `Z` offset, invalid timestamps are kept as is.
+    /// ```
+    /// use cyclonedx_bom::external_models::date_time::DateTime;
+    /// use std::convert::TryFrom;
+    ///
+    /// let date_time = DateTime::try_from("2024-03-01T12:30:00+02:00".to_string()).unwrap();
+    /// assert_eq!(date_time.to_utc().to_string(), "2024-03-01T10:30:00Z");
+    /// ```
+    pub fn to_utc(&self) -> Self {
+        OffsetDateTime::parse(&self.0, &Iso8601::DEFAULT)
+            .ok()
+            .and_then(|date_time| date_time.to_offset(UtcOffset::UTC).format(&Rfc3339).ok())
+            .map_or_else(|| self.clone(), Self)
+    }
+
+    /// Returns `true` if the timestamp lies after the current time, invalid timestamps are not.
+    pub fn is_in_the_future(&self) -> bool {
+        OffsetDateTime::parse(&self.0, &Iso8601::DEFAULT)
+            .is_ok_and(|date_time| date_time > OffsetDateTime::now_utc())
+    }
 }
 
 impl TryFrom<String> for DateTime {
@@ -106,6 +145,22 @@ mod test {
         assert!(validation_result.is_ok());
     }
 
+    #[test]
+    fn it_should_convert_datetimes_to_utc() {
+        assert_eq!(
+            DateTime("1969-06-28T01:20:00.25-04:00".to_string()).to_utc(),
+            DateTime("1969-06-28T05:20:00.25Z".to_string())
+        );
+        assert_eq!(
+            DateTime("2024-01-01T00:00:00Z".to_string()).to_utc(),
+            DateTime("2024-01-01T00:00:00Z".to_string())
+        );
+        assert_eq!(
+            DateTime("invalid date".to_string()).to_utc(),
+            DateTime("invalid date".to_string())
+        );
+    }
+
     #[test]
     fn invalid_datetimes_should_fail_validation() {
         let validation_result = validate_date_time(&DateTime("invalid date".to_string()));
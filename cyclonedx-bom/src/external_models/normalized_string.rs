@@ -16,6 +16,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::errors::NormalizationWarning;
 use crate::validation::ValidationError;
 use std::fmt::Display;
 use std::ops::Deref;
@@ -39,6 +40,32 @@ impl NormalizedString {
         NormalizedString(value)
     }
 
+    /// Construct a `NormalizedString` like [`NormalizedString::new`], but report the positions
+    /// of the characters that were replaced instead of changing the input silently
+    /// ```
+    /// use cyclonedx_bom::prelude::*;
+    ///
+    /// let warning = NormalizedString::new_checked("A\tstring").unwrap_err();
+    /// assert_eq!(warning.normalized.to_string(), "A string".to_string());
+    /// assert_eq!(warning.positions, vec![1]);
+    /// ```
+    pub fn new_checked(value: &str) -> Result<Self, NormalizationWarning> {
+        let positions: Vec<usize> = value
+            .char_indices()
+            .filter(|(_, c)| matches!(c, '\r' | '\n' | '\t'))
+            .map(|(index, _)| index)
+            .collect();
+
+        if positions.is_empty() {
+            Ok(NormalizedString(value.to_string()))
+        } else {
+            Err(NormalizationWarning {
+                normalized: Self::new(value),
+                positions,
+            })
+        }
+    }
+
     /// Allow for the existence of invalid inputs from other data sources
     pub(crate) fn new_unchecked(value: String) -> Self {
         NormalizedString(value)
@@ -121,6 +148,32 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_should_report_replaced_characters() {
+        assert_eq!(
+            NormalizedString::new_checked("no_whitespace"),
+            Ok(NormalizedString("no_whitespace".to_string()))
+        );
+        assert_eq!(
+            NormalizedString::new_checked("tabs\tand\nnewlines\r\n"),
+            Err(NormalizationWarning {
+                normalized: NormalizedString("tabs and newlines ".to_string()),
+                positions: vec![4, 8, 17, 18],
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_keep_characters_that_are_not_replaced() {
+        assert_eq!(
+            NormalizedString::new_checked("nul\0byte\twith tab"),
+            Err(NormalizationWarning {
+                normalized: NormalizedString("nul\0byte with tab".to_string()),
+                positions: vec![8],
+            })
+        );
+    }
+
     #[test]
     fn it_should_pass_validation() {
         assert!(validate_normalized_string(&NormalizedString("no_whitespace".to_string())).is_ok());
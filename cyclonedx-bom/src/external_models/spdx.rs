@@ -185,9 +185,15 @@ impl std::fmt::Display for SpdxExpression {
     }
 }
 
+/// Checks an expression's syntax and that every identifier is a known SPDX license or exception,
+/// or a custom `LicenseRef-`. The error names the position of the offending token.
 pub fn validate_spdx_expression(expression: &SpdxExpression) -> Result<(), ValidationError> {
-    if Expression::parse(&expression.expression).is_err() {
-        return Err(ValidationError::new("SPDX expression is not valid"));
+    if let Err(e) = Expression::parse(&expression.expression) {
+        return Err(format!(
+            "SPDX expression is not valid at position {}: {}",
+            e.span.start, e.reason
+        )
+        .into());
     }
     Ok(())
 }
@@ -292,9 +298,17 @@ mod test {
 
     #[test]
     fn valid_spdx_expressions_should_pass_validation() {
-        let validation_result = validate_spdx_expression(&SpdxExpression::new("MIT OR Apache-2.0"));
-
-        assert!(validation_result.is_ok());
+        for expression in [
+            "MIT OR Apache-2.0",
+            "(MIT OR Apache-2.0) AND BSD-3-Clause",
+            "Apache-2.0 WITH LLVM-exception",
+            "LicenseRef-custom",
+            "MIT AND LicenseRef-custom",
+        ] {
+            let validation_result = validate_spdx_expression(&SpdxExpression::new(expression));
+
+            assert!(validation_result.is_ok(), "{expression} should be valid");
+        }
     }
 
     #[test]
@@ -304,7 +318,21 @@ mod test {
 
         assert_eq!(
             validation_result,
-            Err("SPDX expression is not valid".into()),
+            Err("SPDX expression is not valid at position 0: unknown term".into()),
+        );
+    }
+
+    #[test]
+    fn it_should_report_the_position_of_a_repeated_operator() {
+        let validation_result =
+            validate_spdx_expression(&SpdxExpression::new("MIT AND AND Apache-2.0"));
+
+        assert_eq!(
+            validation_result,
+            Err(
+                "SPDX expression is not valid at position 8: expected one of `<license>`, `(` here"
+                    .into()
+            ),
         );
     }
 }
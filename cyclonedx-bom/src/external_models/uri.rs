@@ -35,6 +35,9 @@ pub fn validate_purl(purl: &Purl) -> Result<(), ValidationError> {
 pub struct Purl(pub(crate) String);
 
 impl Purl {
+    /// The package type of crates published to crates.io and other Cargo registries.
+    pub const CARGO_TYPE: &'static str = "cargo";
+
     pub fn new(package_type: &str, name: &str, version: &str) -> Result<Purl, UriError> {
         let builder = GenericPurlBuilder::new(package_type.to_string(), name).with_version(version);
 
@@ -43,6 +46,90 @@ impl Purl {
             Err(e) => Err(UriError::InvalidPurl(e.to_string())),
         }
     }
+
+    /// Creates a purl for a Cargo package, `pkg:cargo/<name>@<version>`.
+    /// ```
+    /// use cyclonedx_bom::prelude::Purl;
+    ///
+    /// let purl = Purl::cargo("serde", "1.0.193").unwrap();
+    /// assert_eq!(purl.to_string(), "pkg:cargo/serde@1.0.193");
+    /// ```
+    pub fn cargo(name: &str, version: &str) -> Result<Purl, UriError> {
+        Self::new(Self::CARGO_TYPE, name, version)
+    }
+
+    /// Parses a purl string, checking that it conforms to the
+    /// [package-url spec](https://github.com/package-url/purl-spec).
+    /// ```
+    /// use cyclonedx_bom::prelude::Purl;
+    ///
+    /// assert!(Purl::parse("pkg:cargo/serde@1.0.0").is_ok());
+    /// assert!(Purl::parse("serde@1.0.0").is_err());
+    /// ```
+    pub fn parse(purl: &str) -> Result<Purl, UriError> {
+        match GenericPurl::<String>::from_str(purl) {
+            Ok(_) => Ok(Self(purl.to_string())),
+            Err(e) => Err(UriError::InvalidPurl(e.to_string())),
+        }
+    }
+
+    /// Wraps a purl string as is, for example when reading a BOM from another tool.
+    /// Use [`Validate`] or [`Purl::parse`] to check it against the spec.
+    pub fn new_unchecked(purl: &str) -> Purl {
+        Self(purl.to_string())
+    }
+
+    /// Returns the purl in a canonical form, so that equivalent purls compare equal.
+    ///
+    /// The scheme and type are lowercased, qualifier keys are lowercased and sorted, and
+    /// qualifiers without a value are dropped.
+    /// ```
+    /// use cyclonedx_bom::prelude::Purl;
+    /// use std::str::FromStr;
+    ///
+    /// let purl = Purl::from_str("pkg:Cargo/foo@1.0.0?os=linux&Arch=x86").unwrap();
+    /// assert_eq!(purl.canonical().to_string(), "pkg:cargo/foo@1.0.0?arch=x86&os=linux");
+    /// ```
+    pub fn canonical(&self) -> Purl {
+        let (rest, subpath) = match self.0.split_once('#') {
+            Some((rest, subpath)) => (rest, Some(subpath)),
+            None => (self.0.as_str(), None),
+        };
+        let (rest, qualifiers) = match rest.split_once('?') {
+            Some((rest, qualifiers)) => (rest, Some(qualifiers)),
+            None => (rest, None),
+        };
+
+        let mut canonical = match rest.split_once('/') {
+            Some((scheme_and_type, path)) => format!("{}/{path}", scheme_and_type.to_lowercase()),
+            None => rest.to_string(),
+        };
+
+        if let Some(qualifiers) = qualifiers {
+            let mut qualifiers = qualifiers
+                .split('&')
+                .filter_map(|qualifier| qualifier.split_once('='))
+                .filter(|(_, value)| !value.is_empty())
+                .map(|(key, value)| (key.to_lowercase(), value))
+                .collect::<Vec<_>>();
+            qualifiers.sort();
+            if !qualifiers.is_empty() {
+                let qualifiers = qualifiers
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect::<Vec<_>>();
+                canonical.push('?');
+                canonical.push_str(&qualifiers.join("&"));
+            }
+        }
+
+        if let Some(subpath) = subpath {
+            canonical.push('#');
+            canonical.push_str(subpath);
+        }
+
+        Self(canonical)
+    }
 }
 
 impl std::fmt::Display for Purl {
@@ -123,7 +210,7 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use crate::{
-        external_models::uri::{validate_purl, validate_uri},
+        external_models::uri::{validate_purl, validate_uri, UriError},
         prelude::{Purl, Uri},
     };
 
@@ -143,6 +230,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_should_create_a_cargo_purl() {
+        assert_eq!(
+            Purl::cargo("cargo-cyclonedx", "0.5.7"),
+            Ok(Purl("pkg:cargo/cargo-cyclonedx@0.5.7".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_should_parse_purls() {
+        assert_eq!(
+            Purl::parse("pkg:cargo/serde@1.0.0"),
+            Ok(Purl("pkg:cargo/serde@1.0.0".to_string()))
+        );
+        assert_eq!(
+            Purl::parse("pkg:npm/%40angular/core@16.0.0"),
+            Ok(Purl("pkg:npm/%40angular/core@16.0.0".to_string()))
+        );
+        assert!(matches!(
+            Purl::parse("pkg:serde@1.0.0"),
+            Err(UriError::InvalidPurl(_))
+        ));
+        assert_eq!(
+            Purl::new_unchecked("pkg:serde@1.0.0"),
+            Purl("pkg:serde@1.0.0".to_string())
+        );
+    }
+
+    #[test]
+    fn it_should_canonicalize_purls() {
+        let purl =
+            Purl("PKG:Cargo/Foo@1.0.0?repository_url=&os=linux&arch=x86#src/lib".to_string());
+
+        assert_eq!(
+            purl.canonical(),
+            Purl("pkg:cargo/Foo@1.0.0?arch=x86&os=linux#src/lib".to_string())
+        );
+    }
+
     #[test]
     fn valid_uris_should_pass_validation() {
         let validation_result = validate_uri(&Uri("https://example.com".to_string()));
@@ -128,6 +128,7 @@ pub mod models;
 pub mod prelude;
 pub mod validation;
 
+mod spdx_export;
 mod specs;
 mod utilities;
 mod xml;
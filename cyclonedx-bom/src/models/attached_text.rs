@@ -76,7 +76,7 @@ impl Validate for AttachedText {
 /// Function to check [`Encoding`].
 pub fn validate_encoding(encoding: &Encoding) -> Result<(), ValidationError> {
     if matches!(encoding, Encoding::UnknownEncoding(_)) {
-        return Err(ValidationError::new("Unknown encoding"));
+        return Err(ValidationError::unknown_value("Unknown encoding"));
     }
     Ok(())
 }
@@ -104,7 +104,7 @@ mod test {
     use crate::{
         models::attached_text::{AttachedText, Encoding},
         prelude::{NormalizedString, Validate},
-        validation,
+        validation::{self, ValidationError},
     };
 
     use pretty_assertions::assert_eq;
@@ -170,7 +170,10 @@ mod test {
 
         assert_eq!(
             validation_result,
-            validation::field("encoding", "Unknown encoding"),
+            validation::field(
+                "encoding",
+                ValidationError::unknown_value("Unknown encoding")
+            ),
         );
     }
 
@@ -16,6 +16,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use base64::{engine::general_purpose::STANDARD, Engine};
+
 use crate::{
     prelude::{Validate, ValidationResult},
     validation::{ValidationContext, ValidationError},
@@ -32,9 +34,16 @@ pub struct Attachment {
 
 impl Validate for Attachment {
     fn validate_version(&self, _version: SpecVersion) -> ValidationResult {
-        ValidationContext::new()
-            .add_field_option("encoding", self.encoding.as_ref(), validate_encoding)
-            .into()
+        let mut context = ValidationContext::new();
+        context.add_field_option("encoding", self.encoding.as_ref(), validate_encoding);
+
+        if self.encoding.as_deref() == Some("base64") && STANDARD.decode(&self.content).is_err() {
+            context.add_field("content", &self.content, |_| {
+                Err("Content is not Base64 encoded".into())
+            });
+        }
+
+        context.into()
     }
 }
 
@@ -44,3 +53,37 @@ fn validate_encoding(encoding: &String) -> Result<(), ValidationError> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::validation;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn valid_base64_attachment_should_pass_validation() {
+        let validation_result = Attachment {
+            content: "dGhpcyB0ZXh0IGlzIHBsYWlu".to_string(),
+            content_type: Some("text/plain".to_string()),
+            encoding: Some("base64".to_string()),
+        }
+        .validate();
+
+        assert!(validation_result.passed());
+    }
+
+    #[test]
+    fn invalid_base64_attachment_should_fail_validation() {
+        let validation_result = Attachment {
+            content: "not base64 encoded".to_string(),
+            content_type: Some("text/plain".to_string()),
+            encoding: Some("base64".to_string()),
+        }
+        .validate();
+
+        assert_eq!(
+            validation_result,
+            validation::field("content", "Content is not Base64 encoded")
+        );
+    }
+}
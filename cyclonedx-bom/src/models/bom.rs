@@ -16,7 +16,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryInto;
 use std::fmt;
 use std::str::FromStr;
@@ -27,25 +27,30 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use xml::{EmitterConfig, EventReader, EventWriter, ParserConfig};
 
-use crate::errors::BomError;
+use crate::errors::{BomError, SpecVersionError};
+use crate::external_models::date_time::DateTime;
+use crate::external_models::uri::Purl;
 use crate::models::annotation::Annotations;
 use crate::models::component::{Component, Components};
 use crate::models::composition::Compositions;
-use crate::models::dependency::Dependencies;
-use crate::models::external_reference::ExternalReferences;
+use crate::models::dependency::{Dependencies, Dependency};
+use crate::models::diff::BomDiff;
+use crate::models::extension::{Extensions, RawXml};
+use crate::models::external_reference::{ExternalReferences, Uri};
 use crate::models::formulation::Formula;
 use crate::models::metadata::Metadata;
 use crate::models::property::Properties;
 use crate::models::service::{Service, Services};
 use crate::models::signature::Signature;
-use crate::models::vulnerability::Vulnerabilities;
-use crate::validation::{Validate, ValidationContext, ValidationError, ValidationResult};
-use crate::xml::{FromXmlDocument, ToXml};
-
-use super::vulnerability::Vulnerability;
+use crate::models::vulnerability::{Vulnerabilities, Vulnerability};
+use crate::models::vulnerability_rating::Severity;
+use crate::validation::{
+    self, Validate, ValidationContext, ValidationError, ValidationOptions, ValidationResult,
+};
+use crate::xml::{normalize_utf16_line_endings, FromXmlDocument, NormalizeLineEndings, ToXml};
 
 /// Represents the spec version of a BOM.
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, PartialOrd, strum::Display)]
+#[derive(Debug, Serialize, PartialEq, Eq, Clone, Copy, PartialOrd, strum::Display)]
 pub enum SpecVersion {
     #[strum(to_string = "1.3")]
     #[serde(rename = "1.3")]
@@ -58,6 +63,13 @@ pub enum SpecVersion {
     V1_5 = 3,
 }
 
+impl SpecVersion {
+    /// Returns all supported spec versions, from oldest to newest.
+    pub fn all() -> &'static [SpecVersion] {
+        &[Self::V1_3, Self::V1_4, Self::V1_5]
+    }
+}
+
 impl Default for SpecVersion {
     fn default() -> Self {
         Self::V1_3
@@ -65,18 +77,135 @@ impl Default for SpecVersion {
 }
 
 impl FromStr for SpecVersion {
-    type Err = BomError;
+    type Err = SpecVersionError;
 
+    /// Parses a spec version, ignoring leading and trailing whitespace some producers emit.
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
+        match input.trim() {
             "1.3" => Ok(SpecVersion::V1_3),
             "1.4" => Ok(SpecVersion::V1_4),
             "1.5" => Ok(SpecVersion::V1_5),
-            s => Err(BomError::UnsupportedSpecVersion(s.to_string())),
+            s => Err(SpecVersionError(s.to_string())),
+        }
+    }
+}
+
+/// How deeply XML elements may be nested before parsing fails, so that adversarial documents
+/// cannot overflow the stack of the recursive XML readers.
+pub const DEFAULT_MAX_XML_DEPTH: usize = 256;
+
+/// The serialization format of a BOM.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, strum::Display)]
+pub enum Format {
+    #[strum(to_string = "JSON")]
+    Json,
+    #[strum(to_string = "XML")]
+    Xml,
+}
+
+const UTF8_BYTE_ORDER_MARK: &[u8] = b"\xEF\xBB\xBF";
+
+impl Format {
+    /// Returns the CycloneDX media type of the format, e.g. `application/vnd.cyclonedx+json`.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/vnd.cyclonedx+json",
+            Self::Xml => "application/vnd.cyclonedx+xml",
+        }
+    }
+
+    /// Parses a CycloneDX media type, ignoring parameters like `; version=1.5`.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let media_type = media_type.split(';').next().unwrap_or_default().trim();
+        match media_type {
+            "application/vnd.cyclonedx+json" => Some(Self::Json),
+            "application/vnd.cyclonedx+xml" => Some(Self::Xml),
+            _ => None,
+        }
+    }
+
+    /// Detects the format from the first character of a document, skipping whitespace and a
+    /// UTF-8 byte order mark.
+    pub fn detect(content: &[u8]) -> Option<Self> {
+        let content = content
+            .strip_prefix(UTF8_BYTE_ORDER_MARK)
+            .unwrap_or(content);
+        match content.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'{') => Some(Self::Json),
+            Some(b'<') => Some(Self::Xml),
+            _ => None,
+        }
+    }
+}
+
+/// How a JSON document is laid out when writing it with [`Bom::output_as_json_version`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JsonOutputOptions {
+    /// Writes the whole document on a single line without any whitespace.
+    Compact,
+    /// Writes one value per line, indented by the given number of spaces per level.
+    Pretty { indent: usize },
+}
+
+impl JsonOutputOptions {
+    fn write<W: std::io::Write, T: Serialize>(
+        &self,
+        writer: &mut W,
+        value: &T,
+    ) -> Result<(), serde_json::Error> {
+        match self {
+            Self::Compact => serde_json::to_writer(writer, value),
+            Self::Pretty { indent } => {
+                let indent = vec![b' '; *indent];
+                let formatter = serde_json::ser::PrettyFormatter::with_indent(&indent);
+                let mut serializer = serde_json::Serializer::with_formatter(writer, formatter);
+                value.serialize(&mut serializer)
+            }
         }
     }
 }
 
+impl Default for JsonOutputOptions {
+    fn default() -> Self {
+        Self::Pretty { indent: 2 }
+    }
+}
+
+/// Changes applied to a BOM before writing it, see [`Bom::with_output_options`].
+///
+/// The layout of JSON documents is chosen separately with [`JsonOutputOptions`], as it does not
+/// change the BOM itself.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub struct OutputOptions {
+    /// Converts the timestamps of the metadata and of formulation workflows and tasks to UTC.
+    pub normalize_timestamps_to_utc: bool,
+}
+
+impl OutputOptions {
+    /// Creates options that leave the BOM unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the timestamps of the metadata and of formulation workflows and tasks are
+    /// converted to UTC.
+    pub fn normalize_timestamps_to_utc(mut self, normalize: bool) -> Self {
+        self.normalize_timestamps_to_utc = normalize;
+        self
+    }
+}
+
+impl<'de> Deserialize<'de> for SpecVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let version = String::deserialize(deserializer)?;
+        SpecVersion::from_str(&version).map_err(serde::de::Error::custom)
+    }
+}
+
 pub fn validate_bom_ref(
     _bom_ref: &BomReference,
     version: SpecVersion,
@@ -120,9 +249,34 @@ pub struct Bom {
     /// Added in version 1.5
     pub formulation: Option<Vec<Formula>>,
     pub spec_version: SpecVersion,
+    /// XML elements outside of the CycloneDX schema
+    pub foreign_elements: Vec<RawXml>,
+    /// JSON fields outside of the CycloneDX schema
+    pub extra: Extensions,
 }
 
 impl Bom {
+    /// Construct a BOM describing a single component, using the latest supported spec version.
+    ///
+    /// The BOM gets a freshly generated `serial_number` and a `version` of `1`.
+    /// ```
+    /// use cyclonedx_bom::models::component::Classification;
+    /// use cyclonedx_bom::prelude::*;
+    ///
+    /// let component = Component::new(Classification::Library, "serde", "1.0.193", None);
+    /// let bom = Bom::with_single_component(component);
+    /// assert!(bom.validate().passed());
+    /// ```
+    pub fn with_single_component(component: Component) -> Self {
+        Self {
+            version: 1,
+            serial_number: Some(UrnUuid::generate()),
+            components: Some(Components(vec![component])),
+            spec_version: SpecVersion::V1_5,
+            ..Self::default()
+        }
+    }
+
     /// General function to parse a JSON file, fetches the `specVersion` field first then applies the right conversion.
     pub fn parse_from_json<R: std::io::Read>(
         mut reader: R,
@@ -138,7 +292,7 @@ impl Bom {
                 .as_str()
                 .ok_or_else(|| BomError::UnsupportedSpecVersion(version.to_string()))?;
 
-            match SpecVersion::from_str(version)? {
+            match SpecVersion::from_str(version).map_err(BomError::from)? {
                 SpecVersion::V1_3 => Ok(crate::specs::v1_3::bom::Bom::deserialize(json)?.into()),
                 SpecVersion::V1_4 => Ok(crate::specs::v1_4::bom::Bom::deserialize(json)?.into()),
                 SpecVersion::V1_5 => Ok(crate::specs::v1_5::bom::Bom::deserialize(json)?.into()),
@@ -148,6 +302,149 @@ impl Bom {
         }
     }
 
+    /// General function to parse an XML file, detects the spec version from the namespace of the
+    /// root element then applies the right conversion.
+    pub fn parse_from_xml<R: std::io::Read>(
+        reader: R,
+    ) -> Result<Self, crate::errors::XmlReadError> {
+        Self::parse_from_xml_with_max_depth(reader, DEFAULT_MAX_XML_DEPTH)
+    }
+
+    /// Like [`parse_from_xml`](Self::parse_from_xml), but fails with
+    /// [`XmlReadError::MaxDepthExceeded`](crate::errors::XmlReadError::MaxDepthExceeded) when
+    /// elements are nested deeper than `max_depth` instead of [`DEFAULT_MAX_XML_DEPTH`].
+    pub fn parse_from_xml_with_max_depth<R: std::io::Read>(
+        reader: R,
+        max_depth: usize,
+    ) -> Result<Self, crate::errors::XmlReadError> {
+        let content = crate::xml::read_with_max_depth(reader, max_depth)?;
+
+        let namespace = EventReader::new(content.as_slice())
+            .into_iter()
+            .find_map(|event| match event {
+                Ok(xml::reader::XmlEvent::StartElement { name, .. }) => Some(Ok(name.namespace)),
+                Ok(_) => None,
+                Err(error) => Some(Err(error)),
+            })
+            .transpose()
+            .map_err(|error| crate::errors::XmlReadError::ElementReadError {
+                error,
+                element: "bom".to_string(),
+            })?
+            .flatten();
+        let version = namespace
+            .as_deref()
+            .and_then(|namespace| namespace.strip_prefix("http://cyclonedx.org/schema/bom/"))
+            .and_then(|version| SpecVersion::from_str(version).ok())
+            .ok_or_else(|| crate::errors::XmlReadError::InvalidNamespaceError {
+                expected_namespace: "http://cyclonedx.org/schema/bom/<version>".to_string(),
+                actual_namespace: namespace.clone(),
+            })?;
+
+        Self::read_xml_content(&content, version)
+    }
+
+    /// Parses a JSON document held in a string, detecting the spec version from its
+    /// `specVersion` field.
+    /// ```
+    /// use cyclonedx_bom::models::bom::{Bom, SpecVersion};
+    ///
+    /// let bom = Bom::from_json_str(r#"{"bomFormat": "CycloneDX", "specVersion": "1.5", "version": 1}"#).unwrap();
+    /// assert_eq!(bom.spec_version, SpecVersion::V1_5);
+    /// ```
+    pub fn from_json_str(json: &str) -> Result<Self, crate::errors::JsonReadError> {
+        Self::parse_json_value(serde_json::from_str(json)?)
+    }
+
+    /// Parses an XML document held in a string, detecting the spec version from the namespace of
+    /// the root element.
+    /// ```
+    /// use cyclonedx_bom::models::bom::{Bom, SpecVersion};
+    ///
+    /// let bom = Bom::from_xml_str(r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.4" version="1" />"#).unwrap();
+    /// assert_eq!(bom.spec_version, SpecVersion::V1_4);
+    /// ```
+    pub fn from_xml_str(xml: &str) -> Result<Self, crate::errors::XmlReadError> {
+        Self::parse_from_xml(xml.as_bytes())
+    }
+
+    /// Parses a JSON or XML document of any supported spec version. Without a `format_hint` the
+    /// format is detected from the first character of the input.
+    /// ```
+    /// use cyclonedx_bom::models::bom::{Bom, Format};
+    ///
+    /// let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.3" version="1" />"#;
+    /// let bom = Bom::parse_from_reader(xml.as_bytes(), None).unwrap();
+    /// assert_eq!(bom.version, 1);
+    ///
+    /// let json = r#"{"bomFormat": "CycloneDX", "specVersion": "1.4", "version": 1}"#;
+    /// let bom = Bom::parse_from_reader(json.as_bytes(), Some(Format::Json)).unwrap();
+    /// assert_eq!(bom.version, 1);
+    /// ```
+    pub fn parse_from_reader<R: std::io::Read>(
+        mut reader: R,
+        format_hint: Option<Format>,
+    ) -> Result<Self, crate::errors::ReadError> {
+        let mut content = Vec::new();
+        reader.read_to_end(&mut content)?;
+
+        let format = format_hint
+            .or_else(|| Format::detect(&content))
+            .ok_or(crate::errors::ReadError::UnknownFormat)?;
+        match format {
+            Format::Json => {
+                // serde_json rejects the byte order mark, XML parsers skip it themselves
+                let content = content
+                    .strip_prefix(UTF8_BYTE_ORDER_MARK)
+                    .unwrap_or(&content);
+                Ok(Self::parse_from_json(content)?)
+            }
+            Format::Xml => Ok(Self::parse_from_xml(content.as_slice())?),
+        }
+    }
+
+    /// Like [`parse_from_json`](Self::parse_from_json), but fails when the BOM or one of its
+    /// components contains fields outside of the CycloneDX schema of its spec version instead of
+    /// keeping them in their `extra` field. Fields of the schema that are not modelled yet are
+    /// kept in `extra` as usual.
+    pub fn parse_from_json_strict<R: std::io::Read>(
+        reader: R,
+    ) -> Result<Self, crate::errors::JsonReadError> {
+        let bom = Self::parse_from_json(reader)?;
+        let (bom_fields, component_fields) = match bom.spec_version {
+            SpecVersion::V1_3 => (
+                crate::specs::v1_3::bom::SCHEMA_FIELDS,
+                crate::specs::v1_3::component::SCHEMA_FIELDS,
+            ),
+            SpecVersion::V1_4 => (
+                crate::specs::v1_4::bom::SCHEMA_FIELDS,
+                crate::specs::v1_4::component::SCHEMA_FIELDS,
+            ),
+            SpecVersion::V1_5 => (
+                crate::specs::v1_5::bom::SCHEMA_FIELDS,
+                crate::specs::v1_5::component::SCHEMA_FIELDS,
+            ),
+        };
+        let unknown_fields: Vec<String> = bom
+            .extra
+            .0
+            .keys()
+            .filter(|field| !bom_fields.contains(&field.as_str()))
+            .chain(bom.all_components().flat_map(|component| {
+                component
+                    .extra
+                    .0
+                    .keys()
+                    .filter(|field| !component_fields.contains(&field.as_str()))
+            }))
+            .cloned()
+            .collect();
+        if !unknown_fields.is_empty() {
+            return Err(BomError::UnknownFields(unknown_fields).into());
+        }
+        Ok(bom)
+    }
+
     /// Parse the input as a JSON document conforming to the version of the specification that you provide.
     /// Use [`parse_from_json`](Self::parse_from_json) if you want to support multiple versions instead.
     pub fn parse_from_json_with_version<R: std::io::Read>(
@@ -161,6 +458,59 @@ impl Bom {
         }
     }
 
+    /// Parses a gzip-compressed JSON document, see [`parse_from_json`](Self::parse_from_json).
+    ///
+    /// Requires the `gzip` feature. Fails with [`JsonReadError::GzipReadError`] if the input does
+    /// not start with the gzip magic bytes.
+    ///
+    /// [`JsonReadError::GzipReadError`]: crate::errors::JsonReadError::GzipReadError
+    #[cfg(feature = "gzip")]
+    pub fn parse_from_json_gz<R: std::io::Read>(
+        reader: R,
+    ) -> Result<Self, crate::errors::JsonReadError> {
+        let reader = gzip_decoder(reader)
+            .map_err(|error| crate::errors::JsonReadError::GzipReadError { error })?;
+        Self::parse_from_json(reader)
+    }
+
+    /// Converts a pre-parsed JSON document conforming to the version of the specification that
+    /// you provide, e.g. after validating it against the JSON schema.
+    /// Use [`parse_json_value`](Self::parse_json_value) if you want to support multiple versions
+    /// instead.
+    pub fn from_json_value(
+        value: Value,
+        version: SpecVersion,
+    ) -> Result<Self, crate::errors::JsonReadError> {
+        match version {
+            SpecVersion::V1_3 => Self::parse_from_json_value_v1_3(value),
+            SpecVersion::V1_4 => Self::parse_from_json_value_v1_4(value),
+            SpecVersion::V1_5 => Self::parse_from_json_value_v1_5(value),
+        }
+    }
+
+    /// Converts the BOM into a JSON value conforming to the specification version that you
+    /// provide, without serializing it to bytes first.
+    pub fn to_json_value(
+        &self,
+        version: SpecVersion,
+    ) -> Result<Value, crate::errors::JsonWriteError> {
+        let value = match version {
+            SpecVersion::V1_3 => {
+                let bom: crate::specs::v1_3::bom::Bom = self.clone().try_into()?;
+                serde_json::to_value(bom)?
+            }
+            SpecVersion::V1_4 => {
+                let bom: crate::specs::v1_4::bom::Bom = self.clone().try_into()?;
+                serde_json::to_value(bom)?
+            }
+            SpecVersion::V1_5 => {
+                let bom: crate::specs::v1_5::bom::Bom = self.clone().try_into()?;
+                serde_json::to_value(bom)?
+            }
+        };
+        Ok(value)
+    }
+
     /// Output as a JSON document conforming to the specification version that you provide.
     pub fn output_as_json<W: std::io::Write>(
         self,
@@ -174,6 +524,67 @@ impl Bom {
         }
     }
 
+    /// Applies the given options, so that the BOM can be written with any of the output functions.
+    /// ```
+    /// use cyclonedx_bom::models::bom::{Bom, OutputOptions, SpecVersion};
+    ///
+    /// let options = OutputOptions::new().normalize_timestamps_to_utc(true);
+    /// let mut output = Vec::new();
+    /// Bom::default()
+    ///     .with_output_options(options)
+    ///     .output_as_json(&mut output, SpecVersion::V1_5)
+    ///     .unwrap();
+    /// ```
+    pub fn with_output_options(mut self, options: OutputOptions) -> Self {
+        if options.normalize_timestamps_to_utc {
+            let normalize = |timestamp: &mut Option<DateTime>| {
+                if let Some(timestamp) = timestamp {
+                    *timestamp = timestamp.to_utc();
+                }
+            };
+
+            if let Some(metadata) = &mut self.metadata {
+                normalize(&mut metadata.timestamp);
+            }
+            for formula in self.formulation.iter_mut().flatten() {
+                for workflow in formula.workflows.iter_mut().flatten() {
+                    normalize(&mut workflow.time_start);
+                    normalize(&mut workflow.time_end);
+                    for task in workflow.tasks.iter_mut().flatten() {
+                        normalize(&mut task.time_start);
+                        normalize(&mut task.time_end);
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    /// Output as a JSON document conforming to the specification version that you provide, laid
+    /// out according to the given options.
+    pub fn output_as_json_version<W: std::io::Write>(
+        self,
+        writer: &mut W,
+        version: SpecVersion,
+        options: JsonOutputOptions,
+    ) -> Result<(), crate::errors::JsonWriteError> {
+        match version {
+            SpecVersion::V1_3 => {
+                let bom: crate::specs::v1_3::bom::Bom = self.try_into()?;
+                options.write(writer, &bom)?;
+            }
+            SpecVersion::V1_4 => {
+                let bom: crate::specs::v1_4::bom::Bom = self.try_into()?;
+                options.write(writer, &bom)?;
+            }
+            SpecVersion::V1_5 => {
+                let bom: crate::specs::v1_5::bom::Bom = self.try_into()?;
+                options.write(writer, &bom)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Parse the input as an XML document conforming to the version of the specification that you provide.
     pub fn parse_from_xml_with_version<R: std::io::Read>(
         reader: R,
@@ -186,6 +597,47 @@ impl Bom {
         }
     }
 
+    /// Parses a gzip-compressed XML document, see
+    /// [`parse_from_xml_with_version`](Self::parse_from_xml_with_version).
+    ///
+    /// Requires the `gzip` feature. Fails with [`XmlReadError::GzipReadError`] if the input does
+    /// not start with the gzip magic bytes.
+    ///
+    /// [`XmlReadError::GzipReadError`]: crate::errors::XmlReadError::GzipReadError
+    #[cfg(feature = "gzip")]
+    pub fn parse_from_xml_gz<R: std::io::Read>(
+        reader: R,
+        version: SpecVersion,
+    ) -> Result<Self, crate::errors::XmlReadError> {
+        let reader = gzip_decoder(reader)
+            .map_err(|error| crate::errors::XmlReadError::GzipReadError { error })?;
+        Self::parse_from_xml_with_version(reader, version)
+    }
+
+    /// Converts an XML document whose nesting depth has already been checked.
+    fn read_xml_content(
+        content: &[u8],
+        version: SpecVersion,
+    ) -> Result<Self, crate::errors::XmlReadError> {
+        let config = ParserConfig::default().trim_whitespace(true);
+        let reader: Box<dyn std::io::Read + '_> = match normalize_utf16_line_endings(content) {
+            Some(content) => Box::new(std::io::Cursor::new(content)),
+            None => Box::new(NormalizeLineEndings::new(content)),
+        };
+        let mut event_reader = EventReader::new_with_config(reader, config);
+        Ok(match version {
+            SpecVersion::V1_3 => {
+                crate::specs::v1_3::bom::Bom::read_xml_document(&mut event_reader)?.into()
+            }
+            SpecVersion::V1_4 => {
+                crate::specs::v1_4::bom::Bom::read_xml_document(&mut event_reader)?.into()
+            }
+            SpecVersion::V1_5 => {
+                crate::specs::v1_5::bom::Bom::read_xml_document(&mut event_reader)?.into()
+            }
+        })
+    }
+
     /// Output as an XML document conforming to the specification version that you provide.
     pub fn output_as_xml<W: std::io::Write>(
         self,
@@ -218,10 +670,8 @@ impl Bom {
     pub fn parse_from_xml_v1_3<R: std::io::Read>(
         reader: R,
     ) -> Result<Self, crate::errors::XmlReadError> {
-        let config = ParserConfig::default().trim_whitespace(true);
-        let mut event_reader = EventReader::new_with_config(reader, config);
-        let bom = crate::specs::v1_3::bom::Bom::read_xml_document(&mut event_reader)?;
-        Ok(bom.into())
+        let content = crate::xml::read_with_max_depth(reader, DEFAULT_MAX_XML_DEPTH)?;
+        Self::read_xml_content(&content, SpecVersion::V1_3)
     }
 
     /// Output as a JSON document conforming to [version 1.3 of the specification](https://cyclonedx.org/docs/1.3/json/)
@@ -229,9 +679,7 @@ impl Bom {
         self,
         writer: &mut W,
     ) -> Result<(), crate::errors::JsonWriteError> {
-        let bom: crate::specs::v1_3::bom::Bom = self.try_into()?;
-        serde_json::to_writer_pretty(writer, &bom)?;
-        Ok(())
+        self.output_as_json_version(writer, SpecVersion::V1_3, JsonOutputOptions::default())
     }
 
     /// Output as an XML document conforming to [version 1.3 of the specification](https://cyclonedx.org/docs/1.3/xml/)
@@ -265,10 +713,8 @@ impl Bom {
     pub fn parse_from_xml_v1_4<R: std::io::Read>(
         reader: R,
     ) -> Result<Self, crate::errors::XmlReadError> {
-        let config = ParserConfig::default().trim_whitespace(true);
-        let mut event_reader = EventReader::new_with_config(reader, config);
-        let bom = crate::specs::v1_4::bom::Bom::read_xml_document(&mut event_reader)?;
-        Ok(bom.into())
+        let content = crate::xml::read_with_max_depth(reader, DEFAULT_MAX_XML_DEPTH)?;
+        Self::read_xml_content(&content, SpecVersion::V1_4)
     }
 
     /// Output as a JSON document conforming to [version 1.4 of the specification](https://cyclonedx.org/docs/1.4/json/)
@@ -276,9 +722,7 @@ impl Bom {
         self,
         writer: &mut W,
     ) -> Result<(), crate::errors::JsonWriteError> {
-        let bom: crate::specs::v1_4::bom::Bom = self.try_into()?;
-        serde_json::to_writer_pretty(writer, &bom)?;
-        Ok(())
+        self.output_as_json_version(writer, SpecVersion::V1_4, JsonOutputOptions::default())
     }
 
     /// Output as an XML document conforming to [version 1.4 of the specification](https://cyclonedx.org/docs/1.4/xml/)
@@ -301,14 +745,19 @@ impl Bom {
         Ok(bom.into())
     }
 
+    /// Parse the input as a JSON document conforming to [version 1.5 of the specification](https://cyclonedx.org/docs/1.5/json/)
+    /// from an existing [`Value`].
+    pub fn parse_from_json_value_v1_5(value: Value) -> Result<Self, crate::errors::JsonReadError> {
+        let bom: crate::specs::v1_5::bom::Bom = serde_json::from_value(value)?;
+        Ok(bom.into())
+    }
+
     /// Parse the input as an XML document conforming to [version 1.5 of the specification](https://cyclonedx.org/docs/1.5/xml/)
     pub fn parse_from_xml_v1_5<R: std::io::Read>(
         reader: R,
     ) -> Result<Self, crate::errors::XmlReadError> {
-        let config = ParserConfig::default().trim_whitespace(true);
-        let mut event_reader = EventReader::new_with_config(reader, config);
-        let bom = crate::specs::v1_5::bom::Bom::read_xml_document(&mut event_reader)?;
-        Ok(bom.into())
+        let content = crate::xml::read_with_max_depth(reader, DEFAULT_MAX_XML_DEPTH)?;
+        Self::read_xml_content(&content, SpecVersion::V1_5)
     }
 
     /// Output as a JSON document conforming to [version 1.5 of the specification](https://cyclonedx.org/docs/1.5/json/)
@@ -316,9 +765,7 @@ impl Bom {
         self,
         writer: &mut W,
     ) -> Result<(), crate::errors::JsonWriteError> {
-        let bom: crate::specs::v1_5::bom::Bom = self.try_into()?;
-        serde_json::to_writer_pretty(writer, &bom)?;
-        Ok(())
+        self.output_as_json_version(writer, SpecVersion::V1_5, JsonOutputOptions::default())
     }
 
     /// Output as an XML document conforming to [version 1.5 of the specification](https://cyclonedx.org/docs/1.5/xml/)
@@ -332,117 +779,745 @@ impl Bom {
         let bom: crate::specs::v1_5::bom::Bom = self.try_into()?;
         bom.write_xml_element(&mut event_writer)
     }
-}
 
-impl Default for Bom {
-    /// Construct a BOM with a default `version` of `1` and `serial_number` with a random UUID
-    fn default() -> Self {
-        Self {
-            version: 1,
-            serial_number: Some(UrnUuid::generate()),
-            metadata: None,
-            components: None,
-            services: None,
-            external_references: None,
-            dependencies: None,
-            compositions: None,
-            properties: None,
-            vulnerabilities: None,
-            signature: None,
-            annotations: None,
-            formulation: None,
-            spec_version: SpecVersion::V1_3,
+    /// Converts the BOM into a JSON document conforming to [SPDX 2.3](https://spdx.github.io/spdx-spec/v2.3/).
+    ///
+    /// Components become SPDX packages, purls and CPEs become external references and the
+    /// dependency graph is expressed as `DEPENDS_ON` relationships.
+    pub fn to_spdx_json(&self) -> Result<Value, crate::errors::ConvertError> {
+        crate::spdx_export::bom_to_spdx_json(self)
+    }
+
+    /// Validates the BOM like [`Validate::validate`], with `options` relaxing or tightening
+    /// individual rules.
+    /// ```
+    /// use cyclonedx_bom::prelude::*;
+    /// use cyclonedx_bom::validation::ValidationOptions;
+    ///
+    /// let bom = Bom {
+    ///     serial_number: None,
+    ///     ..Bom::default()
+    /// };
+    /// let mut options = ValidationOptions::default();
+    /// options.require_serial_number = true;
+    /// assert!(bom.validate().passed());
+    /// assert!(bom.validate_with_options(&options).has_errors());
+    /// ```
+    pub fn validate_with_options(&self, options: &ValidationOptions) -> ValidationResult {
+        let mut result = self.validate_version(self.spec_version);
+
+        if options.require_serial_number && self.serial_number.is_none() {
+            let missing = ValidationContext::new()
+                .add_field("serial_number", &self.serial_number, |_| {
+                    Err(ValidationError::new("Serial number is required"))
+                })
+                .into();
+            result = ValidationResult::from(vec![result, missing]);
+        }
+        if options.check_bom_link_versions {
+            result = ValidationResult::from(vec![result, self.validate_bom_link_versions()]);
+        }
+        if options.disallow_future_timestamps {
+            result = ValidationResult::from(vec![result, self.validate_timestamps_in_the_past()]);
+        }
+        if options.treat_unknown_enums_as_warnings {
+            result.downgrade_unknown_values();
         }
+
+        result
     }
-}
 
-impl Validate for Bom {
-    fn validate_version(&self, version: SpecVersion) -> ValidationResult {
+    /// Warns about timestamps of the metadata and of formulation workflows and tasks that lie in
+    /// the future.
+    fn validate_timestamps_in_the_past(&self) -> ValidationResult {
         let mut context = ValidationContext::new();
-        context.add_field_option(
-            "serial_number",
-            self.serial_number.as_ref(),
-            validate_urn_uuid,
-        );
-        context.add_struct_option("metadata", self.metadata.as_ref(), version);
-        context.add_struct_option("components", self.components.as_ref(), version);
-        context.add_struct_option("services", self.services.as_ref(), version);
-        context.add_struct_option(
-            "external_references",
-            self.external_references.as_ref(),
-            version,
-        );
-        context.add_struct_option("compositions", self.compositions.as_ref(), version);
-        context.add_struct_option("properties", self.properties.as_ref(), version);
-        context.add_struct_option("vulnerabilities", self.vulnerabilities.as_ref(), version);
+        let mut check = |path: &str, timestamp: Option<&DateTime>| {
+            if timestamp.is_some_and(DateTime::is_in_the_future) {
+                context.add_warning(path, "Timestamp lies in the future");
+            }
+        };
 
-        // To keep track of all Bom references inside.
-        let mut bom_refs = BomReferencesContext::default();
+        check(
+            "metadata.timestamp",
+            self.metadata
+                .as_ref()
+                .and_then(|metadata| metadata.timestamp.as_ref()),
+        );
+        let formulas = self.formulation.iter().flatten().enumerate();
+        for (formula_index, formula) in formulas {
+            let workflows = formula.workflows.iter().flatten().enumerate();
+            for (workflow_index, workflow) in workflows {
+                let path = format!("formulation[{formula_index}].workflows[{workflow_index}]");
+                check(&format!("{path}.time_start"), workflow.time_start.as_ref());
+                check(&format!("{path}.time_end"), workflow.time_end.as_ref());
 
-        if let Some(metadata) = &self.metadata {
-            if let Some(component) = &metadata.component {
-                validate_component_bom_refs(&mut context, &mut bom_refs, component);
+                for (task_index, task) in workflow.tasks.iter().flatten().enumerate() {
+                    let path = format!("{path}.tasks[{task_index}]");
+                    check(&format!("{path}.time_start"), task.time_start.as_ref());
+                    check(&format!("{path}.time_end"), task.time_end.as_ref());
+                }
             }
         }
 
-        if let Some(components) = &self.components {
-            validate_components(&mut context, &mut bom_refs, components);
-        }
+        context.into()
+    }
 
-        if let Some(services) = &self.services {
-            validate_services(&mut context, &mut bom_refs, services);
-        }
+    /// Warns about BOM-Links to an earlier version of this BOM that do not have a lower version.
+    fn validate_bom_link_versions(&self) -> ValidationResult {
+        let mut context = ValidationContext::new();
+        let Some(serial_number) = self
+            .serial_number
+            .as_ref()
+            .and_then(|serial_number| serial_number.0.strip_prefix("urn:uuid:"))
+        else {
+            return context.into();
+        };
 
-        if let Some(vulnerabilities) = &self.vulnerabilities {
-            validate_vulnerabilities(&mut context, &mut bom_refs, vulnerabilities);
+        let references = self.external_references.iter().flat_map(|r| r.0.iter());
+        for (index, reference) in references.enumerate() {
+            let Uri::BomLink(link) = &reference.url else {
+                continue;
+            };
+            let Some(linked_version) = link.version() else {
+                continue;
+            };
+            if link.serial_number() == Some(serial_number) && linked_version >= self.version {
+                context.add_warning(
+                    &format!("external_references.inner[{index}]"),
+                    format!(
+                        "Links to version {linked_version} of the same BOM, expected it to be older than version {}",
+                        self.version
+                    ),
+                );
+            }
         }
 
-        // Check dependencies & sub dependencies
-        if let Some(dependencies) = &self.dependencies {
-            for dependency in &dependencies.0 {
-                if !bom_refs.contains(&dependency.dependency_ref) {
-                    context.add_custom(
-                        "dependency_ref",
-                        format!(
-                            "Dependency ref '{}' does not exist in the BOM",
-                            dependency.dependency_ref
-                        ),
-                    );
-                }
+        context.into()
+    }
 
-                for sub_dependency in &dependency.dependencies {
-                    if !bom_refs.contains(sub_dependency) {
-                        context.add_custom(
-                            "sub dependency_ref",
-                            format!(
-                                "Dependency ref '{}' does not exist in the BOM",
-                                sub_dependency
-                            ),
+    /// Validates the BOM against its own spec version like [`Validate::validate`], but stops
+    /// after collecting `max_errors` errors, see [`ValidationResult::is_truncated`].
+    ///
+    /// This keeps the result small for huge invalid BOMs, where validation can easily produce
+    /// thousands of errors.
+    pub fn validate_capped(&self, max_errors: usize) -> ValidationResult {
+        validation::validate_capped(max_errors, || self.validate())
+    }
+
+    /// Compares the components and dependencies of this BOM with those of a newer BOM.
+    ///
+    /// Components are matched by their purl regardless of its version, falling back to their
+    /// group and name.
+    pub fn diff(&self, other: &Bom) -> BomDiff {
+        BomDiff::new(self, other)
+    }
+
+    /// Merges the components, services, dependencies and vulnerabilities of `other` into this BOM.
+    ///
+    /// Top level components are matched by their canonical purl, services and vulnerabilities are
+    /// matched by bom-ref. Elements of `other` matching an element of this BOM are dropped and
+    /// dependencies referring to them are redirected to the matching element. Other components
+    /// whose bom-ref, or that of a nested component, is already taken get a fresh bom-ref with a
+    /// counter appended. The metadata of this BOM is kept as is.
+    pub fn merge(&mut self, other: Bom) {
+        let mut bom_refs = self.collect_bom_refs();
+        // Fresh bom-refs must not collide with either BOM.
+        let mut taken = other.collect_bom_refs();
+        taken.extend(
+            bom_refs
+                .iter()
+                .map(|(bom_ref, kind)| (bom_ref.clone(), *kind)),
+        );
+        // Maps bom-refs of dropped or renamed components in `other` to their new bom-refs.
+        let mut renamed_refs = HashMap::new();
+
+        if let Some(other_components) = other.components {
+            let components = self
+                .components
+                .get_or_insert_with(|| Components(Vec::new()));
+            let mut metadata_component = self
+                .metadata
+                .as_mut()
+                .and_then(|metadata| metadata.component.as_mut());
+            // Maps canonical purls to the index of their component, `None` for the metadata one.
+            let mut purls = components
+                .0
+                .iter()
+                .enumerate()
+                .map(|(index, component)| (Some(index), component))
+                .chain(
+                    metadata_component
+                        .as_deref()
+                        .map(|component| (None, component)),
+                )
+                .filter_map(|(index, component)| {
+                    Some((component.purl.as_ref()?.canonical(), index))
+                })
+                .collect::<HashMap<_, _>>();
+
+            for mut component in other_components.0 {
+                let purl = component.purl.as_ref().map(|purl| purl.canonical());
+                match purl.as_ref().and_then(|purl| purls.get(purl)) {
+                    Some(index) => {
+                        let existing = match index {
+                            Some(index) => &mut components.0[*index],
+                            None => metadata_component
+                                .as_deref_mut()
+                                .expect("the metadata component has a purl"),
+                        };
+                        if let Some(bom_ref) = component.bom_ref {
+                            let existing_ref = existing.bom_ref.get_or_insert_with(|| {
+                                claim_bom_ref(bom_ref.clone(), &mut bom_refs, &mut taken)
+                            });
+                            if *existing_ref != bom_ref {
+                                renamed_refs.insert(bom_ref, existing_ref.clone());
+                            }
+                        }
+                    }
+                    None => {
+                        rename_taken_bom_refs(
+                            &mut component,
+                            &mut bom_refs,
+                            &mut taken,
+                            &mut renamed_refs,
                         );
+                        if let Some(purl) = purl {
+                            purls.insert(purl, Some(components.0.len()));
+                        }
+                        components.0.push(component);
                     }
                 }
             }
         }
 
-        // Check compositions, its dependencies & assemblies
-        if let Some(compositions) = &self.compositions {
-            for composition in &compositions.0 {
-                if let Some(assemblies) = &composition.assemblies {
-                    for BomReference(assembly) in assemblies {
-                        if !bom_refs.contains(assembly) {
-                            context.add_custom(
-                                "composition ref",
-                                format!(
-                                    "Composition reference '{assembly}' does not exist in the BOM"
-                                ),
-                            );
-                        }
-                    }
+        if let Some(other_services) = other.services {
+            let services = self.services.get_or_insert_with(|| Services(Vec::new()));
+            for service in other_services.0 {
+                match &service.bom_ref {
+                    Some(bom_ref) if bom_refs.contains_key(bom_ref) => {}
+                    _ => services.0.push(service),
+                }
+            }
+        }
+
+        if let Some(other_vulnerabilities) = other.vulnerabilities {
+            let vulnerabilities = self
+                .vulnerabilities
+                .get_or_insert_with(|| Vulnerabilities(Vec::new()));
+            for vulnerability in other_vulnerabilities.0 {
+                match &vulnerability.bom_ref {
+                    Some(bom_ref) if bom_refs.contains_key(bom_ref) => {}
+                    _ => vulnerabilities.0.push(vulnerability),
+                }
+            }
+        }
+
+        if let Some(other_dependencies) = other.dependencies {
+            let rename = |bom_ref: String| renamed_refs.get(&bom_ref).cloned().unwrap_or(bom_ref);
+            let dependencies = self
+                .dependencies
+                .get_or_insert_with(|| Dependencies(Vec::new()));
+            for dependency in other_dependencies.0 {
+                let dependency_ref = rename(dependency.dependency_ref);
+                let sub_dependencies = dependency.dependencies.into_iter().map(rename);
+
+                match dependencies
+                    .0
+                    .iter_mut()
+                    .find(|existing| existing.dependency_ref == dependency_ref)
+                {
+                    Some(existing) => {
+                        for sub_dependency in sub_dependencies {
+                            if !existing.dependencies.contains(&sub_dependency) {
+                                existing.dependencies.push(sub_dependency);
+                            }
+                        }
+                    }
+                    None => dependencies.0.push(Dependency {
+                        dependency_ref,
+                        dependencies: sub_dependencies.collect(),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Iterates over the metadata component, the top level components and all of their nested
+    /// components in depth-first order, yielding every component before its children.
+    ///
+    /// The traversal is lazy, it only keeps track of the path to the current component.
+    pub fn all_components(&self) -> impl Iterator<Item = &Component> {
+        let mut stack = Vec::new();
+        if let Some(components) = &self.components {
+            stack.push(components.0.iter());
+        }
+        if let Some(component) = self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.component.as_ref())
+        {
+            stack.push(std::slice::from_ref(component).iter());
+        }
+        AllComponents { stack }
+    }
+
+    /// Calls `edit` for every component in the same order as [`Bom::all_components`].
+    ///
+    /// This takes a callback rather than returning an iterator, as a component cannot be handed
+    /// out mutably while its nested components are still to be visited.
+    pub fn all_components_mut(&mut self, mut edit: impl FnMut(&mut Component)) {
+        fn visit(component: &mut Component, edit: &mut impl FnMut(&mut Component)) {
+            edit(component);
+            if let Some(components) = &mut component.components {
+                for component in &mut components.0 {
+                    visit(component, edit);
+                }
+            }
+        }
+
+        if let Some(component) = self
+            .metadata
+            .as_mut()
+            .and_then(|metadata| metadata.component.as_mut())
+        {
+            visit(component, &mut edit);
+        }
+        if let Some(components) = &mut self.components {
+            for component in &mut components.0 {
+                visit(component, &mut edit);
+            }
+        }
+    }
+
+    /// Assigns a Bom reference to every component and service without one, returning the number
+    /// of references assigned.
+    ///
+    /// The reference is the component's purl if present, otherwise `group/name@version`. A
+    /// counter is appended to references that are already taken, so the result only depends on
+    /// the order of the elements in the BOM.
+    pub fn assign_missing_bom_refs(&mut self) -> usize {
+        fn assign_services(
+            services: &mut Services,
+            bom_refs: &mut HashMap<String, RefKind>,
+        ) -> usize {
+            let mut assigned = 0;
+            for service in &mut services.0 {
+                if service.bom_ref.is_none() {
+                    let label = element_label(
+                        service.group.as_deref(),
+                        &service.name,
+                        service.version.as_deref(),
+                    );
+                    service.bom_ref = Some(unique_bom_ref(label, bom_refs, RefKind::Service));
+                    assigned += 1;
+                }
+                if let Some(services) = &mut service.services {
+                    assigned += assign_services(services, bom_refs);
+                }
+            }
+            assigned
+        }
+
+        let mut bom_refs = self.collect_bom_refs();
+        let mut assigned = 0;
+
+        self.all_components_mut(|component| {
+            if component.bom_ref.is_none() {
+                let label = match &component.purl {
+                    Some(purl) => purl.to_string(),
+                    None => element_label(
+                        component.group.as_deref(),
+                        &component.name,
+                        component.version.as_deref(),
+                    ),
+                };
+                component.bom_ref = Some(unique_bom_ref(label, &mut bom_refs, RefKind::Component));
+                assigned += 1;
+            }
+        });
+        if let Some(services) = &mut self.services {
+            assigned += assign_services(services, &mut bom_refs);
+        }
+
+        assigned
+    }
+
+    /// Collects the Bom references of all components, services and vulnerabilities, including
+    /// nested ones, the components of pedigrees and the components and services of formulas.
+    ///
+    /// If a Bom reference is used more than once, the first occurrence determines its [`RefKind`].
+    pub fn collect_bom_refs(&self) -> HashMap<String, RefKind> {
+        let mut bom_refs = HashMap::new();
+        self.visit_bom_refs(|bom_ref, kind| {
+            bom_refs.entry(bom_ref.to_string()).or_insert(kind);
+        });
+        bom_refs
+    }
+
+    /// Calls `visit` for every Bom reference in document order, duplicates included.
+    fn visit_bom_refs(&self, mut visit: impl FnMut(&str, RefKind)) {
+        if let Some(component) = self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.component.as_ref())
+        {
+            visit_component_bom_refs(component, &mut visit);
+        }
+
+        if let Some(components) = &self.components {
+            for component in &components.0 {
+                visit_component_bom_refs(component, &mut visit);
+            }
+        }
+
+        if let Some(services) = &self.services {
+            for service in &services.0 {
+                visit_service_bom_refs(service, &mut visit);
+            }
+        }
+
+        if let Some(vulnerabilities) = &self.vulnerabilities {
+            for bom_ref in vulnerabilities
+                .0
+                .iter()
+                .filter_map(|vulnerability| vulnerability.bom_ref.as_ref())
+            {
+                visit(bom_ref, RefKind::Vulnerability);
+            }
+        }
+
+        for formula in self.formulation.iter().flatten() {
+            for component in formula.components.iter().flat_map(|c| &c.0) {
+                visit_component_bom_refs(component, &mut visit);
+            }
+            for service in formula.services.iter().flat_map(|s| &s.0) {
+                visit_service_bom_refs(service, &mut visit);
+            }
+        }
+    }
+
+    /// Records that the element referenced by `from_ref` depends on the one referenced by `to_ref`.
+    ///
+    /// The dependency entry of `from_ref` is created if it does not exist yet, an edge that is
+    /// already present is not added twice.
+    pub fn add_dependency(&mut self, from_ref: &str, to_ref: &str) {
+        let dependencies = &mut self
+            .dependencies
+            .get_or_insert_with(|| Dependencies(Vec::new()))
+            .0;
+
+        let index = match dependencies
+            .iter()
+            .position(|dependency| dependency.dependency_ref == from_ref)
+        {
+            Some(index) => index,
+            None => {
+                dependencies.push(Dependency {
+                    dependency_ref: from_ref.to_string(),
+                    dependencies: Vec::new(),
+                });
+                dependencies.len() - 1
+            }
+        };
+
+        let dependency = &mut dependencies[index];
+        if !dependency.dependencies.iter().any(|d| d == to_ref) {
+            dependency.dependencies.push(to_ref.to_string());
+        }
+    }
+
+    /// Returns `true` if the dependency graph has an edge from `from_ref` to `to_ref`.
+    pub fn depends(&self, from_ref: &str, to_ref: &str) -> bool {
+        self.dependencies
+            .iter()
+            .flat_map(|dependencies| &dependencies.0)
+            .filter(|dependency| dependency.dependency_ref == from_ref)
+            .any(|dependency| dependency.dependencies.iter().any(|d| d == to_ref))
+    }
+
+    /// Counts the vulnerabilities in the BOM by their severity.
+    ///
+    /// Only the most severe rating of every vulnerability is taken into account. Vulnerabilities
+    /// without any rated severity are counted as [`Severity::Unknown`].
+    pub fn count_vulnerabilities_by_severity(&self) -> BTreeMap<Severity, usize> {
+        let mut counts = BTreeMap::new();
+
+        for vulnerability in self.vulnerabilities.iter().flat_map(|v| &v.0) {
+            let highest = vulnerability
+                .vulnerability_ratings
+                .iter()
+                .flat_map(|ratings| &ratings.0)
+                .filter_map(|rating| rating.severity.as_ref())
+                .max()
+                .cloned()
+                .unwrap_or(Severity::Unknown);
+
+            *counts.entry(highest).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// Returns the vulnerabilities affecting the component or service referenced by `bom_ref`.
+    ///
+    /// If a vulnerability lists the affected versions, the version of the component with that
+    /// reference is matched against them, see [`VulnerabilityTarget::affects`]. Vulnerabilities
+    /// affecting a service or a component without version are always included.
+    ///
+    /// [`VulnerabilityTarget::affects`]: crate::models::vulnerability_target::VulnerabilityTarget::affects
+    pub fn vulnerabilities_for(&self, bom_ref: &str) -> Vec<&Vulnerability> {
+        let version = self
+            .all_components()
+            .find(|component| component.bom_ref.as_deref() == Some(bom_ref))
+            .and_then(|component| component.version.as_deref());
+
+        self.vulnerabilities
+            .iter()
+            .flat_map(|vulnerabilities| &vulnerabilities.0)
+            .filter(|vulnerability| {
+                vulnerability
+                    .vulnerability_targets
+                    .iter()
+                    .flat_map(|targets| &targets.0)
+                    .any(|target| target.bom_ref == bom_ref && target.affects(version))
+            })
+            .collect()
+    }
+
+    /// Returns the most severe rating of all vulnerabilities in the BOM, if any are rated.
+    ///
+    /// This is the greatest [`Severity`], so ratings of `unknown` or `none` only count if there
+    /// is no other rating.
+    pub fn highest_severity(&self) -> Option<Severity> {
+        self.vulnerabilities
+            .iter()
+            .flat_map(|vulnerabilities| &vulnerabilities.0)
+            .flat_map(|vulnerability| &vulnerability.vulnerability_ratings)
+            .flat_map(|ratings| &ratings.0)
+            .filter_map(|rating| rating.severity.as_ref())
+            .max()
+            .cloned()
+    }
+
+    /// Sorts the top level components and all nested components by group, name, version and
+    /// purl, so that the same set of components always produces the same document.
+    ///
+    /// Versions are compared by semver precedence where they parse, e.g. `1.0.9` comes before
+    /// `1.0.10`, and as plain strings after all semver versions otherwise.
+    ///
+    /// The sort is stable, components that agree on all of these keep their relative order.
+    pub fn sort_components(&mut self) {
+        fn sort(components: &mut Components) {
+            components
+                .0
+                .sort_by(|a, b| component_sort_key(a).cmp(&component_sort_key(b)));
+            for component in &mut components.0 {
+                if let Some(components) = &mut component.components {
+                    sort(components);
+                }
+            }
+        }
+
+        if let Some(components) = self
+            .metadata
+            .as_mut()
+            .and_then(|metadata| metadata.component.as_mut())
+            .and_then(|component| component.components.as_mut())
+        {
+            sort(components);
+        }
+        if let Some(components) = &mut self.components {
+            sort(components);
+        }
+    }
+
+    /// Sorts the dependency entries by their reference and the references each entry depends on.
+    pub fn sort_dependencies(&mut self) {
+        if let Some(dependencies) = &mut self.dependencies {
+            dependencies
+                .0
+                .sort_by(|a, b| a.dependency_ref.cmp(&b.dependency_ref));
+            for dependency in &mut dependencies.0 {
+                dependency.dependencies.sort();
+            }
+        }
+    }
+}
+
+/// The order used by [`Bom::sort_components`].
+fn component_sort_key(
+    component: &Component,
+) -> (Option<&str>, &str, Option<VersionKey<'_>>, Option<&str>) {
+    (
+        component.group.as_deref(),
+        &component.name,
+        component.version.as_deref().map(VersionKey::new),
+        component.purl.as_ref().map(|purl| purl.0.as_str()),
+    )
+}
+
+/// Orders versions by semver precedence, versions that are not semver come last.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum VersionKey<'a> {
+    Semver(semver::Version),
+    Text(&'a str),
+}
+
+impl<'a> VersionKey<'a> {
+    fn new(version: &'a str) -> Self {
+        semver::Version::parse(version).map_or(Self::Text(version), Self::Semver)
+    }
+}
+
+impl Default for Bom {
+    /// Construct a BOM with a default `version` of `1` and `serial_number` with a random UUID
+    fn default() -> Self {
+        Self {
+            version: 1,
+            serial_number: Some(UrnUuid::generate()),
+            metadata: None,
+            components: None,
+            services: None,
+            external_references: None,
+            dependencies: None,
+            compositions: None,
+            properties: None,
+            vulnerabilities: None,
+            signature: None,
+            annotations: None,
+            formulation: None,
+            spec_version: SpecVersion::V1_3,
+            foreign_elements: Vec::new(),
+            extra: Extensions::default(),
+        }
+    }
+}
+
+impl Validate for Bom {
+    fn validate_version(&self, version: SpecVersion) -> ValidationResult {
+        let mut context = ValidationContext::new();
+        context.add_field_option(
+            "serial_number",
+            self.serial_number.as_ref(),
+            validate_urn_uuid,
+        );
+        context.add_struct_option("metadata", self.metadata.as_ref(), version);
+        context.add_struct_option("components", self.components.as_ref(), version);
+        context.add_struct_option("services", self.services.as_ref(), version);
+        context.add_struct_option(
+            "external_references",
+            self.external_references.as_ref(),
+            version,
+        );
+        context.add_struct_option("compositions", self.compositions.as_ref(), version);
+        context.add_struct_option("properties", self.properties.as_ref(), version);
+        context.add_struct_option("vulnerabilities", self.vulnerabilities.as_ref(), version);
+        context.add_struct_option("annotations", self.annotations.as_ref(), version);
+        context.add_list_option("formulation", self.formulation.as_ref(), |formula| {
+            formula.validate_version(version)
+        });
+
+        // Collect all Bom references inside, duplicates are reported while walking the tree.
+        let mut bom_refs = HashMap::new();
+        self.visit_bom_refs(|bom_ref, kind| {
+            if bom_refs.contains_key(bom_ref) {
+                context.add_custom("bom_ref", format!(r#"Bom ref "{bom_ref}" is not unique"#));
+            } else {
+                bom_refs.insert(bom_ref.to_string(), kind);
+            }
+        });
+
+        let metadata_component = self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.component.as_ref());
+
+        // The root component is described by the metadata, listing it again duplicates it
+        if let (Some(root), Some(components)) = (metadata_component, &self.components) {
+            for (index, component) in components.0.iter().enumerate() {
+                let same_bom_ref = root.bom_ref.is_some() && root.bom_ref == component.bom_ref;
+                let same_purl = root.purl.is_some() && root.purl == component.purl;
+                if same_bom_ref || same_purl {
+                    context.add_warning(
+                        &format!("components.inner[{index}]"),
+                        "Component duplicates the metadata component",
+                    );
+                }
+            }
+        }
+
+        // Components sharing a purl are most likely listed twice, the metadata component is
+        // already covered above. Each shared purl is reported once, in document order.
+        let mut purls: Vec<(Purl, usize)> = Vec::new();
+        let mut purl_indices: HashMap<Purl, usize> = HashMap::new();
+        for component in self.all_components() {
+            if metadata_component.is_some_and(|root| std::ptr::eq(root, component)) {
+                continue;
+            }
+            if let Some(purl) = &component.purl {
+                let purl = purl.canonical();
+                match purl_indices.get(&purl) {
+                    Some(&index) => purls[index].1 += 1,
+                    None => {
+                        purl_indices.insert(purl.clone(), purls.len());
+                        purls.push((purl, 1));
+                    }
+                }
+            }
+        }
+        for (purl, count) in purls.into_iter().filter(|(_, count)| *count > 1) {
+            context.add_warning(
+                "components",
+                format!(r#"Purl "{purl}" is not unique, {count} components share it"#),
+            );
+        }
+
+        // Check dependencies & sub dependencies
+        if let Some(dependencies) = &self.dependencies {
+            for dependency in &dependencies.0 {
+                if !bom_refs.contains_key(&dependency.dependency_ref) {
+                    context.add_custom(
+                        "dependency_ref",
+                        format!(
+                            "Dependency ref '{}' does not exist in the BOM",
+                            dependency.dependency_ref
+                        ),
+                    );
+                }
+
+                for sub_dependency in &dependency.dependencies {
+                    if !bom_refs.contains_key(sub_dependency) {
+                        context.add_custom(
+                            "sub dependency_ref",
+                            format!(
+                                "Dependency ref '{}' does not exist in the BOM",
+                                sub_dependency
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        // Check compositions, its dependencies & assemblies
+        if let Some(compositions) = &self.compositions {
+            for composition in &compositions.0 {
+                if let Some(assemblies) = &composition.assemblies {
+                    for BomReference(assembly) in assemblies {
+                        if !bom_refs.contains_key(assembly) {
+                            context.add_custom(
+                                "composition ref",
+                                format!(
+                                    "Composition reference '{assembly}' does not exist in the BOM"
+                                ),
+                            );
+                        }
+                    }
                 }
 
                 if let Some(dependencies) = &composition.dependencies {
                     for BomReference(dependency) in dependencies {
-                        if !bom_refs.contains(dependency) {
+                        if !bom_refs.contains_key(dependency) {
                             context.add_custom(
                                 "composition ref",
                                 format!(
@@ -455,116 +1530,224 @@ impl Validate for Bom {
             }
         }
 
+        // Check annotation subjects
+        if let Some(annotations) = &self.annotations {
+            for annotation in &annotations.0 {
+                for subject in &annotation.subjects {
+                    if !bom_refs.contains_key(subject) {
+                        context.add_custom(
+                            "annotation subject",
+                            format!("Annotation subject '{subject}' does not exist in the BOM"),
+                        );
+                    }
+                }
+            }
+        }
+
+        // Check that vulnerabilities affect components or services of this BOM, BOM-Links
+        // pointing into other BOMs cannot be resolved and are skipped.
+        if let Some(vulnerabilities) = &self.vulnerabilities {
+            for vulnerability in &vulnerabilities.0 {
+                for target in vulnerability
+                    .vulnerability_targets
+                    .iter()
+                    .flat_map(|targets| &targets.0)
+                {
+                    let affected = bom_refs.get(&target.bom_ref);
+                    if !target.bom_ref.starts_with("urn:cdx:")
+                        && !matches!(affected, Some(RefKind::Component | RefKind::Service))
+                    {
+                        context.add_custom(
+                            "vulnerability affects",
+                            format!(
+                                "Affected ref '{}' does not refer to a component or service in the BOM",
+                                target.bom_ref
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
         context.into()
     }
 
     fn validate(&self) -> ValidationResult {
-        self.validate_version(self.spec_version)
+        self.validate_with_options(&ValidationOptions::default())
     }
 }
 
-#[derive(Default)]
-struct BomReferencesContext {
-    component_bom_refs: HashSet<String>,
-    service_bom_refs: HashSet<String>,
-    vulnerabilities_bom_refs: HashSet<String>,
+/// Depth-first iterator over components, see [`Bom::all_components`].
+struct AllComponents<'a> {
+    stack: Vec<std::slice::Iter<'a, Component>>,
 }
 
-impl BomReferencesContext {
-    fn contains(&self, bom_ref: &String) -> bool {
-        self.component_bom_refs.contains(bom_ref)
-            || self.service_bom_refs.contains(bom_ref)
-            || self.vulnerabilities_bom_refs.contains(bom_ref)
-    }
+impl<'a> Iterator for AllComponents<'a> {
+    type Item = &'a Component;
 
-    fn add_component_bom_ref(&mut self, bom_ref: impl ToString) {
-        self.component_bom_refs.insert(bom_ref.to_string());
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some(component) => {
+                    if let Some(components) = &component.components {
+                        self.stack.push(components.0.iter());
+                    }
+                    return Some(component);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
     }
+}
+
+/// The kind of element a Bom reference points to, see [`Bom::collect_bom_refs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RefKind {
+    Component,
+    Service,
+    Vulnerability,
+}
 
-    fn add_service_bom_ref(&mut self, bom_ref: impl ToString) {
-        self.service_bom_refs.insert(bom_ref.to_string());
+fn visit_component_bom_refs(component: &Component, visit: &mut impl FnMut(&str, RefKind)) {
+    if let Some(bom_ref) = &component.bom_ref {
+        visit(bom_ref, RefKind::Component);
     }
 
-    fn add_vulnerability_bom_ref(&mut self, bom_ref: impl ToString) {
-        self.vulnerabilities_bom_refs.insert(bom_ref.to_string());
+    let pedigree = component.pedigree.as_ref();
+    let nested = [
+        component.components.as_ref(),
+        pedigree.and_then(|pedigree| pedigree.ancestors.as_ref()),
+        pedigree.and_then(|pedigree| pedigree.descendants.as_ref()),
+        pedigree.and_then(|pedigree| pedigree.variants.as_ref()),
+    ];
+    for components in nested.into_iter().flatten() {
+        for component in &components.0 {
+            visit_component_bom_refs(component, visit);
+        }
     }
 }
 
-/// Validates the Bom references.
-fn validate_component_bom_refs(
-    context: &mut ValidationContext,
-    bom_refs: &mut BomReferencesContext,
-    component: &Component,
-) {
-    if let Some(bom_ref) = &component.bom_ref {
-        if bom_refs.contains(bom_ref) {
-            context.add_custom("bom_ref", format!(r#"Bom ref "{bom_ref}" is not unique"#));
+/// Wraps `reader` in a gzip decoder, after checking that it starts with the gzip magic bytes.
+/// Without this check, plain input would only fail later with a confusing parse error.
+#[cfg(feature = "gzip")]
+fn gzip_decoder<R: std::io::Read>(
+    mut reader: R,
+) -> std::io::Result<flate2::read::GzDecoder<impl std::io::Read>> {
+    use std::io::{Error, ErrorKind, Read};
+
+    let mut magic = [0; 2];
+    match reader.read_exact(&mut magic) {
+        Ok(()) if magic == [0x1f, 0x8b] => {}
+        Ok(()) => {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "not a gzip stream, expected the magic bytes 1f 8b",
+            ))
+        }
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => {
+            return Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "not a gzip stream, the input is too short",
+            ))
         }
-        bom_refs.add_component_bom_ref(bom_ref);
+        Err(error) => return Err(error),
     }
 
-    if let Some(components) = &component.components {
-        validate_components(context, bom_refs, components);
-    }
+    Ok(flate2::read::GzDecoder::new(
+        std::io::Cursor::new(magic).chain(reader),
+    ))
 }
 
-fn validate_components(
-    context: &mut ValidationContext,
-    bom_refs: &mut BomReferencesContext,
-    components: &Components,
-) {
-    for component in &components.0 {
-        validate_component_bom_refs(context, bom_refs, component);
+/// Formats an element as `group/name@version`, leaving out the parts it does not have.
+fn element_label(group: Option<&str>, name: &str, version: Option<&str>) -> String {
+    let mut label = match group {
+        Some(group) => format!("{group}/{name}"),
+        None => name.to_string(),
+    };
+    if let Some(version) = version {
+        label.push_str(&format!("@{version}"));
     }
+    label
 }
 
-fn validate_services(
-    context: &mut ValidationContext,
-    bom_refs: &mut BomReferencesContext,
-    services: &Services,
-) {
-    for service in &services.0 {
-        validate_service_bom_refs(context, bom_refs, service);
-    }
+/// Returns `bom_ref`, or a fresh bom-ref if it is already in `bom_refs`, and marks the result as
+/// used in both `bom_refs` and `taken`.
+fn claim_bom_ref(
+    bom_ref: String,
+    bom_refs: &mut HashMap<String, RefKind>,
+    taken: &mut HashMap<String, RefKind>,
+) -> String {
+    let bom_ref = if bom_refs.contains_key(&bom_ref) {
+        unique_bom_ref(bom_ref, taken, RefKind::Component)
+    } else {
+        bom_ref
+    };
+    bom_refs.insert(bom_ref.clone(), RefKind::Component);
+    taken.insert(bom_ref.clone(), RefKind::Component);
+    bom_ref
 }
 
-fn validate_service_bom_refs(
-    context: &mut ValidationContext,
-    bom_refs: &mut BomReferencesContext,
-    service: &Service,
+/// Claims the bom-refs of a merged component and its nested components, recording the ones
+/// that had to be renamed in `renamed_refs`.
+fn rename_taken_bom_refs(
+    component: &mut Component,
+    bom_refs: &mut HashMap<String, RefKind>,
+    taken: &mut HashMap<String, RefKind>,
+    renamed_refs: &mut HashMap<String, String>,
 ) {
-    if let Some(bom_ref) = &service.bom_ref {
-        if bom_refs.contains(bom_ref) {
-            context.add_custom("bom_ref", format!(r#"Bom ref "{bom_ref}" is not unique"#));
+    if let Some(bom_ref) = component.bom_ref.take() {
+        let claimed = claim_bom_ref(bom_ref.clone(), bom_refs, taken);
+        if claimed != bom_ref {
+            renamed_refs.insert(bom_ref, claimed.clone());
         }
-        bom_refs.add_service_bom_ref(bom_ref);
+        component.bom_ref = Some(claimed);
     }
 
-    if let Some(services) = &service.services {
-        validate_services(context, bom_refs, services);
+    let pedigree = component.pedigree.as_mut();
+    let (ancestors, descendants, variants) = match pedigree {
+        Some(pedigree) => (
+            pedigree.ancestors.as_mut(),
+            pedigree.descendants.as_mut(),
+            pedigree.variants.as_mut(),
+        ),
+        None => (None, None, None),
+    };
+    let nested = [
+        component.components.as_mut(),
+        ancestors,
+        descendants,
+        variants,
+    ];
+    for components in nested.into_iter().flatten() {
+        for component in &mut components.0 {
+            rename_taken_bom_refs(component, bom_refs, taken, renamed_refs);
+        }
     }
 }
 
-fn validate_vulnerabilities(
-    context: &mut ValidationContext,
-    bom_refs: &mut BomReferencesContext,
-    vulnerabilities: &Vulnerabilities,
-) {
-    for vulnerability in &vulnerabilities.0 {
-        validate_vulnerabilities_bom_refs(context, bom_refs, vulnerability);
+/// Returns `base`, with a counter appended if it is already taken, and marks it as taken.
+fn unique_bom_ref(base: String, bom_refs: &mut HashMap<String, RefKind>, kind: RefKind) -> String {
+    let mut candidate = base.clone();
+    let mut counter = 1;
+    while bom_refs.contains_key(&candidate) {
+        candidate = format!("{base}-{counter}");
+        counter += 1;
     }
+    bom_refs.insert(candidate.clone(), kind);
+    candidate
 }
 
-fn validate_vulnerabilities_bom_refs(
-    context: &mut ValidationContext,
-    bom_refs: &mut BomReferencesContext,
-    vulnerability: &Vulnerability,
-) {
-    if let Some(bom_ref) = &vulnerability.bom_ref {
-        if bom_refs.contains(bom_ref) {
-            context.add_custom("bom_ref", format!(r#"Bom ref "{bom_ref}" is not unique"#));
+fn visit_service_bom_refs(service: &Service, visit: &mut impl FnMut(&str, RefKind)) {
+    if let Some(bom_ref) = &service.bom_ref {
+        visit(bom_ref, RefKind::Service);
+    }
+
+    if let Some(services) = &service.services {
+        for service in &services.0 {
+            visit_service_bom_refs(service, visit);
         }
-        bom_refs.add_vulnerability_bom_ref(bom_ref);
     }
 }
 
@@ -623,15 +1806,18 @@ fn matches_urn_uuid_regex(value: &str) -> bool {
 mod test {
     use crate::{
         external_models::{
-            date_time::DateTime, normalized_string::NormalizedString, uri::Uri as Url,
+            date_time::DateTime,
+            normalized_string::NormalizedString,
+            uri::{Purl, Uri as Url},
         },
         models::{
-            component::{Classification, Component},
+            component::{Classification, Component, Pedigree},
             composition::{AggregateType, Composition},
             dependency::Dependency,
-            external_reference::{ExternalReference, ExternalReferenceType, Uri},
+            external_reference::{BomLink, ExternalReference, ExternalReferenceType, Uri},
             property::Property,
             service::Service,
+            tool::{Tool, Tools},
             vulnerability::Vulnerability,
         },
         validation,
@@ -654,22 +1840,109 @@ mod test {
     }
 
     #[test]
-    fn it_should_validate_an_empty_bom_as_passed() {
-        let bom = Bom {
-            version: 1,
-            spec_version: SpecVersion::V1_3,
-            serial_number: None,
-            metadata: None,
-            components: None,
-            services: None,
-            external_references: None,
-            dependencies: None,
+    fn it_should_normalize_timestamps_to_utc_if_configured() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "metadata": { "timestamp": "2024-03-01T12:30:00+02:00" },
+            "formulation": [
+                {
+                    "workflows": [
+                        {
+                            "bom-ref": "workflow-1",
+                            "uid": "workflow-1",
+                            "taskTypes": ["build"],
+                            "timeStart": "2024-03-01T12:00:00-05:00",
+                            "tasks": [
+                                {
+                                    "bom-ref": "task-1",
+                                    "uid": "task-1",
+                                    "taskTypes": ["build"],
+                                    "timeEnd": "2024-03-01T23:30:00.5+01:00"
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        let unchanged = bom.clone().with_output_options(OutputOptions::default());
+        assert_eq!(unchanged, bom);
+
+        let normalized =
+            bom.with_output_options(OutputOptions::new().normalize_timestamps_to_utc(true));
+        let mut output = Vec::new();
+        normalized
+            .output_as_json_version(&mut output, SpecVersion::V1_5, JsonOutputOptions::Compact)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(r#""timestamp":"2024-03-01T10:30:00Z""#));
+        assert!(output.contains(r#""timeStart":"2024-03-01T17:00:00Z""#));
+        assert!(output.contains(r#""timeEnd":"2024-03-01T22:30:00.5Z""#));
+    }
+
+    #[test]
+    fn it_should_write_compact_json() {
+        let bom = Bom::with_single_component(Component::new(
+            Classification::Library,
+            "serde",
+            "1.0.193",
+            Some("serde-1.0.193".to_string()),
+        ));
+
+        let mut compact = Vec::new();
+        bom.clone()
+            .output_as_json_version(&mut compact, SpecVersion::V1_5, JsonOutputOptions::Compact)
+            .expect("Failed to write JSON");
+        assert!(!compact.contains(&b'\n'));
+
+        let mut pretty = Vec::new();
+        bom.clone()
+            .output_as_json_v1_5(&mut pretty)
+            .expect("Failed to write JSON");
+        assert!(compact.len() < pretty.len());
+
+        let parsed = Bom::parse_from_json_v1_5(compact.as_slice()).expect("Failed to parse JSON");
+        assert_eq!(parsed, bom);
+    }
+
+    #[test]
+    fn it_should_write_pretty_json_with_a_custom_indentation() {
+        let mut output = Vec::new();
+        Bom::default()
+            .output_as_json_version(
+                &mut output,
+                SpecVersion::V1_5,
+                JsonOutputOptions::Pretty { indent: 4 },
+            )
+            .expect("Failed to write JSON");
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("{\n    \"bomFormat\": \"CycloneDX\",\n"));
+    }
+
+    #[test]
+    fn it_should_validate_an_empty_bom_as_passed() {
+        let bom = Bom {
+            version: 1,
+            spec_version: SpecVersion::V1_3,
+            serial_number: None,
+            metadata: None,
+            components: None,
+            services: None,
+            external_references: None,
+            dependencies: None,
             compositions: None,
             vulnerabilities: None,
             signature: None,
             annotations: None,
             properties: None,
             formulation: None,
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         };
 
         let actual = bom.validate();
@@ -677,6 +1950,286 @@ mod test {
         assert!(actual.passed());
     }
 
+    #[test]
+    fn it_should_cap_the_number_of_validation_errors() {
+        let components = (0..10)
+            .map(|_| {
+                let mut component = Component::new(Classification::Library, "", "1.0.0", None);
+                component.name = NormalizedString::new_unchecked("invalid\tname".to_string());
+                component
+            })
+            .collect();
+        let bom = Bom {
+            components: Some(Components(components)),
+            ..Bom::default()
+        };
+        assert_eq!(bom.validate().error_count(), 10);
+
+        let actual = bom.validate_capped(3);
+
+        assert_eq!(actual.error_count(), 3);
+        assert!(actual.is_truncated());
+        match actual.error("components") {
+            Some(validation::ValidationErrorsKind::Struct(components)) => {
+                match components.error("inner") {
+                    Some(validation::ValidationErrorsKind::List(errors)) => {
+                        assert_eq!(errors.keys().copied().collect::<Vec<_>>(), [0, 1, 2])
+                    }
+                    other => panic!("Unexpected errors: {other:?}"),
+                }
+            }
+            other => panic!("Unexpected errors: {other:?}"),
+        }
+
+        let actual = bom.validate_capped(10);
+        assert!(!actual.is_truncated());
+        assert_eq!(actual, bom.validate());
+    }
+
+    #[test]
+    fn it_should_downgrade_unknown_enum_values_to_warnings() {
+        let mut component = Component::new(
+            Classification::UnknownClassification("foo".to_string()),
+            "name",
+            "1.0.0",
+            None,
+        );
+        component.purl = Some(Purl::cargo("name", "1.0.0").unwrap());
+        let bom = Bom {
+            components: Some(Components(vec![component])),
+            ..Bom::default()
+        };
+
+        assert_eq!(
+            bom.validate(),
+            validation::r#struct(
+                "components",
+                validation::list(
+                    "inner",
+                    [(
+                        0,
+                        validation::field(
+                            "component_type",
+                            ValidationError::unknown_value("Unknown classification")
+                        )
+                    )]
+                )
+            )
+        );
+
+        let options = ValidationOptions {
+            treat_unknown_enums_as_warnings: true,
+            ..ValidationOptions::default()
+        };
+        assert_eq!(
+            bom.validate_with_options(&options),
+            validation::warning(
+                "components.inner[0].component_type",
+                [ValidationError::unknown_value("Unknown classification")]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_not_downgrade_values_unsupported_by_the_spec_version() {
+        let mut component = Component::new(Classification::Platform, "name", "1.0.0", None);
+        component.purl = Some(Purl::cargo("name", "1.0.0").unwrap());
+        let bom = Bom {
+            components: Some(Components(vec![component])),
+            spec_version: SpecVersion::V1_4,
+            ..Bom::default()
+        };
+
+        let options = ValidationOptions {
+            treat_unknown_enums_as_warnings: true,
+            ..ValidationOptions::default()
+        };
+        let result = bom.validate_with_options(&options);
+
+        assert!(result.has_errors());
+        assert!(!result.has_warnings());
+    }
+
+    #[test]
+    fn it_should_require_a_serial_number_if_configured() {
+        let bom = Bom {
+            serial_number: None,
+            ..Bom::default()
+        };
+        assert!(bom.validate().passed());
+
+        let options = ValidationOptions {
+            require_serial_number: true,
+            ..ValidationOptions::default()
+        };
+        assert_eq!(
+            bom.validate_with_options(&options),
+            validation::field("serial_number", "Serial number is required")
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_bom_links_to_a_newer_version_of_the_same_bom() {
+        let link = |version: u32| ExternalReference {
+            external_reference_type: ExternalReferenceType::Bom,
+            url: Uri::BomLink(BomLink(format!(
+                "urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/{version}"
+            ))),
+            comment: None,
+            hashes: None,
+        };
+        let bom = Bom {
+            version: 2,
+            spec_version: SpecVersion::V1_5,
+            serial_number: Some(UrnUuid(
+                "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79".to_string(),
+            )),
+            external_references: Some(ExternalReferences(vec![link(1), link(2)])),
+            ..Bom::default()
+        };
+        assert!(bom.validate().passed());
+        assert!(!bom.validate().has_warnings());
+
+        let options = ValidationOptions {
+            check_bom_link_versions: true,
+            ..ValidationOptions::default()
+        };
+        assert_eq!(
+            bom.validate_with_options(&options),
+            validation::warning(
+                "external_references.inner[1]",
+                ["Links to version 2 of the same BOM, expected it to be older than version 2"]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_repeated_purls() {
+        let component = |name: &str, purl: &str| {
+            let mut component = Component::new(
+                Classification::Library,
+                name,
+                "1.0.0",
+                Some(name.to_string()),
+            );
+            component.purl = Some(Purl::from_str(purl).unwrap());
+            component
+        };
+        let mut parent = component("parent", "pkg:cargo/parent@1.0.0");
+        parent.components = Some(Components(vec![component(
+            "nested",
+            "pkg:Cargo/serde@1.0.0",
+        )]));
+        let bom = Bom {
+            components: Some(Components(vec![
+                parent,
+                component("serde", "pkg:cargo/serde@1.0.0"),
+                component("other", "pkg:cargo/other@1.0.0"),
+            ])),
+            ..Bom::default()
+        };
+        assert_eq!(
+            bom.validate(),
+            validation::warning(
+                "components",
+                [r#"Purl "pkg:cargo/serde@1.0.0" is not unique, 2 components share it"#]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_the_metadata_component_in_the_components() {
+        let component = |bom_ref: &str| {
+            let mut component = Component::new(
+                Classification::Application,
+                "app",
+                "1.0.0",
+                Some(bom_ref.to_string()),
+            );
+            component.purl = Some(Purl::cargo("app", "1.0.0").unwrap());
+            component
+        };
+        let bom = Bom {
+            metadata: Some(Metadata {
+                component: Some(component("root")),
+                ..Metadata::default()
+            }),
+            components: Some(Components(vec![component("app")])),
+            ..Bom::default()
+        };
+        assert_eq!(
+            bom.validate(),
+            validation::warning(
+                "components.inner[0]",
+                ["Component duplicates the metadata component"]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_validate_pedigree_components_with_a_duplicate_bom_ref_as_failed() {
+        let mut ancestor = Component::new(Classification::Library, "lib-x", "0.9.0", None);
+        ancestor.bom_ref = Some("lib-x".to_string());
+        ancestor.purl = Some(Purl::cargo("lib-x", "0.9.0").unwrap());
+        let mut component = Component::new(
+            Classification::Library,
+            "lib-x",
+            "1.0.0",
+            Some("lib-x".to_string()),
+        );
+        component.purl = Some(Purl::cargo("lib-x", "1.0.0").unwrap());
+        component.pedigree = Some(Pedigree {
+            ancestors: Some(Components(vec![ancestor])),
+            descendants: None,
+            variants: None,
+            commits: None,
+            patches: None,
+            notes: None,
+        });
+        let bom = Bom {
+            components: Some(Components(vec![component])),
+            ..Bom::default()
+        };
+
+        assert_eq!(
+            bom.validate(),
+            validation::custom("bom_ref", [r#"Bom ref "lib-x" is not unique"#])
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_future_timestamps_if_configured() {
+        let bom = Bom {
+            metadata: Some(Metadata {
+                timestamp: Some(DateTime("2999-01-01T00:00:00Z".to_string())),
+                ..Metadata::default()
+            }),
+            ..Bom::default()
+        };
+        assert_eq!(bom.validate(), ValidationResult::default());
+
+        let options = ValidationOptions {
+            disallow_future_timestamps: true,
+            ..ValidationOptions::default()
+        };
+        assert_eq!(
+            bom.validate_with_options(&options),
+            validation::warning("metadata.timestamp", ["Timestamp lies in the future"])
+        );
+
+        let bom = Bom {
+            metadata: Some(Metadata {
+                timestamp: Some(DateTime("2000-01-01T00:00:00Z".to_string())),
+                ..Metadata::default()
+            }),
+            ..Bom::default()
+        };
+        assert_eq!(
+            bom.validate_with_options(&options),
+            ValidationResult::default()
+        );
+    }
+
     #[test]
     fn it_should_validate_broken_dependency_refs_as_failed() {
         let bom = Bom {
@@ -697,6 +2250,8 @@ mod test {
             signature: None,
             annotations: None,
             formulation: None,
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         };
 
         let actual = bom.validate();
@@ -741,6 +2296,8 @@ mod test {
             signature: None,
             annotations: None,
             formulation: None,
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         };
 
         let actual = bom.validate_version(SpecVersion::V1_3);
@@ -801,6 +2358,8 @@ mod test {
                 signature: None,
                 model_card: None,
                 data: None,
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             }])),
             services: Some(Services(vec![Service::new("invalid\tname", None)])),
             external_references: Some(ExternalReferences(vec![ExternalReference {
@@ -853,6 +2412,8 @@ mod test {
             signature: None,
             annotations: None,
             formulation: None,
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         };
 
         let actual = bom.validate();
@@ -874,10 +2435,17 @@ mod test {
                         "inner",
                         [(
                             0,
-                            validation::field("component_type", "Unknown classification")
+                            validation::field(
+                                "component_type",
+                                ValidationError::unknown_value("Unknown classification")
+                            )
                         )]
                     )
                 ),
+                validation::warning(
+                    "components.inner[0]",
+                    ["Component has no unique identifier, expected one of purl, cpe or swid"]
+                ),
                 validation::r#struct(
                     "services",
                     validation::list(
@@ -891,23 +2459,16 @@ mod test {
                         )]
                     )
                 ),
-                validation::r#struct(
-                    "external_references",
-                    validation::list(
-                        "inner",
-                        [(
-                            0,
-                            validation::field("external_reference_type", "Unknown external reference type")
-                        )]
-                    )
-                ),
                 validation::r#struct(
                     "compositions",
                     validation::list(
                         "composition",
                         [(
                             0,
-                            validation::field("aggregate", "Unknown aggregate type")
+                            validation::field(
+                                "aggregate",
+                                ValidationError::unknown_value("Unknown aggregate type")
+                            )
                         )]
                     )
                 ),
@@ -923,7 +2484,18 @@ mod test {
                             )
                         )]
                     )
-                )
+                ),
+                validation::r#struct(
+                    "vulnerabilities",
+                    validation::list(
+                        "inner",
+                        [(0, validation::field("id", "Vulnerability id is required"))]
+                    )
+                ),
+                validation::warning(
+                    "external_references.inner[0].external_reference_type",
+                    [ValidationError::unknown_value("Unknown external reference type")]
+                ),
             ]
             .into()
         );
@@ -932,12 +2504,14 @@ mod test {
     #[test]
     fn it_should_validate_that_bom_references_are_unique() {
         let component_builder = |bom_ref: &str| {
-            Component::new(
+            let mut component = Component::new(
                 Classification::Library,
                 "lib-x",
                 "v0.1.0",
                 Some(bom_ref.to_string()),
-            )
+            );
+            component.purl = Some(Purl::from_str("pkg:cargo/lib-x@0.1.0").unwrap());
+            component
         };
         let mut component_with_sub_components = component_builder("subcomponent-component");
         component_with_sub_components.components = Some(Components(vec![component_builder(
@@ -957,7 +2531,10 @@ mod test {
                 timestamp: None,
                 tools: None,
                 authors: None,
-                component: Some(component_builder("metadata-component")),
+                component: Some(Component {
+                    purl: Some(Purl::from_str("pkg:cargo/app@0.1.0").unwrap()),
+                    ..component_builder("metadata-component")
+                }),
                 manufacture: None,
                 supplier: None,
                 licenses: None,
@@ -985,39 +2562,1672 @@ mod test {
             signature: None,
             annotations: None,
             formulation: None,
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         }
         .validate();
 
         assert_eq!(
             validation_result,
-            validation::custom(
-                "bom_ref",
-                [
-                    r#"Bom ref "metadata-component" is not unique"#,
-                    r#"Bom ref "component-component" is not unique"#,
-                    r#"Bom ref "subcomponent-component" is not unique"#,
-                    r#"Bom ref "service-service" is not unique"#,
-                    r#"Bom ref "subservice-service" is not unique"#,
-                    r#"Bom ref "component-service" is not unique"#,
-                ]
-            ),
+            vec![
+                validation::custom(
+                    "bom_ref",
+                    [
+                        r#"Bom ref "metadata-component" is not unique"#,
+                        r#"Bom ref "component-component" is not unique"#,
+                        r#"Bom ref "subcomponent-component" is not unique"#,
+                        r#"Bom ref "service-service" is not unique"#,
+                        r#"Bom ref "subservice-service" is not unique"#,
+                        r#"Bom ref "component-service" is not unique"#,
+                    ]
+                ),
+                validation::warning(
+                    "components.inner[0]",
+                    ["Component duplicates the metadata component"]
+                ),
+                validation::warning(
+                    "components",
+                    [r#"Purl "pkg:cargo/lib-x@0.1.0" is not unique, 6 components share it"#]
+                ),
+            ]
+            .into(),
         );
     }
 
     #[test]
-    fn valid_uuids_should_pass_validation() {
-        let validation_result = validate_urn_uuid(&UrnUuid::from(uuid::Uuid::new_v4()));
+    fn it_should_validate_bom_references_of_formulas() {
+        let component = |name: &str, bom_ref: &str| {
+            let mut component =
+                Component::new(Classification::Library, name, "0.1.0", Some(bom_ref.into()));
+            component.purl = Some(Purl::cargo(name, "0.1.0").unwrap());
+            component
+        };
+        let mut bom = Bom {
+            spec_version: SpecVersion::V1_5,
+            serial_number: None,
+            components: Some(Components(vec![component("lib-x", "lib-x")])),
+            formulation: Some(vec![Formula {
+                bom_ref: None,
+                components: Some(Components(vec![component("lib-y", "lib-x")])),
+                services: Some(Services(vec![Service::new(
+                    "builder",
+                    Some("builder".to_string()),
+                )])),
+                workflows: None,
+                properties: None,
+            }]),
+            ..Bom::default()
+        };
+        // Formula services can be referenced from the dependency graph
+        bom.add_dependency("lib-x", "builder");
 
-        assert!(validation_result.is_ok());
+        assert_eq!(
+            bom.validate(),
+            validation::custom("bom_ref", [r#"Bom ref "lib-x" is not unique"#])
+        );
     }
 
     #[test]
-    fn invalid_uuids_should_fail_validation() {
-        let validation_result = validate_urn_uuid(&UrnUuid("invalid uuid".to_string()));
+    fn it_should_create_a_valid_bom_with_a_single_component() {
+        let mut component = Component::new(
+            Classification::Library,
+            "serde",
+            "1.0.193",
+            Some("serde-1.0.193".to_string()),
+        );
+        component.purl = Some(Purl::cargo("serde", "1.0.193").unwrap());
+
+        let bom = Bom::with_single_component(component.clone());
+
+        assert_eq!(bom.version, 1);
+        assert_eq!(bom.spec_version, SpecVersion::V1_5);
+        assert!(bom.serial_number.is_some());
+        assert_eq!(bom.components, Some(Components(vec![component])));
+        assert!(bom.validate().passed());
+    }
+
+    #[test]
+    fn it_should_keep_unknown_json_fields() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                {
+                    "type": "library",
+                    "name": "lib",
+                    "x-myorg-owner": "team-a"
+                }
+            ],
+            "x-myorg": {
+                "pipeline": "nightly",
+                "attempt": 2
+            }
+        }"#;
+        let bom = Bom::parse_from_json(input.as_bytes()).expect("Failed to parse JSON");
+
+        let mut json = Vec::new();
+        bom.output_as_json_v1_5(&mut json)
+            .expect("Failed to write JSON");
+        let json: Value = serde_json::from_slice(&json).expect("Failed to read JSON");
 
         assert_eq!(
-            validation_result,
-            Err("UrnUuid does not match regular expression".into()),
+            json["x-myorg"],
+            serde_json::json!({ "pipeline": "nightly", "attempt": 2 })
+        );
+        assert_eq!(json["components"][0]["x-myorg-owner"], "team-a");
+
+        let error = Bom::parse_from_json_strict(input.as_bytes())
+            .expect_err("Should have rejected the unknown fields");
+        assert_eq!(
+            error.to_string(),
+            "Invalid input format found: Unknown fields found: x-myorg, x-myorg-owner"
+        );
+    }
+
+    #[test]
+    fn it_should_accept_schema_fields_that_are_not_modelled_when_parsing_strictly() {
+        let input = include_str!("../../tests/spec/1.5/valid-release-notes-1.5.json");
+
+        let bom = Bom::parse_from_json_strict(input.as_bytes()).expect("Failed to parse JSON");
+
+        assert!(bom.components.unwrap().0[0]
+            .extra
+            .0
+            .contains_key("releaseNotes"));
+    }
+
+    #[test]
+    fn it_should_drop_extra_fields_when_writing_an_older_version() {
+        let input = include_str!("../../tests/spec/1.5/valid-release-notes-1.5.json");
+        let mut bom = Bom::parse_from_json(input.as_bytes()).expect("Failed to parse JSON");
+        bom.extra = Extensions(
+            [("x-myorg".to_string(), serde_json::json!("nightly"))]
+                .into_iter()
+                .collect(),
+        );
+
+        let mut json = Vec::new();
+        bom.clone()
+            .output_as_json_v1_4(&mut json)
+            .expect("Failed to write JSON");
+        let json: Value = serde_json::from_slice(&json).expect("Failed to read JSON");
+
+        assert_eq!(json.get("x-myorg"), None);
+        assert_eq!(json["components"][0].get("releaseNotes"), None);
+
+        let mut json = Vec::new();
+        bom.output_as_json_v1_5(&mut json)
+            .expect("Failed to write JSON");
+        let json: Value = serde_json::from_slice(&json).expect("Failed to read JSON");
+
+        assert_eq!(json["x-myorg"], "nightly");
+        assert!(json["components"][0].get("releaseNotes").is_some());
+    }
+
+    #[test]
+    fn it_should_drop_newer_fields_when_writing_an_older_version() {
+        for input in [
+            include_str!("../../tests/spec/1.5/valid-metadata-lifecycle-1.5.json"),
+            include_str!("../../tests/spec/1.5/valid-annotation-1.5.json"),
+            include_str!("../../tests/spec/1.5/valid-license-licensing-1.5.json"),
+        ] {
+            let bom = Bom::parse_from_json(input.as_bytes()).expect("Failed to parse JSON");
+
+            let mut json = Vec::new();
+            bom.output_as_json_v1_4(&mut json)
+                .expect("Failed to write JSON");
+            let json: Value = serde_json::from_slice(&json).expect("Failed to read JSON");
+
+            assert_eq!(json["specVersion"], "1.4");
+            assert_eq!(json["metadata"].get("lifecycles"), None);
+            assert_eq!(json.get("annotations"), None);
+        }
+    }
+
+    #[test]
+    fn it_should_normalize_crlf_line_endings_in_xml_text() {
+        let input = r#"<?xml version="1.0" encoding="utf-8"?>
+<bom xmlns="http://cyclonedx.org/schema/bom/1.5" version="1">
+  <components>
+    <component type="library">
+      <name>lib</name>
+      <licenses>
+        <license>
+          <name>Custom</name>
+          <text><![CDATA[First line
+Second line]]></text>
+        </license>
+      </licenses>
+      <description>Multi
+line</description>
+    </component>
+  </components>
+</bom>
+"#;
+        let lf = Bom::parse_from_xml_v1_5(input.as_bytes()).expect("Failed to parse XML");
+        let crlf = Bom::parse_from_xml_v1_5(input.replace('\n', "\r\n").as_bytes())
+            .expect("Failed to parse XML");
+
+        assert_eq!(lf, crlf);
+        let component = &crlf.components.as_ref().unwrap().0[0];
+        assert_eq!(
+            component.description,
+            Some(NormalizedString::new_unchecked("Multi\nline".to_string()))
+        );
+    }
+
+    #[test]
+    fn it_should_normalize_crlf_line_endings_in_utf16_xml() {
+        // U+0D0A is encoded as the bytes `\n\r` in UTF-16LE and must be kept as is
+        let input = "<?xml version=\"1.0\" encoding=\"UTF-16\"?>\r\n\
+            <bom xmlns=\"http://cyclonedx.org/schema/bom/1.5\" version=\"1\"><components>\
+            <component type=\"library\"><name>lib</name>\
+            <description>Multi\r\nline\u{0D0A}</description>\
+            </component></components></bom>";
+        let utf16le: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain(input.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+
+        let bom = Bom::parse_from_xml_v1_5(utf16le.as_slice()).expect("Failed to parse XML");
+
+        let component = &bom.components.as_ref().unwrap().0[0];
+        assert_eq!(
+            component.description,
+            Some(NormalizedString::new_unchecked(
+                "Multi\nline\u{0D0A}".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn it_should_keep_the_metadata_timestamp_identical_across_formats() {
+        let timestamp = "2020-04-13T20:20:39.123+02:00";
+        let input = format!(
+            r#"{{
+                "bomFormat": "CycloneDX",
+                "specVersion": "1.5",
+                "version": 1,
+                "metadata": {{
+                    "timestamp": "{timestamp}"
+                }}
+            }}"#
+        );
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse JSON");
+
+        let mut xml = Vec::new();
+        bom.output_as_xml_v1_5(&mut xml)
+            .expect("Failed to write XML");
+        let xml = String::from_utf8(xml).expect("Failed to read XML as a string");
+        assert!(xml.contains(&format!("<timestamp>{timestamp}</timestamp>")));
+
+        let bom = Bom::parse_from_xml_v1_5(xml.as_bytes()).expect("Failed to parse XML");
+        let mut json = Vec::new();
+        bom.output_as_json_v1_5(&mut json)
+            .expect("Failed to write JSON");
+        let json: Value = serde_json::from_slice(&json).expect("Failed to read JSON");
+
+        assert_eq!(json["metadata"]["timestamp"], timestamp);
+    }
+
+    #[test]
+    fn it_should_iterate_over_all_nested_components() {
+        let component = |name: &str, nested: Vec<Component>| {
+            let mut component = Component::new(Classification::Library, name, "1.0.0", None);
+            if !nested.is_empty() {
+                component.components = Some(Components(nested));
+            }
+            component
+        };
+        let mut bom = Bom {
+            metadata: Some(Metadata {
+                component: Some(component("root", vec![component("root-bin", vec![])])),
+                ..Metadata::default()
+            }),
+            components: Some(Components(vec![
+                component(
+                    "a",
+                    vec![component("a.1", vec![component("a.1.1", vec![])])],
+                ),
+                component("b", vec![]),
+            ])),
+            ..Bom::default()
+        };
+
+        let names: Vec<_> = bom
+            .all_components()
+            .map(|component| component.name.to_string())
+            .collect();
+        assert_eq!(names, ["root", "root-bin", "a", "a.1", "a.1.1", "b"]);
+
+        bom.all_components_mut(|component| {
+            component.version = Some(NormalizedString::new("2.0.0"));
+        });
+        assert!(bom
+            .all_components()
+            .all(|component| component.version == Some(NormalizedString::new("2.0.0"))));
+    }
+
+    #[test]
+    fn it_should_assign_missing_bom_refs() {
+        let component = |group: &str, name: &str, bom_ref: Option<&str>| {
+            let mut component = Component::new(
+                Classification::Library,
+                name,
+                "1.0.0",
+                bom_ref.map(String::from),
+            );
+            component.group = Some(NormalizedString::new(group));
+            component
+        };
+        let mut with_purl = Component::new(Classification::Library, "serde", "1.0.193", None);
+        with_purl.purl = Some(Purl::cargo("serde", "1.0.193").unwrap());
+        let mut bom = Bom {
+            components: Some(Components(vec![
+                component("other", "lib", Some("acme/lib@1.0.0")),
+                component("acme", "lib", None),
+                component("example", "lib", None),
+                component("acme", "lib", None),
+                with_purl,
+            ])),
+            services: Some(Services(vec![Service::new("api", None)])),
+            ..Bom::default()
+        };
+
+        assert_eq!(bom.assign_missing_bom_refs(), 5);
+        assert_eq!(bom.assign_missing_bom_refs(), 0);
+
+        let bom_refs: Vec<_> = bom
+            .all_components()
+            .map(|component| component.bom_ref.clone().unwrap())
+            .collect();
+        assert_eq!(
+            bom_refs,
+            [
+                "acme/lib@1.0.0",
+                "acme/lib@1.0.0-1",
+                "example/lib@1.0.0",
+                "acme/lib@1.0.0-2",
+                "pkg:cargo/serde@1.0.193",
+            ]
+        );
+        assert_eq!(bom.services.unwrap().0[0].bom_ref, Some("api".to_string()));
+    }
+
+    #[test]
+    fn it_should_sort_components_and_dependencies() {
+        let component = |group: Option<&str>, name: &str, version: &str| {
+            let mut component = Component::new(
+                Classification::Library,
+                name,
+                version,
+                Some(format!("{name}@{version}")),
+            );
+            component.group = group.map(NormalizedString::new);
+            component
+        };
+        let mut parent = component(None, "parent", "1.0.0");
+        parent.components = Some(Components(vec![
+            component(None, "zeta", "1.0.0"),
+            component(None, "alpha", "1.0.0"),
+        ]));
+
+        let mut bom = Bom {
+            components: Some(Components(vec![
+                component(None, "serde", "1.0.193"),
+                parent.clone(),
+                component(Some("acme"), "anyhow", "1.0.0"),
+                component(None, "serde", "1.0.9"),
+                component(None, "anyhow", "1.0.0"),
+            ])),
+            dependencies: Some(Dependencies(vec![
+                Dependency {
+                    dependency_ref: "serde@1.0.193".to_string(),
+                    dependencies: vec![],
+                },
+                Dependency {
+                    dependency_ref: "parent@1.0.0".to_string(),
+                    dependencies: vec!["zeta@1.0.0".to_string(), "alpha@1.0.0".to_string()],
+                },
+            ])),
+            ..Bom::default()
+        };
+        bom.sort_components();
+        bom.sort_dependencies();
+
+        parent.components = Some(Components(vec![
+            component(None, "alpha", "1.0.0"),
+            component(None, "zeta", "1.0.0"),
+        ]));
+        assert_eq!(
+            bom.components,
+            Some(Components(vec![
+                component(None, "anyhow", "1.0.0"),
+                parent,
+                component(None, "serde", "1.0.9"),
+                component(None, "serde", "1.0.193"),
+                component(Some("acme"), "anyhow", "1.0.0"),
+            ]))
+        );
+        assert_eq!(
+            bom.dependencies,
+            Some(Dependencies(vec![
+                Dependency {
+                    dependency_ref: "parent@1.0.0".to_string(),
+                    dependencies: vec!["alpha@1.0.0".to_string(), "zeta@1.0.0".to_string()],
+                },
+                Dependency {
+                    dependency_ref: "serde@1.0.193".to_string(),
+                    dependencies: vec![],
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_should_add_dependencies_between_components() {
+        let components = ["app", "lib"]
+            .iter()
+            .map(|name| {
+                let mut component = Component::new(
+                    Classification::Library,
+                    name,
+                    "1.0.0",
+                    Some(name.to_string()),
+                );
+                component.purl = Some(Purl::cargo(name, "1.0.0").unwrap());
+                component
+            })
+            .collect();
+        let mut bom = Bom {
+            components: Some(Components(components)),
+            ..Bom::default()
+        };
+
+        bom.add_dependency("app", "lib");
+        bom.add_dependency("app", "lib");
+
+        assert!(bom.depends("app", "lib"));
+        assert!(!bom.depends("lib", "app"));
+        assert_eq!(
+            bom.dependencies,
+            Some(Dependencies(vec![Dependency {
+                dependency_ref: "app".to_string(),
+                dependencies: vec!["lib".to_string()],
+            }]))
+        );
+        assert!(bom.validate().passed());
+    }
+
+    #[test]
+    fn it_should_convert_to_a_json_value_and_back() {
+        let mut component = Component::new(
+            Classification::Library,
+            "serde",
+            "1.0.193",
+            Some("serde-1.0.193".to_string()),
+        );
+        component.purl = Some(Purl::cargo("serde", "1.0.193").unwrap());
+        let bom = Bom {
+            serial_number: Some(UrnUuid(
+                "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79".to_string(),
+            )),
+            components: Some(Components(vec![component])),
+            ..Bom::default()
+        };
+
+        let value = bom.to_json_value(SpecVersion::V1_5).unwrap();
+
+        assert_eq!(value["specVersion"], "1.5");
+        assert_eq!(value["components"][0]["name"], "serde");
+        let mut output = Vec::new();
+        bom.clone().output_as_json_v1_5(&mut output).unwrap();
+        assert_eq!(value, serde_json::from_slice::<Value>(&output).unwrap());
+
+        let parsed = Bom::from_json_value(value, SpecVersion::V1_5).unwrap();
+        assert_eq!(parsed.components, bom.components);
+        assert_eq!(parsed.serial_number, bom.serial_number);
+        assert_eq!(parsed.spec_version, SpecVersion::V1_5);
+    }
+
+    #[test]
+    fn it_should_detect_format_and_version_when_parsing_from_a_reader() {
+        let json = r#"
+{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.4",
+  "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+  "version": 2,
+  "components": [
+    {
+      "type": "library",
+      "name": "json-component",
+      "version": "1.0.0"
+    }
+  ]
+}"#;
+        let bom = Bom::parse_from_reader(json.as_bytes(), None).unwrap();
+        assert_eq!(bom.spec_version, SpecVersion::V1_4);
+        assert_eq!(bom.version, 2);
+        assert_eq!(
+            bom.components.unwrap().0[0].name.to_string(),
+            "json-component"
+        );
+
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<bom xmlns="http://cyclonedx.org/schema/bom/1.3" serialNumber="urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79" version="3">
+  <components>
+    <component type="library">
+      <name>xml-component</name>
+      <version>1.0.0</version>
+    </component>
+  </components>
+</bom>"#;
+        let bom = Bom::parse_from_reader(xml.as_bytes(), None).unwrap();
+        assert_eq!(bom.spec_version, SpecVersion::V1_3);
+        assert_eq!(bom.version, 3);
+        assert_eq!(
+            bom.components.unwrap().0[0].name.to_string(),
+            "xml-component"
+        );
+
+        assert!(matches!(
+            Bom::parse_from_reader(xml.as_bytes(), Some(Format::Json)),
+            Err(crate::errors::ReadError::JsonReadError(_))
+        ));
+        assert!(matches!(
+            Bom::parse_from_reader("name,version".as_bytes(), None),
+            Err(crate::errors::ReadError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn it_should_skip_a_utf8_byte_order_mark_when_parsing_from_a_reader() {
+        let json =
+            "\u{FEFF}{\"bomFormat\": \"CycloneDX\", \"specVersion\": \"1.5\", \"version\": 2}";
+        let xml = "\u{FEFF}<bom xmlns=\"http://cyclonedx.org/schema/bom/1.5\" version=\"3\" />";
+
+        for format_hint in [None, Some(Format::Json)] {
+            let bom = Bom::parse_from_reader(json.as_bytes(), format_hint).unwrap();
+            assert_eq!(bom.version, 2);
+        }
+        for format_hint in [None, Some(Format::Xml)] {
+            let bom = Bom::parse_from_reader(xml.as_bytes(), format_hint).unwrap();
+            assert_eq!(bom.version, 3);
+        }
+    }
+
+    #[test]
+    fn it_should_parse_boms_from_strings() {
+        for version in SpecVersion::all() {
+            let json = format!(
+                r#"{{"bomFormat": "CycloneDX", "specVersion": "{version}", "version": 2, "components": [{{"type": "library", "name": "json-component", "version": "1.0.0"}}]}}"#
+            );
+            let bom = Bom::from_json_str(&json).unwrap();
+            assert_eq!(bom.spec_version, *version);
+            assert_eq!(bom.version, 2);
+            assert_eq!(
+                bom.components.unwrap().0[0].name.to_string(),
+                "json-component"
+            );
+
+            let xml = format!(
+                r#"<bom xmlns="http://cyclonedx.org/schema/bom/{version}" version="3"><components><component type="library"><name>xml-component</name><version>1.0.0</version></component></components></bom>"#
+            );
+            let bom = Bom::from_xml_str(&xml).unwrap();
+            assert_eq!(bom.spec_version, *version);
+            assert_eq!(bom.version, 3);
+            assert_eq!(
+                bom.components.unwrap().0[0].name.to_string(),
+                "xml-component"
+            );
+        }
+
+        assert!(matches!(
+            Bom::from_json_str(r#"{"bomFormat": "CycloneDX", "specVersion": "1.1", "version": 1}"#),
+            Err(crate::errors::JsonReadError::BomError {
+                error: BomError::UnsupportedSpecVersion(_)
+            })
+        ));
+        assert!(matches!(
+            Bom::from_xml_str(r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.1" version="1" />"#),
+            Err(crate::errors::XmlReadError::InvalidNamespaceError { .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_deeply_nested_xml() {
+        let component =
+            r#"<component type="library"><name>nested</name><version>1.0.0</version><components>"#;
+        let depth = 10_000;
+        let xml = format!(
+            r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.5" version="1"><components>{}{}</components></bom>"#,
+            component.repeat(depth),
+            "</components></component>".repeat(depth)
+        );
+
+        assert!(matches!(
+            Bom::parse_from_xml(xml.as_bytes()),
+            Err(crate::errors::XmlReadError::MaxDepthExceeded {
+                max_depth: DEFAULT_MAX_XML_DEPTH
+            })
+        ));
+        assert!(matches!(
+            Bom::parse_from_xml_v1_5(xml.as_bytes()),
+            Err(crate::errors::XmlReadError::MaxDepthExceeded { .. })
+        ));
+        assert!(matches!(
+            Bom::from_xml_str(&xml),
+            Err(crate::errors::XmlReadError::MaxDepthExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn it_should_parse_xml_within_a_custom_max_depth() {
+        let xml = r#"<bom xmlns="http://cyclonedx.org/schema/bom/1.5" version="1"><components><component type="library"><name>lib</name></component></components></bom>"#;
+
+        assert!(Bom::parse_from_xml_with_max_depth(xml.as_bytes(), 4).is_ok());
+        assert!(matches!(
+            Bom::parse_from_xml_with_max_depth(xml.as_bytes(), 3),
+            Err(crate::errors::XmlReadError::MaxDepthExceeded { max_depth: 3 })
+        ));
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_xml_without_a_cyclonedx_namespace() {
+        let xml = r#"<bom xmlns="https://example.com" version="1" />"#;
+        match Bom::parse_from_xml(xml.as_bytes()) {
+            Err(crate::errors::XmlReadError::InvalidNamespaceError {
+                actual_namespace, ..
+            }) => assert_eq!(actual_namespace.as_deref(), Some("https://example.com")),
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn it_should_map_formats_to_media_types() {
+        assert_eq!(Format::Json.media_type(), "application/vnd.cyclonedx+json");
+        assert_eq!(
+            Format::from_media_type("application/vnd.cyclonedx+xml; version=1.5"),
+            Some(Format::Xml)
+        );
+        assert_eq!(Format::from_media_type("application/json"), None);
+        assert_eq!(Format::detect(b"\xEF\xBB\xBF  {}"), Some(Format::Json));
+    }
+
+    #[test]
+    fn it_should_parse_and_display_spec_versions() {
+        for version in SpecVersion::all() {
+            assert_eq!(version.to_string().parse::<SpecVersion>(), Ok(*version));
+        }
+        assert_eq!("1.4".parse::<SpecVersion>(), Ok(SpecVersion::V1_4));
+        assert_eq!(SpecVersion::all().len(), 3);
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_an_unsupported_spec_version() {
+        let error = "2.0".parse::<SpecVersion>().unwrap_err();
+
+        assert_eq!(error, SpecVersionError("2.0".to_string()));
+        assert_eq!(
+            error.to_string(),
+            "Unsupported spec version '2.0', supported versions are: 1.3, 1.4, 1.5"
+        );
+    }
+
+    #[test]
+    fn it_should_parse_a_whitespace_padded_spec_version() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": " 1.5 ",
+            "version": 1
+        }"#;
+
+        let detected = Bom::parse_from_json(input.as_bytes()).expect("Failed to parse JSON");
+        let explicit = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse JSON");
+
+        assert_eq!(detected.spec_version, SpecVersion::V1_5);
+        assert_eq!(explicit.spec_version, SpecVersion::V1_5);
+    }
+
+    #[test]
+    fn it_should_read_legacy_metadata_tools_from_xml_v1_5() {
+        let input = r#"<?xml version="1.0" encoding="utf-8"?>
+<bom xmlns="http://cyclonedx.org/schema/bom/1.5" version="1">
+  <metadata>
+    <tools>
+      <tool>
+        <vendor>Acme</vendor>
+        <name>scanner</name>
+        <version>1.0.0</version>
+      </tool>
+    </tools>
+  </metadata>
+</bom>"#;
+        let bom = Bom::parse_from_xml_v1_5(input.as_bytes()).expect("Failed to parse XML");
+
+        let tools = bom.metadata.and_then(|metadata| metadata.tools);
+        assert_eq!(
+            tools,
+            Some(Tools::List(vec![Tool {
+                vendor: Some(NormalizedString::new("Acme")),
+                name: Some(NormalizedString::new("scanner")),
+                version: Some(NormalizedString::new("1.0.0")),
+                hashes: None,
+                external_references: None,
+            }]))
+        );
+    }
+
+    #[test]
+    fn it_should_read_object_metadata_tools_from_xml_v1_5() {
+        let input = r#"<?xml version="1.0" encoding="utf-8"?>
+<bom xmlns="http://cyclonedx.org/schema/bom/1.5" version="1">
+  <metadata>
+    <tools>
+      <components>
+        <component type="application">
+          <name>scanner</name>
+          <version>1.0.0</version>
+        </component>
+      </components>
+    </tools>
+  </metadata>
+</bom>"#;
+        let bom = Bom::parse_from_xml_v1_5(input.as_bytes()).expect("Failed to parse XML");
+
+        let tools = bom.metadata.and_then(|metadata| metadata.tools);
+        assert_eq!(
+            tools,
+            Some(Tools::Object {
+                services: None,
+                components: Some(Components(vec![Component::new(
+                    Classification::Application,
+                    "scanner",
+                    "1.0.0",
+                    None
+                )])),
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_round_trip_declared_and_evidence_copyrights() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                {
+                    "type": "library",
+                    "name": "lib",
+                    "copyright": "Copyright Acme Inc.",
+                    "evidence": {
+                        "copyright": [
+                            { "text": "Copyright 2021 Jane Doe" },
+                            { "text": "Copyright 2022 John Doe" }
+                        ]
+                    }
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse JSON");
+
+        let mut json = Vec::new();
+        bom.clone()
+            .output_as_json_v1_5(&mut json)
+            .expect("Failed to write JSON");
+        let from_json = Bom::parse_from_json_v1_5(json.as_slice()).expect("Failed to parse JSON");
+        let mut xml = Vec::new();
+        bom.clone()
+            .output_as_xml_v1_5(&mut xml)
+            .expect("Failed to write XML");
+        let from_xml = Bom::parse_from_xml_v1_5(xml.as_slice()).expect("Failed to parse XML");
+
+        assert_eq!(from_json.components, bom.components);
+        assert_eq!(from_xml.components, bom.components);
+        assert_eq!(
+            bom.components.unwrap().0[0].all_copyrights(),
+            vec![
+                "Copyright Acme Inc.",
+                "Copyright 2021 Jane Doe",
+                "Copyright 2022 John Doe"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_validate_a_composition_without_aggregate_as_failed() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "compositions": [
+                {
+                    "assemblies": []
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(
+            bom.validate(),
+            validation::r#struct(
+                "compositions",
+                validation::list(
+                    "composition",
+                    [(0, validation::field("aggregate", "Missing aggregate type"))]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_collect_bom_refs_of_all_kinds() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "metadata": {
+                "component": { "type": "application", "bom-ref": "app", "name": "app" }
+            },
+            "components": [
+                {
+                    "type": "library",
+                    "bom-ref": "lib",
+                    "name": "lib",
+                    "components": [
+                        { "type": "library", "bom-ref": "nested-lib", "name": "nested-lib" }
+                    ]
+                },
+                { "type": "library", "name": "no-ref" }
+            ],
+            "services": [
+                {
+                    "bom-ref": "api",
+                    "name": "api",
+                    "services": [{ "bom-ref": "nested-api", "name": "nested-api" }]
+                }
+            ],
+            "vulnerabilities": [
+                { "bom-ref": "vuln", "id": "CVE-2021-0001" },
+                { "bom-ref": "lib", "id": "CVE-2021-0002" }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        let actual = bom.collect_bom_refs();
+
+        assert_eq!(
+            actual,
+            HashMap::from([
+                ("app".to_string(), RefKind::Component),
+                ("lib".to_string(), RefKind::Component),
+                ("nested-lib".to_string(), RefKind::Component),
+                ("api".to_string(), RefKind::Service),
+                ("nested-api".to_string(), RefKind::Service),
+                ("vuln".to_string(), RefKind::Vulnerability),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_merge_components_whose_purls_only_differ_in_qualifier_order() {
+        let first = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                {
+                    "type": "library",
+                    "bom-ref": "first-lib",
+                    "name": "lib",
+                    "purl": "pkg:cargo/lib@1.0.0?arch=x86_64&os=linux"
+                }
+            ],
+            "dependencies": [{ "ref": "first-lib", "dependsOn": [] }]
+        }"#;
+        let second = r#"<?xml version="1.0" encoding="utf-8"?>
+<bom xmlns="http://cyclonedx.org/schema/bom/1.5" version="1">
+  <components>
+    <component type="library" bom-ref="second-lib">
+      <name>lib</name>
+      <purl>pkg:CARGO/lib@1.0.0?os=linux&amp;arch=x86_64</purl>
+    </component>
+    <component type="library" bom-ref="other-lib">
+      <name>other-lib</name>
+      <purl>pkg:cargo/other-lib@2.0.0</purl>
+    </component>
+  </components>
+  <dependencies>
+    <dependency ref="second-lib">
+      <dependency ref="other-lib"/>
+    </dependency>
+    <dependency ref="other-lib"/>
+  </dependencies>
+</bom>"#;
+        let mut bom = Bom::parse_from_json_v1_5(first.as_bytes()).expect("Failed to parse JSON");
+        let other = Bom::parse_from_xml_v1_5(second.as_bytes()).expect("Failed to parse XML");
+
+        bom.merge(other);
+
+        let bom_refs = bom
+            .components
+            .as_ref()
+            .unwrap()
+            .0
+            .iter()
+            .map(|component| component.bom_ref.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(bom_refs, vec!["first-lib", "other-lib"]);
+        assert_eq!(
+            bom.dependencies,
+            Some(Dependencies(vec![
+                Dependency {
+                    dependency_ref: "first-lib".to_string(),
+                    dependencies: vec!["other-lib".to_string()],
+                },
+                Dependency {
+                    dependency_ref: "other-lib".to_string(),
+                    dependencies: vec![],
+                },
+            ]))
+        );
+        assert!(bom.validate().passed());
+    }
+
+    #[test]
+    fn it_should_rename_merged_components_whose_bom_ref_is_taken() {
+        let first = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                { "type": "library", "bom-ref": "lib", "name": "lib", "purl": "pkg:cargo/lib@1.0.0" },
+                { "type": "library", "bom-ref": "lib-1", "name": "unrelated" }
+            ],
+            "dependencies": [{ "ref": "lib", "dependsOn": [] }]
+        }"#;
+        let second = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                {
+                    "type": "library",
+                    "bom-ref": "lib",
+                    "name": "other-lib",
+                    "purl": "pkg:cargo/other-lib@2.0.0",
+                    "components": [{ "type": "library", "bom-ref": "lib-1", "name": "nested" }]
+                },
+                { "type": "library", "bom-ref": "util", "name": "util" }
+            ],
+            "dependencies": [{ "ref": "lib", "dependsOn": ["util", "lib-1"] }]
+        }"#;
+        let mut bom = Bom::parse_from_json_v1_5(first.as_bytes()).expect("Failed to parse BOM");
+        let other = Bom::parse_from_json_v1_5(second.as_bytes()).expect("Failed to parse BOM");
+
+        bom.merge(other);
+
+        let bom_refs = bom
+            .all_components()
+            .map(|component| component.bom_ref.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(bom_refs, vec!["lib", "lib-1", "lib-2", "lib-1-1", "util"]);
+        assert_eq!(
+            bom.dependencies,
+            Some(Dependencies(vec![
+                Dependency {
+                    dependency_ref: "lib".to_string(),
+                    dependencies: vec![],
+                },
+                Dependency {
+                    dependency_ref: "lib-2".to_string(),
+                    dependencies: vec!["util".to_string(), "lib-1-1".to_string()],
+                },
+            ]))
+        );
+        assert!(bom.validate().passed());
+    }
+
+    #[test]
+    fn it_should_keep_the_edges_of_a_merged_component_matching_one_without_bom_ref() {
+        let first = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                { "type": "library", "name": "lib", "purl": "pkg:cargo/lib@1.0.0" }
+            ]
+        }"#;
+        let second = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                { "type": "library", "bom-ref": "lib", "name": "lib", "purl": "pkg:cargo/lib@1.0.0" },
+                { "type": "library", "bom-ref": "util", "name": "util" }
+            ],
+            "dependencies": [{ "ref": "lib", "dependsOn": ["util"] }]
+        }"#;
+        let mut bom = Bom::parse_from_json_v1_5(first.as_bytes()).expect("Failed to parse BOM");
+        let other = Bom::parse_from_json_v1_5(second.as_bytes()).expect("Failed to parse BOM");
+
+        bom.merge(other);
+
+        let bom_refs = bom
+            .all_components()
+            .map(|component| component.bom_ref.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(bom_refs, vec!["lib", "util"]);
+        assert_eq!(
+            bom.dependencies,
+            Some(Dependencies(vec![Dependency {
+                dependency_ref: "lib".to_string(),
+                dependencies: vec!["util".to_string()],
+            }]))
+        );
+        assert!(bom.validate().passed());
+    }
+
+    #[test]
+    fn it_should_validate_an_annotation_with_an_unknown_subject_as_failed() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                { "type": "library", "bom-ref": "lib", "name": "lib", "purl": "pkg:cargo/lib@1.0.0" }
+            ],
+            "annotations": [
+                {
+                    "subjects": ["lib", "missing"],
+                    "annotator": { "organization": { "name": "Acme" } },
+                    "timestamp": "2020-04-13T20:20:39+00:00",
+                    "text": "Reviewed"
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(
+            bom.validate(),
+            validation::custom(
+                "annotation subject",
+                ["Annotation subject 'missing' does not exist in the BOM"]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_validate_an_annotation_with_a_malformed_timestamp_as_failed() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "annotations": [
+                {
+                    "subjects": [],
+                    "annotator": { "organization": { "name": "Acme" } },
+                    "timestamp": "2020-04-13 20:20",
+                    "text": "Reviewed"
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(
+            bom.validate(),
+            validation::r#struct(
+                "annotations",
+                validation::list(
+                    "inner",
+                    [(
+                        0,
+                        validation::field("timestamp", "DateTime does not conform to ISO 8601")
+                    )]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_validate_a_vulnerability_affecting_an_unknown_ref_as_failed() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                { "type": "library", "bom-ref": "lib", "name": "lib", "purl": "pkg:cargo/lib@1.0.0" }
+            ],
+            "vulnerabilities": [
+                {
+                    "bom-ref": "vuln",
+                    "id": "CVE-2023-0001",
+                    "affects": [
+                        { "ref": "lib" },
+                        { "ref": "urn:cdx:3e671687-395b-41f5-a30f-a58921a69b79/1#other" },
+                        { "ref": "missing" },
+                        { "ref": "vuln" }
+                    ]
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(
+            bom.validate(),
+            validation::custom(
+                "vulnerability affects",
+                [
+                    "Affected ref 'missing' does not refer to a component or service in the BOM",
+                    "Affected ref 'vuln' does not refer to a component or service in the BOM",
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_round_trip_service_data_governance() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "services": [
+                {
+                    "name": "api",
+                    "data": [
+                        {
+                            "flow": "inbound",
+                            "classification": "PII",
+                            "governance": {
+                                "owners": [
+                                    { "contact": { "name": "Jane Doe", "email": "jane@example.com" } }
+                                ]
+                            }
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse JSON");
+
+        let mut json = Vec::new();
+        bom.clone()
+            .output_as_json_v1_5(&mut json)
+            .expect("Failed to write JSON");
+        let from_json = Bom::parse_from_json_v1_5(json.as_slice()).expect("Failed to parse JSON");
+        let mut xml = Vec::new();
+        bom.clone()
+            .output_as_xml_v1_5(&mut xml)
+            .expect("Failed to write XML");
+        let from_xml = Bom::parse_from_xml_v1_5(xml.as_slice()).expect("Failed to parse XML");
+
+        assert_eq!(from_json.services, bom.services);
+        assert_eq!(from_xml.services, bom.services);
+        assert!(bom.validate().passed());
+
+        let invalid = input.replace("Jane Doe", "Jane\\tDoe");
+        let bom = Bom::parse_from_json_v1_5(invalid.as_bytes()).expect("Failed to parse JSON");
+
+        assert_eq!(
+            bom.validate(),
+            validation::r#struct(
+                "services",
+                validation::list(
+                    "inner",
+                    [(
+                        0,
+                        validation::r#struct(
+                            "data",
+                            validation::list(
+                                "inner",
+                                [(
+                                    0,
+                                    validation::r#struct(
+                                        "governance",
+                                        validation::list(
+                                            "owners",
+                                            [(
+                                                0,
+                                                validation::field(
+                                                    "name",
+                                                    "NormalizedString contains invalid characters \\r \\n \\t or \\r\\n"
+                                                )
+                                            )]
+                                        )
+                                    )
+                                )]
+                            )
+                        )
+                    )]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_validate_formulation_in_a_1_3_bom_as_failed() {
+        let bom = Bom {
+            spec_version: SpecVersion::V1_3,
+            serial_number: None,
+            formulation: Some(vec![Formula {
+                bom_ref: None,
+                components: None,
+                services: None,
+                workflows: None,
+                properties: None,
+            }]),
+            ..Bom::default()
+        };
+
+        assert_eq!(
+            bom.validate(),
+            validation::list(
+                "formulation",
+                [(
+                    0,
+                    validation::custom("", ["Formula is not defined for version 1.3"])
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_validate_a_workflow_with_an_empty_uid_as_failed() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "formulation": [
+                {
+                    "workflows": [
+                        {
+                            "bom-ref": "workflow-1",
+                            "uid": "  ",
+                            "taskTypes": ["build"]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(
+            bom.validate(),
+            validation::list(
+                "formulation",
+                [(
+                    0,
+                    validation::list(
+                        "workflows",
+                        [(
+                            0,
+                            validation::field("uid", "Required string must not be empty")
+                        )]
+                    )
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_validate_workflows_and_tasks_without_task_types_as_failed() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "formulation": [
+                {
+                    "workflows": [
+                        {
+                            "bom-ref": "workflow-1",
+                            "uid": "workflow-1",
+                            "taskTypes": [],
+                            "tasks": [
+                                { "bom-ref": "task-1", "uid": "task-1", "taskTypes": [] }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(
+            bom.validate(),
+            validation::list(
+                "formulation",
+                [(
+                    0,
+                    validation::list(
+                        "workflows",
+                        [(
+                            0,
+                            ValidationResult::from(vec![
+                                validation::list(
+                                    "tasks",
+                                    [(
+                                        0,
+                                        validation::field(
+                                            "task_types",
+                                            "At least one task type is required"
+                                        )
+                                    )]
+                                ),
+                                validation::field(
+                                    "task_types",
+                                    "At least one task type is required"
+                                ),
+                            ])
+                        )]
+                    )
+                )]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_dangling_formulation_refs() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "formulation": [
+                {
+                    "components": [
+                        {
+                            "type": "platform",
+                            "bom-ref": "controller",
+                            "name": "controller",
+                            "purl": "pkg:oci/controller@sha256%3A1234"
+                        }
+                    ],
+                    "workflows": [
+                        {
+                            "bom-ref": "workflow-1",
+                            "uid": "workflow-1",
+                            "taskTypes": ["build"],
+                            "tasks": [
+                                { "bom-ref": "task-1", "uid": "task-1", "taskTypes": ["build"] }
+                            ],
+                            "taskDependencies": [
+                                { "ref": "task-1", "dependsOn": ["task-2"] }
+                            ],
+                            "runtimeTopology": [
+                                { "ref": "controller", "dependsOn": ["runner"] }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(
+            bom.validate(),
+            vec![
+                validation::warning(
+                    "formulation[0].workflows[0].task_dependencies",
+                    ["Task dependency ref 'task-2' does not exist in the workflow"]
+                ),
+                validation::warning(
+                    "formulation[0].workflows[0].runtime_topology",
+                    ["Runtime topology ref 'runner' does not exist in the formula"]
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn it_should_validate_object_form_tools_in_a_1_4_bom_as_failed() {
+        let bom = Bom {
+            spec_version: SpecVersion::V1_4,
+            serial_number: None,
+            metadata: Some(Metadata {
+                tools: Some(Tools::Object {
+                    components: Some(Components(vec![Component::new(
+                        Classification::Application,
+                        "cargo-cyclonedx",
+                        "0.5.0",
+                        None,
+                    )])),
+                    services: None,
+                }),
+                ..Metadata::default()
+            }),
+            ..Bom::default()
+        };
+
+        assert_eq!(
+            bom.validate(),
+            validation::r#struct(
+                "metadata",
+                validation::list(
+                    "tools",
+                    [(
+                        0,
+                        validation::custom("inner", ["Unsupported tools type found."])
+                    )]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_validate_a_component_with_an_invalid_purl_as_failed() {
+        let mut component = Component::new(Classification::Library, "lib-x", "v0.1.0", None);
+        component.purl = Some(Purl::from_str("invalid purl").unwrap());
+
+        let bom = Bom {
+            spec_version: SpecVersion::V1_5,
+            serial_number: None,
+            components: Some(Components(vec![component])),
+            ..Bom::default()
+        };
+
+        assert_eq!(
+            bom.validate(),
+            validation::r#struct(
+                "components",
+                validation::list(
+                    "inner",
+                    [(
+                        0,
+                        validation::field(
+                            "purl",
+                            "Purl does not conform to Package URL spec: URL scheme must be pkg"
+                        )
+                    )]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_count_vulnerabilities_by_highest_severity() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.4",
+            "version": 1,
+            "vulnerabilities": [
+                {
+                    "id": "CVE-2021-0001",
+                    "ratings": [{ "severity": "low" }, { "severity": "critical" }]
+                },
+                {
+                    "id": "CVE-2021-0002",
+                    "ratings": [{ "severity": "critical" }]
+                },
+                {
+                    "id": "CVE-2021-0003",
+                    "ratings": [{ "severity": "medium" }, { "severity": "high" }]
+                },
+                {
+                    "id": "CVE-2021-0004",
+                    "ratings": [{ "score": 5.0 }]
+                },
+                {
+                    "id": "CVE-2021-0005"
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_4(input.as_bytes()).expect("Failed to parse BOM");
+
+        let actual = bom.count_vulnerabilities_by_severity();
+
+        assert_eq!(
+            actual,
+            BTreeMap::from([
+                (Severity::Critical, 2),
+                (Severity::High, 1),
+                (Severity::Unknown, 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_find_the_vulnerabilities_affecting_a_component() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.4",
+            "version": 1,
+            "components": [
+                { "type": "library", "bom-ref": "lib-a", "name": "lib-a", "version": "1.1.4" },
+                { "type": "library", "bom-ref": "lib-b", "name": "lib-b", "version": "2.0.0" }
+            ],
+            "vulnerabilities": [
+                {
+                    "id": "CVE-2021-0001",
+                    "ratings": [{ "severity": "medium" }],
+                    "affects": [
+                        {
+                            "ref": "lib-a",
+                            "versions": [{ "range": "vers:cargo/>=1.0.0|<1.2.0", "status": "affected" }]
+                        },
+                        {
+                            "ref": "lib-b",
+                            "versions": [{ "range": "vers:cargo/>=1.0.0|<1.2.0", "status": "affected" }]
+                        }
+                    ]
+                },
+                {
+                    "id": "CVE-2021-0002",
+                    "ratings": [{ "severity": "high" }, { "severity": "low" }],
+                    "affects": [{ "ref": "lib-b" }]
+                },
+                {
+                    "id": "CVE-2021-0003",
+                    "affects": [
+                        {
+                            "ref": "lib-a",
+                            "versions": [{ "version": "1.1.4", "status": "unaffected" }]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_4(input.as_bytes()).expect("Failed to parse BOM");
+
+        let ids = |bom_ref: &str| {
+            bom.vulnerabilities_for(bom_ref)
+                .into_iter()
+                .filter_map(|vulnerability| vulnerability.id.as_deref())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids("lib-a"), ["CVE-2021-0001"]);
+        assert_eq!(ids("lib-b"), ["CVE-2021-0002"]);
+        assert!(ids("lib-c").is_empty());
+        assert_eq!(bom.highest_severity(), Some(Severity::High));
+        assert_eq!(Bom::default().highest_severity(), None);
+    }
+
+    #[test]
+    fn it_should_prefer_rated_severities_over_unknown_ones() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.4",
+            "version": 1,
+            "vulnerabilities": [
+                { "id": "CVE-2021-0001", "ratings": [{ "severity": "unknown" }] },
+                { "id": "CVE-2021-0002", "ratings": [{ "severity": "none" }] },
+                { "id": "CVE-2021-0003", "ratings": [{ "severity": "info" }] }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_4(input.as_bytes()).expect("Failed to parse BOM");
+
+        assert_eq!(bom.highest_severity(), Some(Severity::Info));
+    }
+
+    #[test]
+    fn valid_uuids_should_pass_validation() {
+        let validation_result = validate_urn_uuid(&UrnUuid::from(uuid::Uuid::new_v4()));
+
+        assert!(validation_result.is_ok());
+    }
+
+    #[test]
+    fn invalid_uuids_should_fail_validation() {
+        let validation_result = validate_urn_uuid(&UrnUuid("invalid uuid".to_string()));
+
+        assert_eq!(
+            validation_result,
+            Err("UrnUuid does not match regular expression".into()),
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn it_should_parse_gzip_compressed_documents() {
+        use std::io::Write;
+
+        let gzip = |document: &[u8]| {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(document).unwrap();
+            encoder.finish().unwrap()
+        };
+        let bom = Bom {
+            spec_version: SpecVersion::V1_5,
+            serial_number: Some(UrnUuid::generate()),
+            components: Some(Components(vec![Component::new(
+                Classification::Library,
+                "lib-x",
+                "0.1.0",
+                Some("lib-x".to_string()),
+            )])),
+            ..Bom::default()
+        };
+
+        let mut json = Vec::new();
+        bom.clone()
+            .output_as_json(&mut json, SpecVersion::V1_5)
+            .unwrap();
+        let parsed = Bom::parse_from_json_gz(gzip(&json).as_slice()).unwrap();
+        assert_eq!(parsed, bom);
+
+        let mut xml = Vec::new();
+        bom.clone()
+            .output_as_xml(&mut xml, SpecVersion::V1_5)
+            .unwrap();
+        let parsed = Bom::parse_from_xml_gz(gzip(&xml).as_slice(), SpecVersion::V1_5).unwrap();
+        assert_eq!(parsed, bom);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn it_should_reject_uncompressed_input_as_gzip() {
+        use crate::errors::{JsonReadError, XmlReadError};
+
+        let error =
+            Bom::parse_from_json_gz(r#"{"bomFormat": "CycloneDX"}"#.as_bytes()).unwrap_err();
+        assert!(matches!(error, JsonReadError::GzipReadError { .. }));
+        assert_eq!(
+            error.to_string(),
+            "Failed to read gzip-compressed input: not a gzip stream, expected the magic bytes 1f 8b"
+        );
+
+        let error = Bom::parse_from_xml_gz(&[0x1f][..], SpecVersion::V1_5).unwrap_err();
+        assert!(matches!(error, XmlReadError::GzipReadError { .. }));
+    }
+
+    #[test]
+    fn bare_uuids_should_fail_validation() {
+        let uuid = "3e671687-395b-41f5-a30f-a58921a69b79";
+
+        assert_eq!(
+            validate_urn_uuid(&UrnUuid(uuid.to_string())),
+            Err("UrnUuid does not match regular expression".into()),
+        );
+        assert!(UrnUuid::new(uuid.to_string()).is_err());
+        assert!(UrnUuid::new(format!("urn:uuid:{uuid}")).is_ok());
+
+        let bom = Bom {
+            serial_number: Some(UrnUuid(uuid.to_string())),
+            ..Bom::default()
+        };
+        assert_eq!(
+            bom.validate(),
+            validation::field("serial_number", "UrnUuid does not match regular expression")
+        );
+    }
+
+    #[test]
+    fn generated_uuids_should_be_random_v4_urns() {
+        let urn_uuid = UrnUuid::generate();
+        let uuid = urn_uuid
+            .0
+            .strip_prefix("urn:uuid:")
+            .expect("Missing urn:uuid: prefix");
+
+        assert_eq!(
+            uuid::Uuid::parse_str(uuid).unwrap().get_version(),
+            Some(uuid::Version::Random)
         );
+        assert!(validate_urn_uuid(&urn_uuid).is_ok());
+        assert_ne!(urn_uuid, UrnUuid::generate());
     }
 }
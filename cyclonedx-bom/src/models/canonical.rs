@@ -0,0 +1,377 @@
+/*
+ * This file is part of CycloneDX Rust Cargo.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use serde_json::{Number, Value};
+
+use crate::errors::JsonWriteError;
+#[cfg(feature = "signature-verify")]
+use crate::errors::SignatureError;
+use crate::models::bom::{Bom, SpecVersion};
+#[cfg(feature = "signature-verify")]
+use crate::models::signature::{PublicKey, Signature};
+#[cfg(feature = "signature-verify")]
+use crate::specs::common::signature::Signature as JsonSignature;
+
+impl Bom {
+    /// Serializes the BOM following the [JSON Canonicalization Scheme](https://www.rfc-editor.org/rfc/rfc8785),
+    /// so that equal BOMs always produce the same bytes, e.g. to hash or sign them.
+    ///
+    /// Object keys are sorted by their UTF-16 code units, there is no whitespace between tokens
+    /// and numbers are formatted like ECMAScript does.
+    pub fn canonicalize_json(&self, version: SpecVersion) -> Result<String, JsonWriteError> {
+        let value = self.to_json_value(version)?;
+        let mut output = String::new();
+        write_value(&value, &mut output)?;
+        Ok(output)
+    }
+
+    /// Returns the canonical JSON an enveloped [JSF](https://cyberphone.github.io/doc/security/jsf.html)
+    /// signature of the BOM is computed over, that is the BOM without the `value` of its signature.
+    ///
+    /// This is meant for signing a BOM built with this library. A parsed BOM may hold data the
+    /// model does not keep, so verify those against the original document with
+    /// [`verify_json_signature`].
+    pub fn signature_payload(&self, version: SpecVersion) -> Result<String, JsonWriteError> {
+        json_signature_payload(&self.to_json_value(version)?)
+    }
+}
+
+/// Returns the canonical JSON an enveloped [JSF](https://cyberphone.github.io/doc/security/jsf.html)
+/// signature of a JSON object is computed over, that is the object exactly as given, only without
+/// the `value` of its `signature`.
+///
+/// Works for the BOM itself as well as for any signed component, service or composition in it.
+pub fn json_signature_payload(object: &Value) -> Result<String, JsonWriteError> {
+    let mut object = object.clone();
+    if let Some(signature) = object.get_mut("signature").and_then(Value::as_object_mut) {
+        signature.remove("value");
+    }
+    let mut output = String::new();
+    write_value(&object, &mut output)?;
+    Ok(output)
+}
+
+/// Verifies the enveloped signature of a JSON object, e.g. a parsed BOM document or one of its
+/// components, with the given public key, requires the `signature-verify` feature.
+///
+/// Only a single signature is supported, see [`Signer::verify`](crate::models::signature::Signer::verify)
+/// for the supported algorithms.
+#[cfg(feature = "signature-verify")]
+pub fn verify_json_signature(object: &Value, public_key: &PublicKey) -> Result<(), SignatureError> {
+    let signature = object
+        .get("signature")
+        .ok_or(SignatureError::MissingSignature)?;
+    let signature: Signature = serde_json::from_value::<JsonSignature>(signature.clone())
+        .map_err(SignatureError::InvalidSignature)?
+        .into();
+    let payload = json_signature_payload(object)?;
+    signature.verify(payload.as_bytes(), public_key)
+}
+
+fn write_value(value: &Value, output: &mut String) -> Result<(), serde_json::Error> {
+    match value {
+        Value::Null | Value::Bool(_) | Value::String(_) => {
+            output.push_str(&serde_json::to_string(value)?);
+        }
+        Value::Number(number) => output.push_str(&format_number(number)),
+        Value::Array(values) => {
+            output.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                write_value(value, output)?;
+            }
+            output.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            output.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+                output.push_str(&serde_json::to_string(key)?);
+                output.push(':');
+                write_value(value, output)?;
+            }
+            output.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Formats a number like ECMAScript's `Number.prototype.toString`, as required by RFC 8785.
+fn format_number(number: &Number) -> String {
+    if number.is_u64() || number.is_i64() {
+        return number.to_string();
+    }
+
+    let value = number.as_f64().unwrap_or_default();
+    if value == 0.0 {
+        return "0".to_string();
+    }
+
+    // Rust already finds the shortest digits that round-trip, only their layout differs.
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let exponent: i32 = exponent.parse().unwrap_or_default();
+
+    let k = digits.len() as i32;
+    let n = exponent + 1;
+    let formatted = if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (integer, fraction) = digits.split_at(n as usize);
+        format!("{integer}.{fraction}")
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat(-n as usize))
+    } else {
+        let (first, rest) = digits.split_at(1);
+        let sign = if n > 0 { "+" } else { "-" };
+        let fraction = if rest.is_empty() {
+            String::new()
+        } else {
+            format!(".{rest}")
+        };
+        format!("{first}{fraction}e{sign}{}", (n - 1).abs())
+    };
+
+    if value < 0.0 {
+        format!("-{formatted}")
+    } else {
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::models::{
+        bom::UrnUuid,
+        component::{Classification, Component, Components},
+        signature::{Algorithm, Signature},
+    };
+
+    fn bom() -> Bom {
+        Bom {
+            serial_number: Some(UrnUuid(
+                "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79".to_string(),
+            )),
+            components: Some(Components(vec![Component::new(
+                Classification::Library,
+                "serde",
+                "1.0.193",
+                Some("serde".to_string()),
+            )])),
+            ..Bom::default()
+        }
+    }
+
+    #[test]
+    fn it_should_sort_keys_lexicographically() {
+        let canonical = bom().canonicalize_json(SpecVersion::V1_5).unwrap();
+
+        assert_eq!(
+            canonical,
+            concat!(
+                r#"{"bomFormat":"CycloneDX","#,
+                r#""components":[{"bom-ref":"serde","name":"serde","type":"library","version":"1.0.193"}],"#,
+                r#""serialNumber":"urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79","#,
+                r#""specVersion":"1.5","version":1}"#
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_canonicalize_equal_boms_identically() {
+        let json = bom().to_json_value(SpecVersion::V1_5).unwrap();
+        let mut reordered = serde_json::Map::new();
+        for (key, value) in json.as_object().unwrap().iter().rev() {
+            reordered.insert(key.clone(), value.clone());
+        }
+        let parsed = Bom::from_json_value(Value::Object(reordered), SpecVersion::V1_5).unwrap();
+
+        assert_eq!(
+            parsed.canonicalize_json(SpecVersion::V1_5).unwrap(),
+            bom().canonicalize_json(SpecVersion::V1_5).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_should_format_numbers_like_ecmascript() {
+        let format = |value: f64| format_number(&Number::from_f64(value).unwrap());
+
+        assert_eq!(format(9.8), "9.8");
+        assert_eq!(format(-0.0), "0");
+        assert_eq!(format(100.0), "100");
+        assert_eq!(format(0.000001), "0.000001");
+        assert_eq!(format(0.0000001), "1e-7");
+        assert_eq!(format(1e21), "1e+21");
+        assert_eq!(format(-1.5e300), "-1.5e+300");
+        assert_eq!(format(123456789012345680000.0), "123456789012345680000");
+    }
+
+    #[test]
+    fn it_should_leave_the_signature_value_out_of_the_payload() {
+        let bom = Bom {
+            signature: Some(Signature::single(Algorithm::RS256, "c2lnbmF0dXJl")),
+            ..bom()
+        };
+
+        let payload = bom.signature_payload(SpecVersion::V1_5).unwrap();
+
+        assert_eq!(
+            payload,
+            concat!(
+                r#"{"bomFormat":"CycloneDX","#,
+                r#""components":[{"bom-ref":"serde","name":"serde","type":"library","version":"1.0.193"}],"#,
+                r#""serialNumber":"urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79","#,
+                r#""signature":{"algorithm":"RS256"},"specVersion":"1.5","version":1}"#
+            )
+        );
+    }
+
+    /// The signed example of the CycloneDX specification, its signers carry a `certificatePath`.
+    fn signed_example() -> Value {
+        serde_json::from_str(include_str!(
+            "../../tests/spec/1.5/valid-signatures-1.5.json"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn it_should_keep_all_signer_fields_in_the_payload_of_a_parsed_document() {
+        let component = &signed_example()["components"][0];
+
+        let payload = json_signature_payload(component).unwrap();
+        let payload: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(
+            payload["signature"]["certificatePath"],
+            component["signature"]["certificatePath"]
+        );
+        assert_eq!(payload["signature"].get("value"), None);
+    }
+
+    #[test]
+    fn it_should_keep_the_jsf_signer_fields_when_parsing() {
+        let bom = Bom::parse_from_json_v1_5(
+            include_bytes!("../../tests/spec/1.5/valid-signatures-1.5.json").as_slice(),
+        )
+        .unwrap();
+
+        let Some(Signature::Single(signer)) = bom.signature else {
+            panic!("expected a single signature");
+        };
+        assert_eq!(signer.certificate_path.map(|path| path.len()), Some(2));
+    }
+
+    #[cfg(feature = "signature-verify")]
+    mod verify {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        use super::*;
+
+        /// DER encoded PKCS#1 `RSAPublicKey` of a throwaway 2048 bit key.
+        const RSA_PUBLIC_KEY: &str = "MIIBCgKCAQEAr9bKXqWl2KQr21OF24YDUSzjXJSG9r1LWL11mklQeiQgId5DwdQlg9tezmJ25fSOKDT4UHk232n2tibu3xeK5BuYjJtey+HuqPR7GTsnd2ye6dmvNWVwEEDBFvmQim0JUhltf2JzEtyYv5r1xuVsEnGUsgbVbKDCZ6g75Q+FGnvMsYGc6NLeId2QicU0OW7oucchPUnKy9/yuuT1wnHawvpyrDjR4+gwcVubXgbz0qXtdRb2oZ56JX5BI0Kq9yQfok2LORZCJ2DlcGpSdAb7IMx3ZfL50CaimzXWiTI6zqB6hKcYO8yxW68rvnbb5g+fAE8JLfGmXxTVxQ1PJay4IQIDAQAB";
+        const RS256_SIGNATURE: &str = "XAVbm_t6lmsUw7ZpxFijLqD1pqLa1nWTlKvqxCaXj6TjEcR9WDCGGcNpGg4IarXeLddA9iv0U3NN2496DChTIgqGN03lz2jGGm4trJF-dP84YMwwvYcM-pVf0YNNfksVuK32AtqE76Iv-UolCAgu3hf_0BiTZDhY_Y3nCZgcuovqj9H7hCtjwR01jblY2VUjZLdiZteWjQPFuu2eGagxwALTtKwVfH-thWDslHtGrSO3CSJ7yQGYEBs-RKnkrUiQM67ESZUMq2ZZeNQ8YQNVoct_3LIeW3QzmCAV0LXvLElJ7x81KmgAaSL8P0u2ki4j88NcIMl_lH1cHdmY7A7XsQ";
+
+        /// Uncompressed SEC1 point of a throwaway P-256 key.
+        const EC_PUBLIC_KEY: &str = "BNG0MdyV0E65MK2PKnGUCfSNsOIFoaQCV/3zvRyJWnFt7dIvSP7yiWEAWwo84EFe+J4ZbwJOBFCHg7YjtpHbdmQ=";
+        const ES256_SIGNATURE: &str = "5w7W6TO_TTm526imowKBzwgcE8pOUnlYoERbrRuhJWzCj1666s0AzARoFspdusqfGzBbua6g1wDz4ksSik3LWg";
+
+        /// Key of the first certificate in the `certificatePath` of the signed example.
+        const EXAMPLE_PUBLIC_KEY: &str = "BHHp7A83DBJIInj8+g1we3A7sBXprIQBUfdFDVUBQoPExq8rze6ewG0+eVcSF72J77gKiD0IHnzpwHaU7t6nVeY=";
+
+        fn signed_bom(algorithm: Algorithm, value: &str) -> Value {
+            Bom {
+                signature: Some(Signature::single(algorithm, value)),
+                ..bom()
+            }
+            .to_json_value(SpecVersion::V1_5)
+            .unwrap()
+        }
+
+        fn tamper(mut document: Value) -> Value {
+            document["components"][0]["version"] = Value::from("1.0.194");
+            document
+        }
+
+        #[test]
+        fn it_should_verify_an_rs256_signature() {
+            let key = PublicKey::Rsa(STANDARD.decode(RSA_PUBLIC_KEY).unwrap());
+            let document = signed_bom(Algorithm::RS256, RS256_SIGNATURE);
+
+            assert!(verify_json_signature(&document, &key).is_ok());
+            assert!(matches!(
+                verify_json_signature(&tamper(document), &key),
+                Err(SignatureError::VerificationFailed)
+            ));
+        }
+
+        #[test]
+        fn it_should_verify_an_es256_signature() {
+            let key = PublicKey::EcdsaP256(STANDARD.decode(EC_PUBLIC_KEY).unwrap());
+            let document = signed_bom(Algorithm::ES256, ES256_SIGNATURE);
+
+            assert!(verify_json_signature(&document, &key).is_ok());
+            assert!(matches!(
+                verify_json_signature(&tamper(document), &key),
+                Err(SignatureError::VerificationFailed)
+            ));
+        }
+
+        #[test]
+        fn it_should_verify_the_signed_example_of_the_specification() {
+            let key = PublicKey::EcdsaP256(STANDARD.decode(EXAMPLE_PUBLIC_KEY).unwrap());
+            let document = signed_example();
+
+            assert!(verify_json_signature(&document["components"][0], &key).is_ok());
+            assert!(verify_json_signature(&document["services"][0], &key).is_ok());
+            assert!(verify_json_signature(&document["compositions"][0], &key).is_ok());
+        }
+
+        #[test]
+        fn it_should_reject_a_key_for_another_algorithm() {
+            let key = PublicKey::EcdsaP256(STANDARD.decode(EC_PUBLIC_KEY).unwrap());
+            let document = signed_bom(Algorithm::RS256, RS256_SIGNATURE);
+
+            assert!(matches!(
+                verify_json_signature(&document, &key),
+                Err(SignatureError::KeyMismatch(_))
+            ));
+        }
+
+        #[test]
+        fn it_should_reject_an_unsigned_document() {
+            let document = bom().to_json_value(SpecVersion::V1_5).unwrap();
+            let key = PublicKey::EcdsaP256(STANDARD.decode(EC_PUBLIC_KEY).unwrap());
+
+            assert!(matches!(
+                verify_json_signature(&document, &key),
+                Err(SignatureError::MissingSignature)
+            ));
+        }
+    }
+}
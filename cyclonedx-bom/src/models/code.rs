@@ -130,7 +130,9 @@ pub fn validate_issue_classification(
         classification,
         IssueClassification::UnknownIssueClassification(_)
     ) {
-        return Err(ValidationError::new("Unknown issue classification"));
+        return Err(ValidationError::unknown_value(
+            "Unknown issue classification",
+        ));
     }
     Ok(())
 }
@@ -198,7 +200,9 @@ pub fn validate_patch_classification(
         classification,
         PatchClassification::UnknownPatchClassification(_)
     ) {
-        return Err("Unknown patch classification".into());
+        return Err(ValidationError::unknown_value(
+            "Unknown patch classification",
+        ));
     }
     Ok(())
 }
@@ -387,7 +391,10 @@ mod test {
                 [(
                     0,
                     vec![
-                        validation::r#enum("patch_type", "Unknown patch classification"),
+                        validation::r#enum(
+                            "patch_type",
+                            ValidationError::unknown_value("Unknown patch classification")
+                        ),
                         validation::r#struct(
                             "diff",
                             vec![
@@ -408,7 +415,12 @@ mod test {
                             [(
                                 0,
                                 vec![
-                                    validation::field("issue_type", "Unknown issue classification"),
+                                    validation::field(
+                                        "issue_type",
+                                        ValidationError::unknown_value(
+                                            "Unknown issue classification"
+                                        )
+                                    ),
                                     validation::field("id", "NormalizedString contains invalid characters \\r \\n \\t or \\r\\n"),
                                     validation::field("name", "NormalizedString contains invalid characters \\r \\n \\t or \\r\\n"),
                                     validation::field("description", "NormalizedString contains invalid characters \\r \\n \\t or \\r\\n"),
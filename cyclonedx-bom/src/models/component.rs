@@ -26,12 +26,14 @@ use crate::external_models::uri::{validate_purl, validate_uri as validate_url};
 use crate::models::attached_text::AttachedText;
 use crate::models::bom::BomReference;
 use crate::models::code::{Commits, Patches};
-use crate::models::external_reference::ExternalReferences;
-use crate::models::hash::Hashes;
-use crate::models::license::Licenses;
+use crate::models::external_reference::{ExternalReference, ExternalReferences};
+#[cfg(feature = "hashes")]
+use crate::models::hash::HashAlgorithm;
+use crate::models::hash::{Hash, Hashes};
+use crate::models::license::{LicenseChoice, Licenses};
 use crate::models::organization::OrganizationalEntity;
-use crate::models::property::Properties;
-use crate::validation::ValidationError;
+use crate::models::property::{Properties, Property};
+use crate::validation::{validate_non_empty, ValidationError};
 use crate::{
     external_models::{
         normalized_string::NormalizedString,
@@ -42,6 +44,7 @@ use crate::{
 
 use super::bom::{validate_bom_ref, SpecVersion};
 use super::component_data::ComponentData;
+use super::extension::{Extensions, RawXml};
 use super::modelcard::ModelCard;
 use super::signature::Signature;
 
@@ -76,6 +79,10 @@ pub struct Component {
     pub model_card: Option<ModelCard>,
     /// Added in version 1.5
     pub data: Option<ComponentData>,
+    /// XML elements outside of the CycloneDX schema
+    pub foreign_elements: Vec<RawXml>,
+    /// JSON fields outside of the CycloneDX schema
+    pub extra: Extensions,
 }
 
 impl Component {
@@ -112,8 +119,230 @@ impl Component {
             signature: None,
             model_card: None,
             data: None,
+            foreign_elements: Vec::new(),
+            extra: Extensions::default(),
         }
     }
+
+    /// Returns the licenses of the component, adding an empty list if there is none yet.
+    pub fn licenses_mut(&mut self) -> &mut Licenses {
+        self.licenses.get_or_insert_with(|| Licenses(Vec::new()))
+    }
+
+    /// Converts named licenses to SPDX identifiers where possible and removes duplicates, see
+    /// [`Licenses::normalize`].
+    pub fn normalize_licenses(&mut self) {
+        if let Some(licenses) = &mut self.licenses {
+            licenses.normalize();
+        }
+    }
+
+    /// Returns the package type of the component's purl, e.g. [`Purl::CARGO_TYPE`] for crates.
+    pub fn well_known_purl_type(&self) -> Option<&str> {
+        let purl = self.purl.as_ref()?.0.strip_prefix("pkg:")?;
+        purl.split_once('/').map(|(package_type, _)| package_type)
+    }
+
+    /// Computes the hashes of the file at `path` with each of the `algorithms` and appends them
+    /// to the component's hashes, requires the `hashes` feature.
+    /// See [`Hash::from_file`] for the supported algorithms.
+    #[cfg(feature = "hashes")]
+    pub fn add_file_hashes(
+        &mut self,
+        path: impl AsRef<std::path::Path>,
+        algorithms: &[HashAlgorithm],
+    ) -> std::io::Result<()> {
+        let hashes = algorithms
+            .iter()
+            .map(|algorithm| Hash::from_file(path.as_ref(), algorithm.clone()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        self.hashes
+            .get_or_insert_with(|| Hashes(Vec::new()))
+            .0
+            .extend(hashes);
+        Ok(())
+    }
+
+    /// Returns the component's evidence, inserting an empty one if none is present yet.
+    pub fn evidence_mut(&mut self) -> &mut ComponentEvidence {
+        self.evidence.get_or_insert_with(ComponentEvidence::default)
+    }
+
+    /// Appends a license to the component's evidence.
+    pub fn add_evidence_license(&mut self, license: LicenseChoice) {
+        self.evidence_mut()
+            .licenses
+            .get_or_insert_with(|| Licenses(Vec::new()))
+            .0
+            .push(license);
+    }
+
+    /// Returns the declared copyright followed by all copyright texts found as evidence.
+    pub fn all_copyrights(&self) -> Vec<&str> {
+        let declared = self.copyright.iter().map(|copyright| copyright.as_ref());
+        let evidence = self
+            .evidence
+            .iter()
+            .filter_map(|evidence| evidence.copyright.as_ref())
+            .flat_map(|copyrights| copyrights.0.iter().map(|copyright| copyright.0.as_str()));
+        declared.chain(evidence).collect()
+    }
+
+    /// Appends an occurrence to the component's evidence.
+    /// Occurrences were added in version 1.5.
+    pub fn add_occurrence(&mut self, occurrence: Occurrence) {
+        self.evidence_mut()
+            .occurrences
+            .get_or_insert_with(|| Occurrences(Vec::new()))
+            .0
+            .push(occurrence);
+    }
+}
+
+/// Builds a [`Component`] fluently, leaving every field not set explicitly empty.
+///
+/// The type and name are required up front, so an incomplete component cannot be built.
+/// ```
+/// use cyclonedx_bom::models::component::{Classification, ComponentBuilder};
+/// use cyclonedx_bom::models::license::LicenseChoice;
+/// use cyclonedx_bom::prelude::{Purl, SpdxExpression};
+///
+/// let component = ComponentBuilder::new(Classification::Library, "serde")
+///     .version("1.0.193")
+///     .bom_ref("serde 1.0.193")
+///     .purl(Purl::cargo("serde", "1.0.193").unwrap())
+///     .add_license(LicenseChoice::Expression(SpdxExpression::new("MIT OR Apache-2.0")))
+///     .build();
+///
+/// assert_eq!(component.name.to_string(), "serde");
+/// assert_eq!(component.licenses.unwrap().0.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ComponentBuilder {
+    component: Component,
+}
+
+impl ComponentBuilder {
+    pub fn new(component_type: Classification, name: &str) -> Self {
+        let mut component = Component::new(component_type, name, "", None);
+        component.version = None;
+        Self { component }
+    }
+
+    pub fn bom_ref(mut self, bom_ref: impl Into<String>) -> Self {
+        self.component.bom_ref = Some(bom_ref.into());
+        self
+    }
+
+    pub fn mime_type(mut self, mime_type: MimeType) -> Self {
+        self.component.mime_type = Some(mime_type);
+        self
+    }
+
+    pub fn supplier(mut self, supplier: OrganizationalEntity) -> Self {
+        self.component.supplier = Some(supplier);
+        self
+    }
+
+    pub fn author(mut self, author: &str) -> Self {
+        self.component.author = Some(NormalizedString::new(author));
+        self
+    }
+
+    pub fn publisher(mut self, publisher: &str) -> Self {
+        self.component.publisher = Some(NormalizedString::new(publisher));
+        self
+    }
+
+    pub fn group(mut self, group: &str) -> Self {
+        self.component.group = Some(NormalizedString::new(group));
+        self
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.component.version = Some(NormalizedString::new(version));
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.component.description = Some(NormalizedString::new(description));
+        self
+    }
+
+    pub fn scope(mut self, scope: Scope) -> Self {
+        self.component.scope = Some(scope);
+        self
+    }
+
+    pub fn copyright(mut self, copyright: &str) -> Self {
+        self.component.copyright = Some(NormalizedString::new(copyright));
+        self
+    }
+
+    pub fn cpe(mut self, cpe: Cpe) -> Self {
+        self.component.cpe = Some(cpe);
+        self
+    }
+
+    pub fn purl(mut self, purl: Purl) -> Self {
+        self.component.purl = Some(purl);
+        self
+    }
+
+    pub fn swid(mut self, swid: Swid) -> Self {
+        self.component.swid = Some(swid);
+        self
+    }
+
+    pub fn add_hash(mut self, hash: Hash) -> Self {
+        self.component
+            .hashes
+            .get_or_insert_with(|| Hashes(Vec::new()))
+            .0
+            .push(hash);
+        self
+    }
+
+    pub fn add_license(mut self, license: LicenseChoice) -> Self {
+        self.component
+            .licenses
+            .get_or_insert_with(|| Licenses(Vec::new()))
+            .0
+            .push(license);
+        self
+    }
+
+    pub fn add_external_reference(mut self, external_reference: ExternalReference) -> Self {
+        self.component
+            .external_references
+            .get_or_insert_with(|| ExternalReferences(Vec::new()))
+            .0
+            .push(external_reference);
+        self
+    }
+
+    pub fn add_property(mut self, property: Property) -> Self {
+        self.component
+            .properties
+            .get_or_insert_with(|| Properties(Vec::new()))
+            .0
+            .push(property);
+        self
+    }
+
+    /// Adds a nested component.
+    pub fn add_component(mut self, component: Component) -> Self {
+        self.component
+            .components
+            .get_or_insert_with(|| Components(Vec::new()))
+            .0
+            .push(component);
+        self
+    }
+
+    pub fn build(self) -> Component {
+        self.component
+    }
 }
 
 impl Validate for Component {
@@ -131,7 +360,10 @@ impl Validate for Component {
             validate_normalized_string,
         );
         ctx.add_field_option("group", self.group.as_ref(), validate_normalized_string);
-        ctx.add_field("name", &self.name, validate_normalized_string);
+        ctx.add_field("name", &self.name, |name| {
+            validate_normalized_string(name)?;
+            validate_non_empty(name)
+        });
         ctx.add_field_option("version", self.version.as_ref(), validate_normalized_string);
         ctx.add_field_option(
             "description",
@@ -149,6 +381,13 @@ impl Validate for Component {
         ctx.add_field_option("cpe", self.cpe.as_ref(), validate_cpe);
         ctx.add_field_option("purl", self.purl.as_ref(), validate_purl);
         ctx.add_struct_option("swid", self.swid.as_ref(), version);
+        if self.purl.is_none() && self.cpe.is_none() && self.swid.is_none() {
+            // Without any identifier the component can hardly be matched with vulnerability data
+            ctx.add_warning(
+                "",
+                "Component has no unique identifier, expected one of purl, cpe or swid",
+            );
+        }
         ctx.add_struct_option("pedigree", self.pedigree.as_ref(), version);
         ctx.add_struct_option(
             "external_references",
@@ -180,13 +419,10 @@ pub fn validate_classification(
     classification: &Classification,
     version: SpecVersion,
 ) -> Result<(), ValidationError> {
-    if SpecVersion::V1_3 <= version && version <= SpecVersion::V1_4 {
-        if Classification::File < *classification {
-            return Err(ValidationError::new("Unknown classification"));
-        }
-    } else if SpecVersion::V1_5 <= version
-        && matches!(classification, Classification::UnknownClassification(_))
-    {
+    if matches!(classification, Classification::UnknownClassification(_)) {
+        return Err(ValidationError::unknown_value("Unknown classification"));
+    }
+    if version <= SpecVersion::V1_4 && Classification::File < *classification {
         return Err(ValidationError::new("Unknown classification"));
     }
     Ok(())
@@ -239,7 +475,7 @@ impl Classification {
 
 pub fn validate_scope(scope: &Scope) -> Result<(), ValidationError> {
     if matches!(scope, Scope::UnknownScope(_)) {
-        return Err(ValidationError::new("Unknown scope"));
+        return Err(ValidationError::unknown_value("Unknown scope"));
     }
     Ok(())
 }
@@ -304,13 +540,13 @@ impl Validate for Swid {
 }
 
 pub fn validate_cpe(cpe: &Cpe) -> Result<(), ValidationError> {
-    static UUID_REGEX: Lazy<Regex> = Lazy::new(|| {
+    static CPE_REGEX: Lazy<Regex> = Lazy::new(|| {
         Regex::new(
-            r##"([c][pP][eE]:/[AHOaho]?(:[A-Za-z0-9\._\-~%]*){0,6})|(cpe:2\.3:[aho\*\-](:(((\?*|\*?)([a-zA-Z0-9\-\._]|(\\[\\\*\?!"#$$%&'\(\)\+,/:;<=>@\[\]\^`\{\|}~]))+(\?*|\*?))|[\*\-])){5}(:(([a-zA-Z]{2,3}(-([a-zA-Z]{2}|[0-9]{3}))?)|[\*\-]))(:(((\?*|\*?)([a-zA-Z0-9\-\._]|(\\[\\\*\?!"#$$%&'\(\)\+,/:;<=>@\[\]\^`\{\|}~]))+(\?*|\*?))|[\*\-])){4})"##,
+            r##"^(?:([c][pP][eE]:/[AHOaho]?(:[A-Za-z0-9\._\-~%]*){0,6})|(cpe:2\.3:[aho\*\-](:(((\?*|\*?)([a-zA-Z0-9\-\._]|(\\[\\\*\?!"#$$%&'\(\)\+,/:;<=>@\[\]\^`\{\|}~]))+(\?*|\*?))|[\*\-])){5}(:(([a-zA-Z]{2,3}(-([a-zA-Z]{2}|[0-9]{3}))?)|[\*\-]))(:(((\?*|\*?)([a-zA-Z0-9\-\._]|(\\[\\\*\?!"#$$%&'\(\)\+,/:;<=>@\[\]\^`\{\|}~]))+(\?*|\*?))|[\*\-])){4}))$"##,
         ).expect("Failed to compile regex.")
     });
 
-    if !UUID_REGEX.is_match(&cpe.0) {
+    if !CPE_REGEX.is_match(&cpe.0) {
         return Err(ValidationError::new(
             "Cpe does not match regular expression",
         ));
@@ -323,7 +559,22 @@ pub fn validate_cpe(cpe: &Cpe) -> Result<(), ValidationError> {
 pub struct Cpe(pub(crate) String);
 
 impl Cpe {
-    pub fn new(inner: &str) -> Self {
+    /// Creates a CPE, checking that it is either a CPE 2.3 formatted string like
+    /// `cpe:2.3:a:vendor:product:1.0.0:*:*:*:*:*:*:*` or a CPE 2.2 URI like
+    /// `cpe:/a:vendor:product:1.0.0`.
+    /// ```
+    /// use cyclonedx_bom::models::component::Cpe;
+    ///
+    /// assert!(Cpe::new("cpe:/a:example:mylibrary:1.0.0").is_ok());
+    /// assert!(Cpe::new("cpe:2.3:a:example:mylibrary").is_err());
+    /// ```
+    pub fn new(inner: &str) -> Result<Self, ValidationError> {
+        let cpe = Self::new_unchecked(inner);
+        validate_cpe(&cpe)?;
+        Ok(cpe)
+    }
+
+    pub(crate) fn new_unchecked(inner: &str) -> Self {
         Self(inner.to_string())
     }
 }
@@ -358,7 +609,7 @@ impl std::fmt::Display for Cpe {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct ComponentEvidence {
     pub licenses: Option<Licenses>,
     pub copyright: Option<CopyrightTexts>,
@@ -370,6 +621,24 @@ pub struct ComponentEvidence {
     pub identity: Option<Identity>,
 }
 
+impl ComponentEvidence {
+    /// Returns the identity fields whose confidence lies below `threshold`.
+    ///
+    /// The overall confidence of the identity is used, or the highest confidence of its methods
+    /// when it has none. Fields without any confidence are not reported.
+    pub fn low_confidence_fields(&self, threshold: f32) -> Vec<IdentityField> {
+        self.identity
+            .iter()
+            .filter(|identity| {
+                identity
+                    .confidence()
+                    .is_some_and(|confidence| confidence < threshold)
+            })
+            .map(|identity| identity.field.clone())
+            .collect()
+    }
+}
+
 impl Validate for ComponentEvidence {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
         ValidationContext::new()
@@ -511,7 +780,10 @@ impl ConfidenceScore {
 
 pub fn validate_identity_field(field: &IdentityField) -> Result<(), ValidationError> {
     if let IdentityField::Unknown(unknown) = field {
-        return Err(format!("Unknown identity found '{}' given", unknown).into());
+        return Err(ValidationError::unknown_value(format!(
+            "Unknown identity found '{}' given",
+            unknown
+        )));
     }
     Ok(())
 }
@@ -527,6 +799,7 @@ pub enum IdentityField {
     Cpe,
     Swid,
     Hash,
+    #[strum(default)]
     Unknown(String),
 }
 
@@ -557,6 +830,22 @@ pub struct Identity {
     pub tools: Option<ToolsReferences>,
 }
 
+impl Identity {
+    /// The overall confidence, falling back to the most confident method.
+    fn confidence(&self) -> Option<f32> {
+        self.confidence
+            .as_ref()
+            .map(ConfidenceScore::get)
+            .or_else(|| {
+                self.methods
+                    .iter()
+                    .flat_map(|methods| &methods.0)
+                    .map(|method| method.confidence.get())
+                    .reduce(f32::max)
+            })
+    }
+}
+
 impl Validate for Identity {
     fn validate_version(&self, _version: SpecVersion) -> ValidationResult {
         ValidationContext::new()
@@ -637,7 +926,7 @@ mod test {
             data_governance::{DataGovernance, DataGovernanceResponsibleParty},
             external_reference::{ExternalReference, ExternalReferenceType, Uri},
             hash::{Hash, HashAlgorithm, HashValue},
-            license::LicenseChoice,
+            license::License,
             modelcard::{
                 ApproachType, ConfidenceInterval, Considerations, Dataset, Datasets, Inputs,
                 MLParameter, ModelParameters, ModelParametersApproach, Outputs, PerformanceMetric,
@@ -652,6 +941,9 @@ mod test {
 
     use super::*;
 
+    const NO_IDENTIFIER: &str =
+        "Component has no unique identifier, expected one of purl, cpe or swid";
+
     #[test]
     fn valid_components_should_pass_validation() {
         let vec = vec![Component {
@@ -846,6 +1138,8 @@ mod test {
                 description: None,
                 governance: None,
             }),
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         }];
         let validation_result = Components(vec).validate();
 
@@ -940,17 +1234,25 @@ mod test {
             signature: Some(Signature::single(Algorithm::HS512, "abcdefgh")),
             model_card: None,
             data: None,
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         }])
         .validate();
 
+        let unknown_term = "SPDX expression is not valid at position 0: unknown term";
+
         assert_eq!(
             validation_result,
-            validation::list(
+            vec![
+                validation::list(
                 "inner",
                 [(
                     0,
                     vec![
-                        validation::field("component_type", "Unknown classification"),
+                        validation::field(
+                            "component_type",
+                            ValidationError::unknown_value("Unknown classification")
+                        ),
                         validation::field(
                             "mime_type",
                             "MimeType does not match regular expression"
@@ -988,7 +1290,7 @@ mod test {
                         ),
                         validation::r#enum(
                             "scope",
-                            "Unknown scope"
+                            ValidationError::unknown_value("Unknown scope")
                         ),
                         validation::r#struct(
                             "hashes",
@@ -1009,10 +1311,7 @@ mod test {
                                 "inner",
                                 [(
                                     0,
-                                    validation::r#enum(
-                                        "expression",
-                                        "SPDX expression is not valid"
-                                    )
+                                    validation::r#enum("expression", unknown_term)
                                 )]
                             )
                         ),
@@ -1053,7 +1352,12 @@ mod test {
                                         "inner",
                                         [(
                                             0,
-                                            validation::field("component_type", "Unknown classification")
+                                            validation::field(
+                                                "component_type",
+                                                ValidationError::unknown_value(
+                                                    "Unknown classification"
+                                                )
+                                            )
                                         )]
                                     )
                                 ),
@@ -1063,7 +1367,12 @@ mod test {
                                         "inner",
                                         [(
                                             0,
-                                            validation::field("component_type", "Unknown classification")
+                                            validation::field(
+                                                "component_type",
+                                                ValidationError::unknown_value(
+                                                    "Unknown classification"
+                                                )
+                                            )
                                         )]
                                     )
                                 ),
@@ -1073,7 +1382,12 @@ mod test {
                                         "inner",
                                         [(
                                             0,
-                                            validation::field("component_type", "Unknown classification")
+                                            validation::field(
+                                                "component_type",
+                                                ValidationError::unknown_value(
+                                                    "Unknown classification"
+                                                )
+                                            )
                                         )]
                                     )
                                 ),
@@ -1096,25 +1410,17 @@ mod test {
                                         "inner",
                                         [(
                                             0,
-                                            validation::r#enum("patch_type", "Unknown patch classification")
+                                            validation::r#enum(
+                                                "patch_type",
+                                                ValidationError::unknown_value(
+                                                    "Unknown patch classification"
+                                                )
+                                            )
                                         )]
                                     )
                                 )
                             ]
                         ),
-                        validation::r#struct(
-                            "external_references",
-                            validation::list(
-                                "inner",
-                                [(
-                                    0,
-                                    validation::field(
-                                        "external_reference_type",
-                                        "Unknown external reference type"
-                                    )
-                                )]
-                            )
-                        ),
                         validation::r#struct(
                             "properties",
                             validation::list(
@@ -1134,7 +1440,10 @@ mod test {
                                 "inner",
                                 [(
                                     0,
-                                    validation::field("component_type", "Unknown classification")
+                                    validation::field(
+                                        "component_type",
+                                        ValidationError::unknown_value("Unknown classification")
+                                    )
                                 )]
                             )
                         ),
@@ -1146,17 +1455,295 @@ mod test {
                                     "inner",
                                     [(
                                         0,
-                                        validation::r#enum("expression", "SPDX expression is not valid")
+                                        validation::r#enum("expression", unknown_term)
                                     )]
                                 )
                             )
                         )
                     ]
                 )]
+            ),
+                validation::warning("inner[0].pedigree.ancestors.inner[0]", [NO_IDENTIFIER]),
+                validation::warning("inner[0].pedigree.descendants.inner[0]", [NO_IDENTIFIER]),
+                validation::warning("inner[0].pedigree.variants.inner[0]", [NO_IDENTIFIER]),
+                validation::warning("inner[0].components.inner[0]", [NO_IDENTIFIER]),
+                validation::warning(
+                    "inner[0].external_references.inner[0].external_reference_type",
+                    [ValidationError::unknown_value("Unknown external reference type")],
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_components_without_a_unique_identifier() {
+        let mut component = Component::new(Classification::Library, "lib-x", "1.0.0", None);
+
+        let validation_result = component.validate();
+
+        assert!(validation_result.passed());
+        assert_eq!(validation_result, validation::warning("", [NO_IDENTIFIER]));
+
+        component.purl = Some(Purl::cargo("lib-x", "1.0.0").unwrap());
+
+        let validation_result = component.validate();
+
+        assert!(!validation_result.has_warnings());
+    }
+
+    #[test]
+    fn it_should_normalize_licenses() {
+        let mut component = Component::new(Classification::Library, "lib-x", "1.0.0", None);
+        component.normalize_licenses();
+        assert_eq!(component.licenses, None);
+
+        let licenses = component.licenses_mut();
+        licenses.0.push(LicenseChoice::license("Apache 2.0"));
+        licenses
+            .0
+            .push(LicenseChoice::License(License::license_id("Apache-2.0")));
+        licenses
+            .0
+            .push(LicenseChoice::license("Company Proprietary License"));
+        component.normalize_licenses();
+
+        assert_eq!(
+            component.licenses,
+            Some(Licenses(vec![
+                LicenseChoice::License(License::license_id("Apache-2.0")),
+                LicenseChoice::license("Company Proprietary License"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_should_validate_the_mime_type_of_a_file_component() {
+        let mut component = Component::new(Classification::File, "README", "1.0.0", None);
+        component.cpe = Some(Cpe::new_unchecked("cpe:/a:example:readme:1.0.0"));
+
+        component.mime_type = Some(MimeType("text/markdown".to_string()));
+        assert!(component.validate().passed());
+
+        component.mime_type = Some(MimeType("Markdown text".to_string()));
+        assert_eq!(
+            component.validate(),
+            validation::field("mime_type", "MimeType does not match regular expression")
+        );
+    }
+
+    #[test]
+    fn it_should_validate_cpes() {
+        assert_eq!(
+            Cpe::new("cpe:2.3:a:example:mylibrary:1.0.0:*:*:*:*:*:*:*"),
+            Ok(Cpe(
+                "cpe:2.3:a:example:mylibrary:1.0.0:*:*:*:*:*:*:*".to_string()
+            ))
+        );
+        assert_eq!(
+            Cpe::new("cpe:/a:example:mylibrary:1.0.0"),
+            Ok(Cpe("cpe:/a:example:mylibrary:1.0.0".to_string()))
+        );
+        assert_eq!(
+            Cpe::new("cpe:2.3:a:example:mylibrary:1.0.0"),
+            Err(ValidationError::new(
+                "Cpe does not match regular expression"
+            ))
+        );
+        assert_eq!(
+            Cpe::new("prefix cpe:/a:example:mylibrary:1.0.0"),
+            Err(ValidationError::new(
+                "Cpe does not match regular expression"
+            ))
+        );
+    }
+
+    #[test]
+    fn it_should_validate_the_identity_field() {
+        let mut identity = Identity {
+            field: IdentityField::new_unchecked("group"),
+            confidence: Some(ConfidenceScore::new(0.8)),
+            methods: None,
+            tools: None,
+        };
+        assert_eq!(identity.field, IdentityField::Group);
+        assert_eq!(identity.field.to_string(), "group");
+        assert!(identity.validate().passed());
+
+        identity.field = IdentityField::new_unchecked("author");
+        assert_eq!(identity.field, IdentityField::Unknown("author".to_string()));
+        assert_eq!(identity.field.to_string(), "author");
+        assert_eq!(
+            identity.validate(),
+            validation::field(
+                "field",
+                ValidationError::unknown_value("Unknown identity found 'author' given")
             )
         );
     }
 
+    #[test]
+    fn it_should_report_fields_identified_with_low_confidence() {
+        let evidence =
+            |field: IdentityField, confidence: Option<f32>, methods: &[f32]| ComponentEvidence {
+                identity: Some(Identity {
+                    field,
+                    confidence: confidence.map(ConfidenceScore::new),
+                    methods: Some(Methods(
+                        methods
+                            .iter()
+                            .map(|confidence| Method {
+                                technique: "filename".to_string(),
+                                confidence: ConfidenceScore::new(*confidence),
+                                value: None,
+                            })
+                            .collect(),
+                    )),
+                    tools: None,
+                }),
+                ..ComponentEvidence::default()
+            };
+
+        let name = evidence(IdentityField::Name, Some(0.3), &[0.9]);
+        assert_eq!(name.low_confidence_fields(0.5), vec![IdentityField::Name]);
+        assert!(name.low_confidence_fields(0.3).is_empty());
+
+        let purl = evidence(IdentityField::Purl, None, &[0.3, 0.9]);
+        assert!(purl.low_confidence_fields(0.5).is_empty());
+        assert_eq!(purl.low_confidence_fields(0.95), vec![IdentityField::Purl]);
+
+        let unknown = evidence(IdentityField::Version, None, &[]);
+        assert!(unknown.low_confidence_fields(1.0).is_empty());
+        assert!(ComponentEvidence::default()
+            .low_confidence_fields(1.0)
+            .is_empty());
+    }
+
+    #[cfg(feature = "hashes")]
+    #[test]
+    fn it_should_add_the_hashes_of_a_file() {
+        use crate::models::hash::HashValue;
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/hash_input.txt");
+        let mut component = Component::new(Classification::File, "hash_input.txt", "1.0", None);
+
+        component
+            .add_file_hashes(path, &[HashAlgorithm::SHA1, HashAlgorithm::SHA_256])
+            .unwrap();
+
+        assert_eq!(
+            component.hashes,
+            Some(Hashes(vec![
+                Hash {
+                    alg: HashAlgorithm::SHA1,
+                    content: HashValue("be417768b5c3c5c1d9bcb2e7c119196dd76b5570".to_string()),
+                },
+                Hash {
+                    alg: HashAlgorithm::SHA_256,
+                    content: HashValue(
+                        "c03905fcdab297513a620ec81ed46ca44ddb62d41cbbd83eb4a5a3592be26a69"
+                            .to_string()
+                    ),
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_should_round_trip_and_gate_classifications_by_version() {
+        let before_1_5 = [
+            ("application", Classification::Application),
+            ("framework", Classification::Framework),
+            ("library", Classification::Library),
+            ("container", Classification::Container),
+            ("operating-system", Classification::OperatingSystem),
+            ("device", Classification::Device),
+            ("firmware", Classification::Firmware),
+            ("file", Classification::File),
+        ];
+        let added_in_1_5 = [
+            ("platform", Classification::Platform),
+            ("device-driver", Classification::DeviceDriver),
+            (
+                "machine-learning-model",
+                Classification::MachineLearningModel,
+            ),
+            ("data", Classification::Data),
+        ];
+
+        for (name, classification) in before_1_5.iter().chain(&added_in_1_5) {
+            assert_eq!(&Classification::new_unchecked(name), classification);
+            assert_eq!(classification.to_string(), *name);
+            assert!(validate_classification(classification, SpecVersion::V1_5).is_ok());
+        }
+        for version in [SpecVersion::V1_3, SpecVersion::V1_4] {
+            for (_, classification) in &before_1_5 {
+                assert!(validate_classification(classification, version).is_ok());
+            }
+            for (_, classification) in &added_in_1_5 {
+                assert!(validate_classification(classification, version).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn it_should_return_the_purl_type() {
+        let mut component = Component::new(Classification::Library, "serde", "1.0.193", None);
+        assert_eq!(component.well_known_purl_type(), None);
+
+        component.purl = Some(Purl::cargo("serde", "1.0.193").unwrap());
+        assert_eq!(component.well_known_purl_type(), Some(Purl::CARGO_TYPE));
+    }
+
+    #[test]
+    fn it_should_build_a_component() {
+        let actual = ComponentBuilder::new(Classification::Library, "lib")
+            .version("1.0.0")
+            .bom_ref("lib-ref")
+            .scope(Scope::Required)
+            .add_property(Property::new("name", "value"))
+            .add_component(ComponentBuilder::new(Classification::File, "lib.rs").build())
+            .build();
+
+        let mut nested = Component::new(Classification::File, "lib.rs", "", None);
+        nested.version = None;
+        let mut expected = Component::new(
+            Classification::Library,
+            "lib",
+            "1.0.0",
+            Some("lib-ref".to_string()),
+        );
+        expected.scope = Some(Scope::Required);
+        expected.properties = Some(Properties(vec![Property::new("name", "value")]));
+        expected.components = Some(Components(vec![nested]));
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn it_should_append_evidence_to_a_component_without_evidence() {
+        let mut component = Component::new(Classification::Library, "lib-x", "1.0.0", None);
+        assert_eq!(component.evidence, None);
+
+        component.add_evidence_license(LicenseChoice::Expression(SpdxExpression::new("MIT")));
+        component.add_occurrence(Occurrence::new("src/lib.rs"));
+        component.add_occurrence(Occurrence::new("LICENSE"));
+
+        assert_eq!(
+            component.evidence,
+            Some(ComponentEvidence {
+                licenses: Some(Licenses(vec![LicenseChoice::Expression(
+                    SpdxExpression::new("MIT")
+                )])),
+                occurrences: Some(Occurrences(vec![
+                    Occurrence::new("src/lib.rs"),
+                    Occurrence::new("LICENSE"),
+                ])),
+                ..ComponentEvidence::default()
+            })
+        );
+    }
+
     fn invalid_component() -> Component {
         Component {
             component_type: Classification::UnknownClassification("unknown".to_string()),
@@ -1185,6 +1772,8 @@ mod test {
             signature: None,
             model_card: None,
             data: None,
+            foreign_elements: Vec::new(),
+            extra: Default::default(),
         }
     }
 
@@ -1206,4 +1795,28 @@ mod test {
         )
         .is_err());
     }
+
+    #[test]
+    fn it_should_deduplicate_components_in_a_hash_set() {
+        // Without serde_json's `preserve_order` feature the first two extensions are identical
+        // maps, see the extension tests for the member order
+        let component = |extra: serde_json::Value| {
+            let mut component = Component::new(Classification::Library, "lib-x", "1.0.0", None);
+            component.purl = Some(Purl::cargo("lib-x", "1.0.0").unwrap());
+            component.extra = Extensions(extra.as_object().unwrap().clone().into_iter().collect());
+            component
+        };
+
+        let components: std::collections::HashSet<_> = [
+            component(serde_json::json!({ "x-vendor": { "a": 1, "b": [true, null] } })),
+            component(serde_json::json!({ "x-vendor": { "b": [true, null], "a": 1 } })),
+            component(serde_json::json!({ "x-vendor": { "a": 2, "b": [true, null] } })),
+            Component::new(Classification::Library, "lib-x", "1.0.0", None),
+            Component::new(Classification::Library, "lib-x", "1.0.0", None),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(components.len(), 3);
+    }
 }
@@ -56,7 +56,9 @@ impl Validate for ComponentData {
 
 fn validate_datatype(datatype: &ComponentDataType) -> Result<(), ValidationError> {
     if matches!(datatype, ComponentDataType::Unknown(_)) {
-        return Err("Unknown component data type found".into());
+        return Err(ValidationError::unknown_value(
+            "Unknown component data type found",
+        ));
     }
     Ok(())
 }
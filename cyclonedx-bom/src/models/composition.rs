@@ -62,14 +62,18 @@ pub fn validate_aggregate_type(
     aggregate_type: &AggregateType,
     version: SpecVersion,
 ) -> Result<(), ValidationError> {
-    if version <= SpecVersion::V1_4 {
-        if AggregateType::IncompleteFirstPartyProprietaryOnly < *aggregate_type {
-            return Err("Unknown aggregate type".into());
-        }
-    } else if version <= SpecVersion::V1_5
-        && matches!(aggregate_type, AggregateType::UnknownAggregateType(_))
+    if matches!(aggregate_type, AggregateType::UnknownAggregateType(unknown) if unknown.trim().is_empty())
+    {
+        return Err(ValidationError::new("Missing aggregate type"));
+    }
+
+    if matches!(aggregate_type, AggregateType::UnknownAggregateType(_)) {
+        return Err(ValidationError::unknown_value("Unknown aggregate type"));
+    }
+    if version <= SpecVersion::V1_4
+        && AggregateType::IncompleteFirstPartyProprietaryOnly < *aggregate_type
     {
-        return Err(ValidationError::new("Unknown aggregate type"));
+        return Err("Unknown aggregate type".into());
     }
     Ok(())
 }
@@ -103,7 +107,7 @@ impl AggregateType {
             "complete" => Self::Complete,
             "incomplete" => Self::Incomplete,
             "incomplete_first_party_only" => Self::IncompleteFirstPartyOnly,
-            "incomplete_first_party_propprietary_only" => Self::IncompleteFirstPartyProprietaryOnly,
+            "incomplete_first_party_proprietary_only" => Self::IncompleteFirstPartyProprietaryOnly,
             "incomplete_first_party_opensource_only" => Self::IncompleteFirstPartyOpensourceOnly,
             "incomplete_third_party_only" => Self::IncompleteThirdPartyOnly,
             "incomplete_third_party_proprietary_only" => Self::IncompleteThirdPartyProprietaryOnly,
@@ -155,9 +159,53 @@ mod test {
                 "composition",
                 [(
                     0,
-                    validation::r#field("aggregate", "Unknown aggregate type")
+                    validation::r#field(
+                        "aggregate",
+                        ValidationError::unknown_value("Unknown aggregate type")
+                    )
                 )]
             )
         );
     }
+
+    #[test]
+    fn it_should_fail_validation_for_a_missing_aggregate() {
+        let validation_result = Compositions(vec![Composition {
+            bom_ref: None,
+            aggregate: AggregateType::new_unchecked(""),
+            assemblies: None,
+            dependencies: None,
+            vulnerabilities: None,
+            signature: None,
+        }])
+        .validate();
+
+        assert_eq!(
+            validation_result,
+            validation::list(
+                "composition",
+                [(0, validation::field("aggregate", "Missing aggregate type"))]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_recognize_all_aggregate_types() {
+        for aggregate in [
+            "complete",
+            "incomplete",
+            "incomplete_first_party_only",
+            "incomplete_first_party_proprietary_only",
+            "incomplete_first_party_opensource_only",
+            "incomplete_third_party_only",
+            "incomplete_third_party_proprietary_only",
+            "incomplete_third_party_opensource_only",
+            "unknown",
+            "not_specified",
+        ] {
+            let aggregate_type = AggregateType::new_unchecked(aggregate);
+            assert_eq!(aggregate_type.to_string(), aggregate);
+            assert!(validate_aggregate_type(&aggregate_type, SpecVersion::V1_5).is_ok());
+        }
+    }
 }
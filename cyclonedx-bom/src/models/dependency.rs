@@ -16,11 +16,277 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use indexmap::IndexSet;
+
+use crate::errors::CycleError;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Dependencies(pub Vec<Dependency>);
 
+impl Dependencies {
+    /// Returns all bom-refs of the graph ordered so that every ref comes after the refs it
+    /// depends on, e.g. in build order.
+    ///
+    /// Refs that are only mentioned as a dependency are included too. Fails with the refs of the
+    /// first cycle found, see [`Dependencies::find_cycles`].
+    pub fn topological_sort(&self) -> Result<Vec<String>, CycleError> {
+        if let Some(refs) = self.find_cycles().into_iter().next() {
+            return Err(CycleError { refs });
+        }
+
+        let graph = Graph::new(self);
+        let mut visited = vec![false; graph.refs.len()];
+        let mut order = Vec::with_capacity(graph.refs.len());
+        // Depth first search with an explicit stack of nodes and the position of their next
+        // dependency, so that long dependency chains cannot overflow the call stack.
+        let mut stack = Vec::new();
+        for root in 0..graph.refs.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            stack.push((root, 0));
+            while let Some((node, next)) = stack.pop() {
+                match graph.edges[node].get(next) {
+                    Some(&dependency) => {
+                        stack.push((node, next + 1));
+                        if !visited[dependency] {
+                            visited[dependency] = true;
+                            stack.push((dependency, 0));
+                        }
+                    }
+                    None => order.push(node),
+                }
+            }
+        }
+
+        Ok(order
+            .into_iter()
+            .map(|node| graph.refs[node].to_string())
+            .collect())
+    }
+
+    /// Returns the bom-refs of every dependency cycle, including refs depending on themselves.
+    ///
+    /// Refs that are part of several intertwined cycles are reported as a single cycle. Refs are
+    /// listed in the order they first appear in the dependencies.
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let graph = Graph::new(self);
+        let mut tarjan = Tarjan {
+            graph: &graph,
+            index: vec![None; graph.refs.len()],
+            low_link: vec![0; graph.refs.len()],
+            on_stack: vec![false; graph.refs.len()],
+            stack: Vec::new(),
+            next_index: 0,
+            components: Vec::new(),
+        };
+        for node in 0..graph.refs.len() {
+            if tarjan.index[node].is_none() {
+                tarjan.connect(node);
+            }
+        }
+
+        let mut cycles: Vec<Vec<usize>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || graph.edges[component[0]].contains(&component[0])
+            })
+            .map(|mut component| {
+                component.sort_unstable();
+                component
+            })
+            .collect();
+        cycles.sort_unstable();
+
+        cycles
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .into_iter()
+                    .map(|node| graph.refs[node].to_string())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The dependency graph with bom-refs replaced by their index in `refs`.
+struct Graph<'a> {
+    refs: IndexSet<&'a str>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<'a> Graph<'a> {
+    fn new(dependencies: &'a Dependencies) -> Self {
+        let mut refs = IndexSet::new();
+        let mut edges: Vec<Vec<usize>> = Vec::new();
+        for dependency in &dependencies.0 {
+            let (from, _) = refs.insert_full(dependency.dependency_ref.as_str());
+            for to in &dependency.dependencies {
+                let (to, _) = refs.insert_full(to.as_str());
+                edges.resize_with(refs.len(), Vec::new);
+                if !edges[from].contains(&to) {
+                    edges[from].push(to);
+                }
+            }
+        }
+        edges.resize_with(refs.len(), Vec::new);
+        Self { refs, edges }
+    }
+}
+
+/// State of Tarjan's algorithm to find the strongly connected components of a [`Graph`].
+struct Tarjan<'a, 'b> {
+    graph: &'b Graph<'a>,
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_, '_> {
+    /// Finds the components reachable from `root`, keeping the nodes being visited and the
+    /// position of their next dependency on an explicit stack instead of recursing.
+    fn connect(&mut self, root: usize) {
+        self.enter(root);
+        let mut calls = vec![(root, 0)];
+        while let Some((node, next)) = calls.pop() {
+            match self.graph.edges[node].get(next) {
+                Some(&dependency) => {
+                    calls.push((node, next + 1));
+                    match self.index[dependency] {
+                        None => {
+                            self.enter(dependency);
+                            calls.push((dependency, 0));
+                        }
+                        Some(index) if self.on_stack[dependency] => {
+                            self.low_link[node] = self.low_link[node].min(index);
+                        }
+                        Some(_) => {}
+                    }
+                }
+                None => {
+                    self.leave(node);
+                    if let Some(&(caller, _)) = calls.last() {
+                        self.low_link[caller] = self.low_link[caller].min(self.low_link[node]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn enter(&mut self, node: usize) {
+        self.index[node] = Some(self.next_index);
+        self.low_link[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+    }
+
+    /// Collects the component of `node` once all its dependencies were visited, if it is the
+    /// first node of the component that was entered.
+    fn leave(&mut self, node: usize) {
+        if Some(self.low_link[node]) == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Dependency {
     pub dependency_ref: String,
     pub dependencies: Vec<String>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn dependencies(edges: &[(&str, &[&str])]) -> Dependencies {
+        Dependencies(
+            edges
+                .iter()
+                .map(|(from, to)| Dependency {
+                    dependency_ref: from.to_string(),
+                    dependencies: to.iter().map(|to| to.to_string()).collect(),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn it_should_sort_a_diamond_graph() {
+        let dependencies = dependencies(&[
+            ("app", &["left", "right"]),
+            ("left", &["base"]),
+            ("right", &["base"]),
+            ("base", &[]),
+        ]);
+
+        assert_eq!(dependencies.find_cycles(), Vec::<Vec<String>>::new());
+        assert_eq!(
+            dependencies.topological_sort(),
+            Ok(vec![
+                "base".to_string(),
+                "left".to_string(),
+                "right".to_string(),
+                "app".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn it_should_find_a_self_loop() {
+        let dependencies = dependencies(&[("app", &["lib"]), ("lib", &["lib"])]);
+
+        assert_eq!(dependencies.find_cycles(), vec![vec!["lib".to_string()]]);
+        assert_eq!(
+            dependencies.topological_sort(),
+            Err(CycleError {
+                refs: vec!["lib".to_string()]
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_find_a_two_node_cycle() {
+        let dependencies =
+            dependencies(&[("app", &["a"]), ("a", &["b"]), ("b", &["a"]), ("c", &["a"])]);
+
+        assert_eq!(
+            dependencies.find_cycles(),
+            vec![vec!["a".to_string(), "b".to_string()]]
+        );
+        let error = dependencies.topological_sort().unwrap_err();
+        assert_eq!(error.to_string(), "Dependency cycle found between a, b");
+    }
+
+    #[test]
+    fn it_should_handle_a_long_dependency_chain() {
+        let refs = (0..100_000).map(|i| format!("ref-{i}")).collect::<Vec<_>>();
+        let dependencies = Dependencies(
+            refs.windows(2)
+                .map(|pair| Dependency {
+                    dependency_ref: pair[0].clone(),
+                    dependencies: vec![pair[1].clone()],
+                })
+                .collect(),
+        );
+
+        assert_eq!(dependencies.find_cycles(), Vec::<Vec<String>>::new());
+        let order = dependencies.topological_sort().unwrap();
+        assert!(order.iter().eq(refs.iter().rev()));
+    }
+}
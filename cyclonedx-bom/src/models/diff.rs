@@ -0,0 +1,432 @@
+/*
+ * This file is part of CycloneDX Rust Cargo.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::fmt;
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::models::bom::Bom;
+use crate::models::component::Component;
+
+/// The changes of components and dependencies between two BOMs, see [`Bom::diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BomDiff {
+    /// Components only present in the newer BOM.
+    pub added: Vec<Component>,
+    /// Components only present in the older BOM.
+    pub removed: Vec<Component>,
+    /// Components present in both BOMs with a different version, as `(old, new)` pairs.
+    pub version_changed: Vec<(Component, Component)>,
+    /// Dependency edges only present in the newer BOM, as `(dependent, dependency)` pairs.
+    ///
+    /// Both ends are identified like matched components, i.e. by their purl without version,
+    /// so upgrading a crate does not change its edges.
+    pub added_dependencies: Vec<(String, String)>,
+    /// Dependency edges only present in the older BOM, as `(dependent, dependency)` pairs.
+    pub removed_dependencies: Vec<(String, String)>,
+}
+
+impl BomDiff {
+    pub(crate) fn new(old: &Bom, new: &Bom) -> Self {
+        let old_components = index_components(old);
+        let mut new_components = index_components(new);
+
+        let mut diff = Self::default();
+        for (key, mut old_versions) in old_components {
+            let mut new_versions = new_components.shift_remove(&key).unwrap_or_default();
+            // Versions present in both BOMs are unchanged. The remaining ones are paired with
+            // the closest version, anything left over was added or removed.
+            old_versions.retain(|old_component| {
+                match new_versions
+                    .iter()
+                    .position(|new_component| new_component.version == old_component.version)
+                {
+                    Some(position) => {
+                        new_versions.remove(position);
+                        false
+                    }
+                    None => true,
+                }
+            });
+
+            let mut candidates: Vec<(usize, usize)> = (0..old_versions.len())
+                .flat_map(|old| (0..new_versions.len()).map(move |new| (old, new)))
+                .collect();
+            candidates
+                .sort_by_key(|&(old, new)| version_distance(old_versions[old], new_versions[new]));
+            let mut old_paired = vec![false; old_versions.len()];
+            let mut new_paired = vec![false; new_versions.len()];
+            let mut pairs = Vec::new();
+            for (old, new) in candidates {
+                if !old_paired[old] && !new_paired[new] {
+                    old_paired[old] = true;
+                    new_paired[new] = true;
+                    pairs.push((old, new));
+                }
+            }
+            pairs.sort_unstable();
+
+            diff.version_changed.extend(
+                pairs
+                    .into_iter()
+                    .map(|(old, new)| (old_versions[old].clone(), new_versions[new].clone())),
+            );
+            diff.removed.extend(
+                old_versions
+                    .iter()
+                    .zip(old_paired)
+                    .filter(|(_, paired)| !paired)
+                    .map(|(component, _)| (*component).clone()),
+            );
+            diff.added.extend(
+                new_versions
+                    .iter()
+                    .zip(new_paired)
+                    .filter(|(_, paired)| !paired)
+                    .map(|(component, _)| (*component).clone()),
+            );
+        }
+        diff.added
+            .extend(new_components.into_values().flatten().cloned());
+
+        let old_edges = dependency_edges(old);
+        let new_edges = dependency_edges(new);
+        diff.added_dependencies = new_edges.difference(&old_edges).cloned().collect();
+        diff.removed_dependencies = old_edges.difference(&new_edges).cloned().collect();
+
+        diff
+    }
+
+    /// Returns `true` if both BOMs have the same components and dependencies.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.version_changed.is_empty()
+            && self.added_dependencies.is_empty()
+            && self.removed_dependencies.is_empty()
+    }
+
+    /// Renders the diff as plain text with one line per change, suitable for CI comments.
+    ///
+    /// Added components and dependencies are prefixed with `+`, removed ones with `-` and
+    /// components whose version changed with `~`.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for component in &self.added {
+            text.push_str(&format!("+ added {}\n", label(component)));
+        }
+        for component in &self.removed {
+            text.push_str(&format!("- removed {}\n", label(component)));
+        }
+        for (old, new) in &self.version_changed {
+            text.push_str(&format!(
+                "~ {} {} -> {}\n",
+                name(new),
+                version(old),
+                version(new)
+            ));
+        }
+        for (dependent, dependency) in &self.added_dependencies {
+            text.push_str(&format!("+ dependency {dependent} -> {dependency}\n"));
+        }
+        for (dependent, dependency) in &self.removed_dependencies {
+            text.push_str(&format!("- dependency {dependent} -> {dependency}\n"));
+        }
+        text
+    }
+}
+
+/// Summarizes the number of changes, use [`BomDiff::to_text`] to list them.
+impl fmt::Display for BomDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no changes");
+        }
+        write!(
+            f,
+            "{} added, {} removed, {} version changed; {} added, {} removed",
+            count(self.added.len(), "component", "components"),
+            self.removed.len(),
+            self.version_changed.len(),
+            count(self.added_dependencies.len(), "dependency", "dependencies"),
+            self.removed_dependencies.len()
+        )
+    }
+}
+
+/// Indexes all components, including nested ones and the one described by the metadata, by
+/// their purl without its version, falling back to their group and name. Several versions of the
+/// same component share one entry.
+fn index_components(bom: &Bom) -> IndexMap<String, Vec<&Component>> {
+    let mut index: IndexMap<String, Vec<&Component>> = IndexMap::new();
+    for component in bom.all_components() {
+        index
+            .entry(component_key(component))
+            .or_default()
+            .push(component);
+    }
+    index
+}
+
+/// Collects the dependency edges of a BOM, identifying components by [`component_key`] and
+/// keeping refs without a component as they are.
+fn dependency_edges(bom: &Bom) -> IndexSet<(String, String)> {
+    let keys: IndexMap<&str, String> = bom
+        .all_components()
+        .filter_map(|component| {
+            let bom_ref = component.bom_ref.as_deref()?;
+            Some((bom_ref, component_key(component)))
+        })
+        .collect();
+    let key = |bom_ref: &str| keys.get(bom_ref).cloned().unwrap_or(bom_ref.to_string());
+
+    bom.dependencies
+        .iter()
+        .flat_map(|dependencies| &dependencies.0)
+        .flat_map(|dependency| {
+            dependency
+                .dependencies
+                .iter()
+                .map(|target| (key(&dependency.dependency_ref), key(target)))
+        })
+        .collect()
+}
+
+fn component_key(component: &Component) -> String {
+    match &component.purl {
+        Some(purl) => {
+            let purl = purl.canonical().to_string();
+            let end = purl.find(['?', '#']).unwrap_or(purl.len());
+            let (path, rest) = purl.split_at(end);
+            match path.rsplit_once('@') {
+                Some((unversioned, _)) => format!("{unversioned}{rest}"),
+                None => purl,
+            }
+        }
+        None => name(component),
+    }
+}
+
+/// How far apart two versions of a component are, for pairing up old and new versions.
+///
+/// Versions with the same major version are closest, versions that are not valid semver are the
+/// farthest apart.
+fn version_distance(old: &Component, new: &Component) -> (bool, u64, u64, u64) {
+    let parse =
+        |component: &Component| semver::Version::parse(component.version.as_ref()?.as_ref()).ok();
+    match (parse(old), parse(new)) {
+        (Some(old), Some(new)) => (
+            old.major != new.major,
+            old.major.abs_diff(new.major),
+            old.minor.abs_diff(new.minor),
+            old.patch.abs_diff(new.patch),
+        ),
+        _ => (true, u64::MAX, u64::MAX, u64::MAX),
+    }
+}
+
+fn count(count: usize, singular: &str, plural: &str) -> String {
+    match count {
+        1 => format!("1 {singular}"),
+        _ => format!("{count} {plural}"),
+    }
+}
+
+fn name(component: &Component) -> String {
+    match &component.group {
+        Some(group) => format!("{}/{}", group, component.name),
+        None => component.name.to_string(),
+    }
+}
+
+fn version(component: &Component) -> String {
+    component
+        .version
+        .as_ref()
+        .map(|version| version.to_string())
+        .unwrap_or_else(|| "(none)".to_string())
+}
+
+fn label(component: &Component) -> String {
+    match &component.version {
+        Some(version) => format!("{}@{}", name(component), version),
+        None => name(component),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::component::{Classification, Components};
+    use crate::models::dependency::{Dependencies, Dependency};
+    use crate::models::metadata::Metadata;
+    use crate::prelude::Purl;
+
+    fn bom(components: &[(&str, &str)]) -> Bom {
+        let components = components
+            .iter()
+            .map(|(name, version)| {
+                let bom_ref = format!("{name}@{version}");
+                let mut component =
+                    Component::new(Classification::Library, name, version, Some(bom_ref));
+                component.purl = Some(Purl::cargo(name, version).unwrap());
+                component
+            })
+            .collect();
+        Bom {
+            components: Some(Components(components)),
+            ..Bom::default()
+        }
+    }
+
+    fn with_dependencies(mut bom: Bom, edges: &[(&str, &str)]) -> Bom {
+        let dependencies = edges
+            .iter()
+            .map(|(dependent, dependency)| Dependency {
+                dependency_ref: dependent.to_string(),
+                dependencies: vec![dependency.to_string()],
+            })
+            .collect();
+        bom.dependencies = Some(Dependencies(dependencies));
+        bom
+    }
+
+    #[test]
+    fn it_should_diff_components_and_dependencies() {
+        let old = with_dependencies(
+            bom(&[("app", "1.0"), ("foo", "0.1"), ("bar", "1.0")]),
+            &[("app@1.0", "foo@0.1"), ("app@1.0", "bar@1.0")],
+        );
+        let new = with_dependencies(
+            bom(&[("app", "1.0"), ("bar", "1.1"), ("serde", "1.0")]),
+            &[("app@1.0", "bar@1.1"), ("app@1.0", "serde@1.0")],
+        );
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.added, bom(&[("serde", "1.0")]).components.unwrap().0);
+        assert_eq!(diff.removed, bom(&[("foo", "0.1")]).components.unwrap().0);
+        assert_eq!(diff.version_changed.len(), 1);
+        assert_eq!(diff.version_changed[0].0.version.as_deref(), Some("1.0"));
+        assert_eq!(diff.version_changed[0].1.version.as_deref(), Some("1.1"));
+        assert_eq!(
+            diff.added_dependencies,
+            [("pkg:cargo/app".to_string(), "pkg:cargo/serde".to_string())]
+        );
+        assert_eq!(
+            diff.removed_dependencies,
+            [("pkg:cargo/app".to_string(), "pkg:cargo/foo".to_string())]
+        );
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.to_string(),
+            "1 component added, 1 removed, 1 version changed; 1 dependency added, 1 removed"
+        );
+    }
+
+    #[test]
+    fn it_should_return_an_empty_diff_for_equal_boms() {
+        let old = with_dependencies(
+            bom(&[("app", "1.0"), ("foo", "0.1")]),
+            &[("app@1.0", "foo@0.1")],
+        );
+
+        let diff = old.diff(&old.clone());
+
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "no changes");
+        assert_eq!(diff.to_text(), "");
+    }
+
+    #[test]
+    fn it_should_render_a_diff_as_text() {
+        let old = bom(&[("foo", "0.1"), ("bar", "1.0"), ("baz", "2.0")]);
+        let new = bom(&[("bar", "1.1"), ("baz", "2.0"), ("serde", "1.0")]);
+
+        let text = old.diff(&new).to_text();
+
+        assert_eq!(
+            text,
+            "+ added serde@1.0\n- removed foo@0.1\n~ bar 1.0 -> 1.1\n"
+        );
+    }
+
+    #[test]
+    fn it_should_diff_the_metadata_component_like_other_components() {
+        let mut old = with_dependencies(bom(&[("foo", "0.1")]), &[("app@1.0", "foo@0.1")]);
+        let mut new = with_dependencies(bom(&[("foo", "0.1")]), &[("app@1.1", "foo@0.1")]);
+        old.metadata = Some(Metadata {
+            component: bom(&[("app", "1.0")]).components.unwrap().0.pop(),
+            ..Metadata::default()
+        });
+        new.metadata = Some(Metadata {
+            component: bom(&[("app", "1.1")]).components.unwrap().0.pop(),
+            ..Metadata::default()
+        });
+
+        let diff = old.diff(&new);
+
+        assert_eq!(diff.to_text(), "~ app 1.0 -> 1.1\n");
+    }
+
+    #[test]
+    fn it_should_diff_multiple_versions_of_a_crate() {
+        let old = bom(&[("syn", "1.0.109"), ("syn", "2.0.38"), ("bar", "1.0")]);
+        let new = bom(&[("syn", "2.0.39"), ("bar", "1.0"), ("bar", "2.0")]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.version_changed
+                .iter()
+                .map(|(old, new)| (label(old), label(new)))
+                .collect::<Vec<_>>(),
+            [("syn@2.0.38".to_string(), "syn@2.0.39".to_string())]
+        );
+        assert_eq!(
+            diff.removed,
+            bom(&[("syn", "1.0.109")]).components.unwrap().0
+        );
+        assert_eq!(diff.added, bom(&[("bar", "2.0")]).components.unwrap().0);
+    }
+
+    #[test]
+    fn it_should_keep_unchanged_versions_of_a_crate() {
+        let old = bom(&[("syn", "1.0.109"), ("syn", "2.0.38")]);
+        let new = bom(&[("syn", "2.0.38"), ("syn", "1.0.109")]);
+
+        assert!(old.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn it_should_pair_versions_of_a_crate_with_the_closest_version() {
+        let old = bom(&[("syn", "2.0.38"), ("syn", "1.0.100")]);
+        let new = bom(&[("syn", "1.0.109"), ("syn", "3.0.0"), ("syn", "2.0.39")]);
+
+        let diff = old.diff(&new);
+
+        assert_eq!(
+            diff.to_text(),
+            "+ added syn@3.0.0\n~ syn 2.0.38 -> 2.0.39\n~ syn 1.0.100 -> 1.0.109\n"
+        );
+        assert_eq!(
+            diff.to_string(),
+            "1 component added, 0 removed, 2 version changed; 0 dependencies added, 0 removed"
+        );
+    }
+}
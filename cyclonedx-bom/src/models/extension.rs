@@ -0,0 +1,154 @@
+/*
+ * This file is part of CycloneDX Rust Cargo.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use serde_json::Value;
+
+/// JSON fields outside of the CycloneDX schema, e.g. vendor-specific metadata, keyed by their
+/// field name.
+///
+/// These are kept when reading a JSON document and written back out as is, so that tools adding
+/// proprietary fields survive a round-trip through this library. They are not part of the XML
+/// format and are dropped when reading or writing XML.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Extensions(pub BTreeMap<String, Value>);
+
+impl Extensions {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Hash for Extensions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.0.len());
+        for (key, value) in &self.0 {
+            key.hash(state);
+            hash_value(value, state);
+        }
+    }
+}
+
+/// Hashes a JSON value consistently with its `PartialEq`, i.e. object members are hashed in key
+/// order regardless of the order the underlying map keeps them in.
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    std::mem::discriminant(value).hash(state);
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => b.hash(state),
+        Value::Number(n) => n.to_string().hash(state),
+        Value::String(s) => s.hash(state),
+        Value::Array(values) => {
+            state.write_usize(values.len());
+            values.iter().for_each(|v| hash_value(v, state));
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_unstable_by_key(|(key, _)| *key);
+            state.write_usize(members.len());
+            for (key, value) in members {
+                key.hash(state);
+                hash_value(value, state);
+            }
+        }
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Extensions {
+    fn from(other: BTreeMap<String, Value>) -> Self {
+        Self(other)
+    }
+}
+
+impl From<Extensions> for BTreeMap<String, Value> {
+    fn from(other: Extensions) -> Self {
+        other.0
+    }
+}
+
+/// An XML element outside of the CycloneDX schema, kept as a serialized fragment.
+///
+/// The schema allows elements from other namespaces at the end of the `bom` element, e.g. vendor
+/// extensions. These are kept when reading an XML document and written back out in the order they
+/// were read, with any namespaces they use declared on the `bom` element. They are not part of the
+/// JSON format and are dropped when reading or writing JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RawXml(pub String);
+
+impl RawXml {
+    pub fn new<T>(input: T) -> Self
+    where
+        T: ToString,
+    {
+        Self(input.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    use serde_json::{Map, Value};
+
+    use super::{hash_value, Extensions};
+
+    fn hash_of(value: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn object(members: &[(&str, Value)]) -> Value {
+        let mut map = Map::new();
+        for (key, value) in members {
+            map.insert(key.to_string(), value.clone());
+        }
+        Value::Object(map)
+    }
+
+    // serde_json only keeps the insertion order of object members with its `preserve_order`
+    // feature, which this crate does not enable. Without it both objects below are the same
+    // sorted map, so the member order is only covered when running this test with
+    // `--features serde_json/preserve_order`.
+    #[test]
+    fn it_should_hash_objects_independent_of_member_order() {
+        let ab = object(&[("a", Value::from(1)), ("b", Value::from(vec![true]))]);
+        let ba = object(&[("b", Value::from(vec![true])), ("a", Value::from(1))]);
+        assert_eq!(ab, ba);
+
+        let hash = |value: &Value| {
+            let mut hasher = DefaultHasher::new();
+            hash_value(value, &mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&ab), hash(&ba));
+
+        let extensions = |value: Value| Extensions([("x-vendor".to_string(), value)].into());
+        assert_eq!(
+            hash_of(&extensions(ab.clone())),
+            hash_of(&extensions(ba.clone()))
+        );
+        assert_ne!(
+            hash_of(&extensions(ab)),
+            hash_of(&extensions(object(&[("a", Value::from(2))])))
+        );
+    }
+}
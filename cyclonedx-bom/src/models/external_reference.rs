@@ -21,7 +21,9 @@ use regex::Regex;
 
 use crate::external_models::uri::{validate_uri as validate_url, Uri as Url};
 use crate::models::hash::Hashes;
-use crate::validation::{Validate, ValidationContext, ValidationError, ValidationResult};
+use crate::validation::{
+    validate_non_empty, Validate, ValidationContext, ValidationError, ValidationResult,
+};
 
 use super::bom::SpecVersion;
 
@@ -57,12 +59,12 @@ impl ExternalReference {
 
 impl Validate for ExternalReference {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
-        ValidationContext::new()
-            .add_field(
-                "external_reference_type",
-                &self.external_reference_type,
-                validate_external_reference_type,
-            )
+        let mut context = ValidationContext::new();
+        // Newer spec versions keep adding reference types, an unknown one is still usable
+        if let Err(warning) = validate_external_reference_type(&self.external_reference_type) {
+            context.add_warning("external_reference_type", warning);
+        }
+        context
             .add_field("url", &self.url, |uri| validate_reference_uri(uri, version))
             .add_list("hashes", &self.hashes, |hash| {
                 hash.validate_version(version)
@@ -75,12 +77,28 @@ impl Validate for ExternalReference {
 pub struct ExternalReferences(pub Vec<ExternalReference>);
 
 impl Validate for ExternalReferences {
+    /// Besides validating every reference, warns about references repeating the type and url of
+    /// an earlier one.
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
-        ValidationContext::new()
-            .add_list("inner", &self.0, |reference| {
-                reference.validate_version(version)
-            })
-            .into()
+        let mut context = ValidationContext::new();
+        context.add_list("inner", &self.0, |reference| {
+            reference.validate_version(version)
+        });
+
+        for (index, reference) in self.0.iter().enumerate() {
+            let duplicate = self.0[..index].iter().any(|earlier| {
+                earlier.external_reference_type == reference.external_reference_type
+                    && earlier.url == reference.url
+            });
+            if duplicate {
+                context.add_warning(
+                    &format!("inner[{index}]"),
+                    "Duplicate external reference with the same type and url",
+                );
+            }
+        }
+
+        context.into()
     }
 }
 
@@ -91,7 +109,9 @@ pub fn validate_external_reference_type(
         reference_type,
         ExternalReferenceType::UnknownExternalReferenceType(_)
     ) {
-        return Err("Unknown external reference type".into());
+        return Err(ValidationError::unknown_value(
+            "Unknown external reference type",
+        ));
     }
     Ok(())
 }
@@ -199,6 +219,8 @@ pub enum Uri {
 
 /// Validates an [`Uri`], the [`Uri::BomLink`] variant was added in 1.5 only.
 fn validate_reference_uri(uri: &Uri, version: SpecVersion) -> Result<(), ValidationError> {
+    validate_non_empty(&uri.to_string())?;
+
     match uri {
         Uri::Url(url) => validate_url(url),
         Uri::BomLink(bom_link) => validate_bom_link(bom_link, version),
@@ -228,6 +250,23 @@ impl std::fmt::Display for Uri {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct BomLink(pub String);
 
+impl BomLink {
+    /// Returns the serial number of the linked BOM without the `urn:uuid:` prefix.
+    pub fn serial_number(&self) -> Option<&str> {
+        let (serial_number, _) = self.0.strip_prefix("urn:cdx:")?.split_once('/')?;
+        Some(serial_number)
+    }
+
+    /// Returns the version of the linked BOM.
+    pub fn version(&self) -> Option<u32> {
+        let (_, version) = self.0.strip_prefix("urn:cdx:")?.split_once('/')?;
+        let version = version
+            .split_once('#')
+            .map_or(version, |(version, _)| version);
+        version.parse().ok()
+    }
+}
+
 fn validate_bom_link(bom_link: &BomLink, version: SpecVersion) -> Result<(), ValidationError> {
     if version < SpecVersion::V1_5 {
         return Err("BOM-Link not supported before version 1.5".into());
@@ -271,6 +310,20 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_should_parse_the_parts_of_a_bomlink() {
+        let link = BomLink("urn:cdx:f08a6ccd-4dce-4759-bd84-c626675d60a7/3#componentA".to_string());
+        assert_eq!(
+            link.serial_number(),
+            Some("f08a6ccd-4dce-4759-bd84-c626675d60a7")
+        );
+        assert_eq!(link.version(), Some(3));
+
+        let link = BomLink("https://example.com".to_string());
+        assert_eq!(link.serial_number(), None);
+        assert_eq!(link.version(), None);
+    }
+
     #[test]
     fn it_should_validate_external_reference_with_bomlink_correctly() {
         let url = Uri::BomLink(BomLink(
@@ -326,6 +379,30 @@ mod test {
         assert!(validation_result.passed());
     }
 
+    #[test]
+    fn it_should_require_a_url() {
+        let validation_result = ExternalReference {
+            external_reference_type: ExternalReferenceType::Website,
+            url: Uri::Url(Url(" ".to_string())),
+            comment: None,
+            hashes: None,
+        }
+        .validate();
+
+        assert_eq!(
+            validation_result,
+            validation::field("url", "Required string must not be empty")
+        );
+
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "externalReferences": [{ "type": "website" }]
+        }"#;
+        assert!(crate::models::bom::Bom::parse_from_json_v1_5(input.as_bytes()).is_err());
+    }
+
     #[test]
     fn it_should_fail_validation() {
         let validation_result = ExternalReferences(vec![
@@ -356,62 +433,120 @@ mod test {
 
         assert_eq!(
             validation_result,
-            validation::list(
-                "inner",
-                [
-                    (
-                        0,
-                        vec![
-                            validation::field(
-                                "external_reference_type",
-                                "Unknown external reference type"
-                            ),
-                            validation::field("url", "Uri does not conform to RFC 3986"),
-                            validation::list(
-                                "hashes",
-                                [(
-                                    0,
-                                    validation::list(
-                                        "inner",
-                                        [(
-                                            0,
-                                            validation::field(
-                                                "content",
-                                                "HashValue does not match regular expression"
-                                            )
-                                        )]
-                                    )
-                                )]
-                            )
-                        ]
-                    ),
-                    (
-                        1,
-                        vec![
-                            validation::field(
-                                "external_reference_type",
-                                "Unknown external reference type"
-                            ),
-                            validation::field("url", "Invalid BOM-Link"),
-                            validation::list(
-                                "hashes",
-                                [(
-                                    0,
-                                    validation::list(
-                                        "inner",
-                                        [(
-                                            0,
-                                            validation::field(
-                                                "content",
-                                                "HashValue does not match regular expression"
-                                            )
-                                        )]
-                                    )
-                                )]
-                            )
-                        ]
-                    )
-                ]
+            vec![
+                validation::list(
+                    "inner",
+                    [
+                        (
+                            0,
+                            vec![
+                                validation::field("url", "Uri does not conform to RFC 3986"),
+                                validation::list(
+                                    "hashes",
+                                    [(
+                                        0,
+                                        validation::list(
+                                            "inner",
+                                            [(
+                                                0,
+                                                validation::field(
+                                                    "content",
+                                                    "HashValue does not match regular expression"
+                                                )
+                                            )]
+                                        )
+                                    )]
+                                )
+                            ]
+                        ),
+                        (
+                            1,
+                            vec![
+                                validation::field("url", "Invalid BOM-Link"),
+                                validation::list(
+                                    "hashes",
+                                    [(
+                                        0,
+                                        validation::list(
+                                            "inner",
+                                            [(
+                                                0,
+                                                validation::field(
+                                                    "content",
+                                                    "HashValue does not match regular expression"
+                                                )
+                                            )]
+                                        )
+                                    )]
+                                )
+                            ]
+                        )
+                    ]
+                ),
+                validation::warning(
+                    "inner[0].external_reference_type",
+                    [ValidationError::unknown_value(
+                        "Unknown external reference type"
+                    )]
+                ),
+                validation::warning(
+                    "inner[1].external_reference_type",
+                    [ValidationError::unknown_value(
+                        "Unknown external reference type"
+                    )]
+                ),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn it_should_warn_about_unknown_reference_types() {
+        let mut reference = ExternalReference::new(
+            ExternalReferenceType::Vcs,
+            Url("https://github.com/CycloneDX/cyclonedx-rust-cargo".to_string()),
+        );
+        let result = reference.validate();
+        assert!(result.passed());
+        assert!(!result.has_warnings());
+
+        reference.external_reference_type = ExternalReferenceType::new_unchecked("chat-bot");
+        let result = reference.validate();
+        assert!(result.passed());
+        assert_eq!(
+            result,
+            validation::warning(
+                "external_reference_type",
+                [ValidationError::unknown_value(
+                    "Unknown external reference type"
+                )]
+            )
+        );
+
+        reference.url = Uri::Url(Url("ht!tp://example.com".to_string()));
+        assert!(reference.validate().has_error("url"));
+    }
+
+    #[test]
+    fn it_should_warn_about_duplicate_external_references() {
+        let reference = |reference_type, url: &str| {
+            ExternalReference::new(reference_type, Url(url.to_string()))
+        };
+        let references = ExternalReferences(vec![
+            reference(ExternalReferenceType::Website, "https://example.com"),
+            reference(ExternalReferenceType::Vcs, "https://example.com"),
+            reference(ExternalReferenceType::Website, "https://example.com/docs"),
+            reference(ExternalReferenceType::Website, "https://example.com"),
+        ]);
+
+        let result = references.validate();
+
+        assert!(result.passed());
+        assert_eq!(
+            result,
+            validation::warning(
+                "inner[3]",
+                ["Duplicate external reference with the same type and url"]
             )
         );
     }
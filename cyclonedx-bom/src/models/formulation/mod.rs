@@ -1,5 +1,7 @@
 pub mod workflow;
 
+use std::collections::HashSet;
+
 use crate::{
     prelude::{SpecVersion, Validate, ValidationResult},
     validation::{ValidationContext, ValidationError},
@@ -7,7 +9,13 @@ use crate::{
 
 use self::workflow::Workflow;
 
-use super::{bom::BomReference, component::Components, property::Properties, service::Services};
+use super::{
+    bom::BomReference,
+    component::{Component, Components},
+    dependency::Dependency,
+    property::Properties,
+    service::{Service, Services},
+};
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Formula {
@@ -25,7 +33,8 @@ impl Validate for Formula {
                 "Formula is not defined for version {version}"
             )))
             .into(),
-            SpecVersion::V1_5 => ValidationContext::new()
+            SpecVersion::V1_5 => self
+                .validate_references(ValidationContext::new())
                 .add_unique_list_option(
                     "components", // components is uniqueItems: true
                     self.components.as_ref().map(|wrapper| wrapper.0.iter()),
@@ -46,3 +55,94 @@ impl Validate for Formula {
         }
     }
 }
+
+impl Formula {
+    /// Warns about task dependencies that do not refer to tasks of their workflow and runtime
+    /// topologies that do not refer to components, services, workflows or tasks of the formula.
+    ///
+    /// These are warnings, as the examples of the specification itself contain such references.
+    fn validate_references(&self, mut context: ValidationContext) -> ValidationContext {
+        fn collect_components<'a>(components: &'a [Component], refs: &mut HashSet<&'a str>) {
+            for component in components {
+                refs.extend(component.bom_ref.as_deref());
+                if let Some(components) = &component.components {
+                    collect_components(&components.0, refs);
+                }
+            }
+        }
+        fn collect_services<'a>(services: &'a [Service], refs: &mut HashSet<&'a str>) {
+            for service in services {
+                refs.extend(service.bom_ref.as_deref());
+                if let Some(services) = &service.services {
+                    collect_services(&services.0, refs);
+                }
+            }
+        }
+        fn dependency_refs(dependencies: &Option<Vec<Dependency>>) -> impl Iterator<Item = &str> {
+            dependencies.iter().flatten().flat_map(|dependency| {
+                std::iter::once(&dependency.dependency_ref)
+                    .chain(&dependency.dependencies)
+                    .map(String::as_str)
+            })
+        }
+
+        let mut element_refs = HashSet::new();
+        if let Some(components) = &self.components {
+            collect_components(&components.0, &mut element_refs);
+        }
+        if let Some(services) = &self.services {
+            collect_services(&services.0, &mut element_refs);
+        }
+
+        for workflow in self.workflows.iter().flatten() {
+            element_refs.insert(workflow.bom_ref.0.as_str());
+            for task in workflow.tasks.iter().flatten() {
+                element_refs.insert(task.bom_ref.0.as_str());
+            }
+        }
+
+        for (index, workflow) in self.workflows.iter().flatten().enumerate() {
+            let path = format!("workflows[{index}]");
+            let task_refs: HashSet<&str> = workflow
+                .tasks
+                .iter()
+                .flatten()
+                .map(|task| task.bom_ref.0.as_str())
+                .collect();
+            for task_ref in dependency_refs(&workflow.task_dependencies) {
+                if !task_refs.contains(task_ref) {
+                    context.add_warning(
+                        &format!("{path}.task_dependencies"),
+                        format!("Task dependency ref '{task_ref}' does not exist in the workflow"),
+                    );
+                }
+            }
+
+            let runtime_topologies = std::iter::once((path.clone(), &workflow.runtime_topology))
+                .chain(
+                    workflow
+                        .tasks
+                        .iter()
+                        .flatten()
+                        .enumerate()
+                        .map(|(index, task)| {
+                            (format!("{path}.tasks[{index}]"), &task.runtime_topology)
+                        }),
+                );
+            for (path, runtime_topology) in runtime_topologies {
+                for element_ref in dependency_refs(runtime_topology) {
+                    if !element_refs.contains(element_ref) {
+                        context.add_warning(
+                            &format!("{path}.runtime_topology"),
+                            format!(
+                                "Runtime topology ref '{element_ref}' does not exist in the formula"
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        context
+    }
+}
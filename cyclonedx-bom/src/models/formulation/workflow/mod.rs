@@ -9,7 +9,7 @@ use crate::{
     external_models::validate_date_time,
     models::{bom::BomReference, dependency::Dependency, property::Properties},
     prelude::{DateTime, Validate, ValidationResult},
-    validation::{ValidationContext, ValidationError},
+    validation::{validate_non_empty, ValidationContext, ValidationError},
 };
 
 use self::{
@@ -44,6 +44,7 @@ impl Validate for Workflow {
         version: crate::prelude::SpecVersion,
     ) -> crate::prelude::ValidationResult {
         ValidationContext::new()
+            .add_field("uid", self.uid.as_str(), validate_non_empty)
             .add_list_option(
                 "resource_references",
                 self.resource_references.as_ref(),
@@ -55,6 +56,11 @@ impl Validate for Workflow {
             .add_unique_list_option("task_dependencies", self.task_dependencies.as_ref(), |_| {
                 ValidationResult::new()
             })
+            .add_field(
+                "task_types",
+                self.task_types.as_slice(),
+                validate_task_types,
+            )
             .add_list("task_types", &self.task_types, |task_type| {
                 task_type.validate_version(version)
             })
@@ -105,11 +111,17 @@ impl Validate for Task {
         version: crate::prelude::SpecVersion,
     ) -> crate::prelude::ValidationResult {
         ValidationContext::new()
+            .add_field("uid", self.uid.as_str(), validate_non_empty)
             .add_list_option(
                 "resource_references",
                 self.resource_references.as_ref(),
                 |resource_reference| resource_reference.validate_version(version),
             )
+            .add_field(
+                "task_types",
+                self.task_types.as_slice(),
+                validate_task_types,
+            )
             .add_list("task_types", &self.task_types, |task_type| {
                 task_type.validate_version(version)
             })
@@ -135,6 +147,14 @@ impl Validate for Task {
     }
 }
 
+/// The schema requires at least one task type for workflows and tasks.
+fn validate_task_types(task_types: &[TaskType]) -> Result<(), ValidationError> {
+    if task_types.is_empty() {
+        return Err(ValidationError::new("At least one task type is required"));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, strum::Display)]
 #[strum(serialize_all = "kebab-case")]
 pub enum TaskType {
@@ -6,7 +6,7 @@ use crate::{
         property::Properties,
     },
     prelude::{DateTime, Validate},
-    validation::{ValidationContext, ValidationError},
+    validation::{validate_non_empty, ValidationContext, ValidationError},
 };
 
 use super::{input::Input, output::Output, resource_reference::ResourceReference};
@@ -36,6 +36,7 @@ impl Validate for Trigger {
             .add_field("bom_ref", &self.bom_ref, |bom_ref| {
                 validate_bom_ref(bom_ref, version)
             })
+            .add_field("uid", self.uid.as_str(), validate_non_empty)
             .add_list_option(
                 "resource_reference",
                 self.resource_references.as_ref(),
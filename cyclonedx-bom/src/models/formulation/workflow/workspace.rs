@@ -1,7 +1,9 @@
+use std::collections::HashSet;
+
 use crate::{
     models::{bom::BomReference, property::Properties},
     prelude::{SpecVersion, Validate, ValidationResult},
-    validation::{ValidationContext, ValidationError},
+    validation::{validate_non_empty, ValidationContext, ValidationError},
 };
 
 use super::resource_reference::ResourceReference;
@@ -22,9 +24,23 @@ pub struct Workspace {
     pub properties: Option<Properties>,
 }
 
+impl Workspace {
+    /// Removes blank and repeated aliases, keeping the first occurrence of every alias.
+    pub fn dedup_aliases(&mut self) {
+        if let Some(aliases) = &mut self.aliases {
+            let mut seen = HashSet::new();
+            aliases.retain(|alias| !alias.trim().is_empty() && seen.insert(alias.clone()));
+        }
+    }
+}
+
 impl Validate for Workspace {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
         ValidationContext::new()
+            .add_field("uid", self.uid.as_str(), validate_non_empty)
+            .add_unique_list_option("aliases", self.aliases.as_ref(), |alias| {
+                validate_non_empty(alias)
+            })
             .add_unique_list_option(
                 "resource_references",
                 self.resource_references.as_ref(),
@@ -67,7 +83,9 @@ impl AccessMode {
 
 pub fn validate_access_mode(access_mode: &AccessMode) -> Result<(), ValidationError> {
     match access_mode {
-        AccessMode::UnknownAccessMode(_) => Err(ValidationError::new("Unknown access mode")),
+        AccessMode::UnknownAccessMode(_) => {
+            Err(ValidationError::unknown_value("Unknown access mode"))
+        }
         _ => Ok(()),
     }
 }
@@ -115,7 +133,63 @@ impl Mode {
 
 pub fn validate_mode(mode: &Mode) -> Result<(), ValidationError> {
     match mode {
-        Mode::UnknownMode(_) => Err(ValidationError::new("Unknown mode")),
+        Mode::UnknownMode(_) => Err(ValidationError::unknown_value("Unknown mode")),
         _ => Ok(()),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::validation;
+
+    fn workspace(aliases: &[&str]) -> Workspace {
+        Workspace {
+            bom_ref: BomReference::new("workspace-1"),
+            uid: "workspace-1".to_string(),
+            name: None,
+            aliases: Some(aliases.iter().map(|alias| alias.to_string()).collect()),
+            description: None,
+            resource_references: None,
+            access_mode: None,
+            mount_path: None,
+            managed_data_type: None,
+            volume_request: None,
+            volume: None,
+            properties: None,
+        }
+    }
+
+    #[test]
+    fn it_should_fail_validation_for_blank_and_repeated_aliases() {
+        let workspace = workspace(&["source", " ", "source"]);
+
+        assert_eq!(
+            workspace.validate(),
+            validation::list(
+                "aliases",
+                [
+                    (
+                        1,
+                        validation::custom("", ["Required string must not be empty"])
+                    ),
+                    (2, validation::custom("", ["repeated element"])),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn it_should_dedup_aliases() {
+        let mut workspace = workspace(&["source", " ", "build", "source"]);
+        workspace.dedup_aliases();
+
+        assert_eq!(
+            workspace.aliases,
+            Some(vec!["source".to_string(), "build".to_string()])
+        );
+        assert!(workspace.validate().passed());
+    }
+}
@@ -41,6 +41,52 @@ impl Validate for Hash {
     }
 }
 
+#[cfg(feature = "hashes")]
+impl Hash {
+    /// Computes the hash of the file at `path` as lowercase hex string, requires the `hashes`
+    /// feature.
+    ///
+    /// Supports MD5, SHA-1, SHA-256, SHA-384 and SHA-512, other algorithms fail with
+    /// [`std::io::ErrorKind::Unsupported`].
+    pub fn from_file(
+        path: impl AsRef<std::path::Path>,
+        algorithm: HashAlgorithm,
+    ) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let content = match algorithm {
+            HashAlgorithm::MD5 => hex_digest::<md5::Md5>(file)?,
+            HashAlgorithm::SHA1 => hex_digest::<sha1::Sha1>(file)?,
+            HashAlgorithm::SHA_256 => hex_digest::<sha2::Sha256>(file)?,
+            HashAlgorithm::SHA_384 => hex_digest::<sha2::Sha384>(file)?,
+            HashAlgorithm::SHA_512 => hex_digest::<sha2::Sha512>(file)?,
+            unsupported => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Unsupported,
+                    format!("Hash algorithm {unsupported} is not supported"),
+                ))
+            }
+        };
+
+        Ok(Self {
+            alg: algorithm,
+            content: HashValue(content),
+        })
+    }
+}
+
+#[cfg(feature = "hashes")]
+fn hex_digest<D: sha2::Digest + std::io::Write>(
+    mut reader: impl std::io::Read,
+) -> std::io::Result<String> {
+    let mut hasher = D::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Hashes(pub Vec<Hash>);
 
@@ -54,7 +100,7 @@ impl Validate for Hashes {
 
 pub fn validate_hash_algorithm(algorithm: &HashAlgorithm) -> Result<(), ValidationError> {
     if matches!(algorithm, HashAlgorithm::UnknownHashAlgorithm(_)) {
-        return Err(ValidationError::new("Unknown HashAlgorithm"));
+        return Err(ValidationError::unknown_value("Unknown HashAlgorithm"));
     }
     Ok(())
 }
@@ -133,6 +179,35 @@ mod test {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[cfg(feature = "hashes")]
+    #[test]
+    fn it_should_compute_the_hash_of_a_file() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/hash_input.txt");
+
+        let hash = Hash::from_file(path, HashAlgorithm::SHA_256).unwrap();
+
+        assert_eq!(
+            hash,
+            Hash {
+                alg: HashAlgorithm::SHA_256,
+                content: HashValue(
+                    "c03905fcdab297513a620ec81ed46ca44ddb62d41cbbd83eb4a5a3592be26a69".to_string()
+                ),
+            }
+        );
+        assert!(hash.validate().passed());
+        assert_eq!(
+            Hash::from_file(path, HashAlgorithm::MD5).unwrap().content,
+            HashValue("37c4b87edffc5d198ff5a185cee7ee09".to_string())
+        );
+        assert_eq!(
+            Hash::from_file(path, HashAlgorithm::BLAKE3)
+                .unwrap_err()
+                .kind(),
+            std::io::ErrorKind::Unsupported
+        );
+    }
+
     #[test]
     fn it_should_pass_validation() {
         let validation_result = Hashes(vec![Hash {
@@ -144,6 +219,38 @@ mod test {
         assert!(validation_result.passed());
     }
 
+    #[test]
+    fn it_should_accept_all_known_algorithms_in_every_version() {
+        // All supported spec versions share the same list of hash algorithms, BLAKE and SHA3
+        // included, see the `hash-alg` definition of the JSON schemas.
+        let hashes = Hashes(
+            [
+                HashAlgorithm::MD5,
+                HashAlgorithm::SHA1,
+                HashAlgorithm::SHA_256,
+                HashAlgorithm::SHA_384,
+                HashAlgorithm::SHA_512,
+                HashAlgorithm::SHA3_256,
+                HashAlgorithm::SHA3_384,
+                HashAlgorithm::SHA3_512,
+                HashAlgorithm::BLAKE2b_256,
+                HashAlgorithm::BLAKE2b_384,
+                HashAlgorithm::BLAKE2b_512,
+                HashAlgorithm::BLAKE3,
+            ]
+            .into_iter()
+            .map(|alg| Hash {
+                alg,
+                content: HashValue("a3bf1f3d584747e2569483783ddee45b".to_string()),
+            })
+            .collect(),
+        );
+
+        for version in [SpecVersion::V1_3, SpecVersion::V1_4, SpecVersion::V1_5] {
+            assert!(hashes.validate_version(version).passed());
+        }
+    }
+
     #[test]
     fn it_should_fail_validation() {
         let validation_result = Hashes(vec![Hash {
@@ -159,7 +266,10 @@ mod test {
                 [(
                     0,
                     vec![
-                        validation::field("alg", "Unknown HashAlgorithm"),
+                        validation::field(
+                            "alg",
+                            ValidationError::unknown_value("Unknown HashAlgorithm")
+                        ),
                         validation::field("content", "HashValue does not match regular expression")
                     ]
                 )]
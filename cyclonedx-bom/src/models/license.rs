@@ -16,6 +16,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use std::collections::HashSet;
+
 use crate::external_models::normalized_string::validate_normalized_string;
 use crate::external_models::spdx::{validate_spdx_expression, validate_spdx_identifier};
 use crate::external_models::uri::validate_uri;
@@ -143,6 +145,61 @@ impl Validate for License {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Licenses(pub Vec<LicenseChoice>);
 
+impl Licenses {
+    /// Replaces named licenses by their SPDX identifier where the name maps to exactly one, e.g.
+    /// `Apache 2.0` becomes `Apache-2.0`, and removes repeated licenses afterwards.
+    ///
+    /// Names are only replaced if they are an SPDX identifier or one of a few well-known
+    /// spellings of one. Names that leave the version or variant open, e.g. `BSD` or `GPL`, are
+    /// kept as they are.
+    pub fn normalize(&mut self) {
+        for choice in &mut self.0 {
+            if let LicenseChoice::License(license) = choice {
+                if let LicenseIdentifier::Name(name) = &license.license_identifier {
+                    if let Some(identifier) = unambiguous_spdx_identifier(name.as_ref()) {
+                        license.license_identifier = LicenseIdentifier::SpdxId(identifier);
+                    }
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        self.0.retain(|choice| seen.insert(choice.clone()));
+    }
+}
+
+/// Well-known license names mapped to the SPDX identifier they stand for, in lowercase.
+const LICENSE_ALIASES: &[(&str, &str)] = &[
+    ("apache 2", "Apache-2.0"),
+    ("apache 2.0", "Apache-2.0"),
+    ("apache license 2.0", "Apache-2.0"),
+    ("apache license, version 2.0", "Apache-2.0"),
+    ("apache-2", "Apache-2.0"),
+    ("apache2", "Apache-2.0"),
+    ("bsd 2-clause", "BSD-2-Clause"),
+    ("bsd 3-clause", "BSD-3-Clause"),
+    ("cc0", "CC0-1.0"),
+    ("mit license", "MIT"),
+    ("mpl 2.0", "MPL-2.0"),
+    ("mpl2", "MPL-2.0"),
+    ("the mit license", "MIT"),
+    ("zlib", "Zlib"),
+];
+
+/// Returns the SPDX identifier `name` unambiguously stands for, if any.
+fn unambiguous_spdx_identifier(name: &str) -> Option<SpdxIdentifier> {
+    let name = name.trim();
+    if let Some(license) = spdx::license_id(name) {
+        return Some(SpdxIdentifier(license.name.to_string()));
+    }
+
+    let name = name.to_lowercase();
+    LICENSE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map(|(_, identifier)| SpdxIdentifier(identifier.to_string()))
+}
+
 impl Validate for Licenses {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
         let mut context = ValidationContext::new();
@@ -254,7 +311,10 @@ impl Validate for LicenseContact {
 
 fn validate_license_type(license_type: &LicenseType) -> Result<(), ValidationError> {
     if let LicenseType::Unknown(unknown) = license_type {
-        return Err(format!("Unknown license type '{}'", unknown).into());
+        return Err(ValidationError::unknown_value(format!(
+            "Unknown license type '{}'",
+            unknown
+        )));
     }
 
     Ok(())
@@ -393,14 +453,16 @@ mod test {
         ))])
         .validate();
 
+        let incomplete_expression = concat!(
+            "SPDX expression is not valid at position 6: ",
+            "expected one of `<license>`, `(` here"
+        );
+
         assert_eq!(
             validation_result,
             validation::list(
                 "inner",
-                [(
-                    0,
-                    validation::r#enum("expression", "SPDX expression is not valid")
-                )],
+                [(0, validation::r#enum("expression", incomplete_expression))],
             )
         );
     }
@@ -478,19 +540,18 @@ mod test {
         ])
         .validate();
 
+        let incomplete_expression = concat!(
+            "SPDX expression is not valid at position 6: ",
+            "expected one of `<license>`, `(` here"
+        );
+
         assert_eq!(
             validation_result,
             validation::list(
                 "inner",
                 [
-                    (
-                        1,
-                        validation::r#enum("expression", "SPDX expression is not valid"),
-                    ),
-                    (
-                        2,
-                        validation::r#enum("expression", "SPDX expression is not valid"),
-                    )
+                    (1, validation::r#enum("expression", incomplete_expression)),
+                    (2, validation::r#enum("expression", incomplete_expression)),
                 ]
             )
         );
@@ -526,4 +587,35 @@ mod test {
             validation::custom("licenses", ["More than one 'expression' entry found."])
         );
     }
+
+    #[test]
+    fn it_should_only_normalize_unambiguous_license_names() {
+        let normalized = |name: &str| {
+            let mut licenses = Licenses(vec![LicenseChoice::license(name)]);
+            licenses.normalize();
+            match &licenses.0[0] {
+                LicenseChoice::License(license) => license.license_identifier.clone(),
+                LicenseChoice::Expression(_) => unreachable!(),
+            }
+        };
+        let spdx_id = |id: &str| LicenseIdentifier::SpdxId(SpdxIdentifier(id.to_string()));
+        let name = |name: &str| LicenseIdentifier::Name(NormalizedString::new(name));
+
+        assert_eq!(normalized("Apache 2.0"), spdx_id("Apache-2.0"));
+        assert_eq!(normalized(" BSD 3-Clause "), spdx_id("BSD-3-Clause"));
+        assert_eq!(
+            normalized("LGPL-3.0-or-later"),
+            spdx_id("LGPL-3.0-or-later")
+        );
+
+        for ambiguous in [
+            "Apache License 1.1",
+            "Mitre Public License",
+            "GPL v3 or later",
+            "BSD",
+            "GPL",
+        ] {
+            assert_eq!(normalized(ambiguous), name(ambiguous));
+        }
+    }
 }
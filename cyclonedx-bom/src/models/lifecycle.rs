@@ -16,18 +16,58 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::prelude::NormalizedString;
+use crate::{
+    external_models::normalized_string::validate_normalized_string,
+    models::bom::SpecVersion,
+    prelude::{NormalizedString, Validate, ValidationResult},
+    validation::{ValidationContext, ValidationError},
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Lifecycles(pub Vec<Lifecycle>);
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl Validate for Lifecycles {
+    fn validate_version(&self, version: SpecVersion) -> ValidationResult {
+        if version <= SpecVersion::V1_4 {
+            return Err(ValidationError::new(format!(
+                "Lifecycles are not defined for version {version}"
+            )))
+            .into();
+        }
+
+        ValidationContext::new()
+            .add_unique_list("inner", &self.0, |lifecycle| {
+                lifecycle.validate_version(version)
+            })
+            .into()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Lifecycle {
     Phase(Phase),
     Description(Description),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl Validate for Lifecycle {
+    fn validate_version(&self, _version: SpecVersion) -> ValidationResult {
+        match self {
+            Lifecycle::Phase(phase) => ValidationContext::new()
+                .add_enum("phase", phase, validate_phase)
+                .into(),
+            Lifecycle::Description(description) => ValidationContext::new()
+                .add_field("name", &description.name, validate_normalized_string)
+                .add_field_option(
+                    "description",
+                    description.description.as_ref(),
+                    validate_normalized_string,
+                )
+                .into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Phase {
     Design,
     PreBuild,
@@ -71,8 +111,15 @@ impl Phase {
     }
 }
 
+pub fn validate_phase(phase: &Phase) -> Result<(), ValidationError> {
+    match phase {
+        Phase::Unknown(_) => Err(ValidationError::unknown_value("Unknown lifecycle phase")),
+        _ => Ok(()),
+    }
+}
+
 /// A description of a `Lifecycle`.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Description {
     pub name: NormalizedString,
     pub description: Option<NormalizedString>,
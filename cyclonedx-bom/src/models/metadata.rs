@@ -21,10 +21,10 @@ use thiserror::Error;
 use crate::external_models::date_time::{DateTime, DateTimeError};
 use crate::external_models::validate_date_time;
 use crate::models::component::Component;
-use crate::models::license::Licenses;
+use crate::models::license::{LicenseChoice, Licenses};
 use crate::models::lifecycle::Lifecycles;
 use crate::models::organization::{OrganizationalContact, OrganizationalEntity};
-use crate::models::property::Properties;
+use crate::models::property::{Properties, Property};
 use crate::models::tool::Tools;
 use crate::validation::{Validate, ValidationContext, ValidationResult};
 
@@ -69,6 +69,92 @@ impl Metadata {
     }
 }
 
+/// Builds [`Metadata`] fluently, leaving every field not set explicitly empty.
+/// ```
+/// use cyclonedx_bom::models::component::{Classification, ComponentBuilder};
+/// use cyclonedx_bom::models::metadata::MetadataBuilder;
+/// use cyclonedx_bom::models::property::Property;
+///
+/// let metadata = MetadataBuilder::new()
+///     .component(ComponentBuilder::new(Classification::Application, "app").build())
+///     .add_property(Property::new("cdx:rustc:sbom:target:all_targets", "true"))
+///     .build();
+///
+/// assert!(metadata.timestamp.is_none());
+/// assert_eq!(metadata.component.unwrap().name.to_string(), "app");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MetadataBuilder {
+    metadata: Metadata,
+}
+
+impl MetadataBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn timestamp(mut self, timestamp: DateTime) -> Self {
+        self.metadata.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn tools(mut self, tools: Tools) -> Self {
+        self.metadata.tools = Some(tools);
+        self
+    }
+
+    pub fn add_author(mut self, author: OrganizationalContact) -> Self {
+        self.metadata
+            .authors
+            .get_or_insert_with(Vec::new)
+            .push(author);
+        self
+    }
+
+    pub fn component(mut self, component: Component) -> Self {
+        self.metadata.component = Some(component);
+        self
+    }
+
+    pub fn manufacture(mut self, manufacture: OrganizationalEntity) -> Self {
+        self.metadata.manufacture = Some(manufacture);
+        self
+    }
+
+    pub fn supplier(mut self, supplier: OrganizationalEntity) -> Self {
+        self.metadata.supplier = Some(supplier);
+        self
+    }
+
+    pub fn add_license(mut self, license: LicenseChoice) -> Self {
+        self.metadata
+            .licenses
+            .get_or_insert_with(|| Licenses(Vec::new()))
+            .0
+            .push(license);
+        self
+    }
+
+    pub fn add_property(mut self, property: Property) -> Self {
+        self.metadata
+            .properties
+            .get_or_insert_with(|| Properties(Vec::new()))
+            .0
+            .push(property);
+        self
+    }
+
+    /// Lifecycles were added in version 1.5.
+    pub fn lifecycles(mut self, lifecycles: Lifecycles) -> Self {
+        self.metadata.lifecycles = Some(lifecycles);
+        self
+    }
+
+    pub fn build(self) -> Metadata {
+        self.metadata
+    }
+}
+
 impl Validate for Metadata {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
         ValidationContext::new()
@@ -88,6 +174,7 @@ impl Validate for Metadata {
             .add_list("properties", self.properties.as_ref(), |property| {
                 property.validate_version(version)
             })
+            .add_struct_option("lifecycles", self.lifecycles.as_ref(), version)
             .into()
     }
 }
@@ -103,14 +190,14 @@ mod test {
     use crate::{
         external_models::{normalized_string::NormalizedString, spdx::SpdxExpression},
         models::{
-            bom::BomReference,
+            bom::{Bom, BomReference},
             component::Classification,
             license::LicenseChoice,
             lifecycle::{Description, Lifecycle, Phase},
             property::Property,
             tool::Tool,
         },
-        validation,
+        validation::{self, ValidationError},
     };
 
     use super::*;
@@ -160,6 +247,8 @@ mod test {
                 signature: None,
                 model_card: None,
                 data: None,
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             }),
             manufacture: Some(OrganizationalEntity {
                 bom_ref: Some(BomReference::new("Manufacturer")),
@@ -182,7 +271,7 @@ mod test {
             }])),
             lifecycles: Some(Lifecycles(vec![Lifecycle::Phase(Phase::Build)])),
         }
-        .validate();
+        .validate_version(SpecVersion::V1_5);
 
         assert!(validation_result.passed());
     }
@@ -231,6 +320,8 @@ mod test {
                 signature: None,
                 model_card: None,
                 data: None,
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             }),
             manufacture: Some(OrganizationalEntity {
                 bom_ref: Some(BomReference::new("Manufacturer")),
@@ -256,7 +347,9 @@ mod test {
                 description: Some(NormalizedString("invalid\tvalue".to_string())),
             })])),
         }
-        .validate();
+        .validate_version(SpecVersion::V1_5);
+
+        let unknown_term = "SPDX expression is not valid at position 0: unknown term";
 
         assert_eq!(
             validation_result,
@@ -290,7 +383,14 @@ mod test {
                 ),
                 validation::r#struct(
                     "component",
-                    validation::field("component_type", "Unknown classification")
+                    validation::field(
+                        "component_type",
+                        ValidationError::unknown_value("Unknown classification")
+                    )
+                ),
+                validation::warning(
+                    "component",
+                    ["Component has no unique identifier, expected one of purl, cpe or swid"]
                 ),
                 validation::r#struct(
                     "manufacture",
@@ -314,7 +414,7 @@ mod test {
                             "inner",
                             [(
                                 0,
-                                validation::r#enum("expression", "SPDX expression is not valid")
+                                validation::r#enum("expression", unknown_term)
                             )]
                         )
                     )]
@@ -334,9 +434,102 @@ mod test {
                             )]
                         )
                     )]
+                ),
+                validation::r#struct(
+                    "lifecycles",
+                    validation::list(
+                        "inner",
+                        [(
+                            0,
+                            validation::field(
+                                "description",
+                                "NormalizedString contains invalid characters \\r \\n \\t or \\r\\n"
+                            )
+                        )]
+                    )
                 )
             ]
             .into()
         );
     }
+
+    #[test]
+    fn it_should_fail_validation_for_lifecycles_before_version_1_5() {
+        let metadata = Metadata {
+            lifecycles: Some(Lifecycles(vec![Lifecycle::Phase(Phase::Build)])),
+            ..Metadata::default()
+        };
+
+        for version in [SpecVersion::V1_3, SpecVersion::V1_4] {
+            assert_eq!(
+                metadata.validate_version(version),
+                validation::r#struct(
+                    "lifecycles",
+                    validation::custom(
+                        "",
+                        [format!("Lifecycles are not defined for version {version}")]
+                    )
+                )
+            );
+        }
+        assert!(metadata.validate_version(SpecVersion::V1_5).passed());
+    }
+
+    #[test]
+    fn it_should_round_trip_lifecycles() {
+        let lifecycles = Lifecycles(vec![
+            Lifecycle::Phase(Phase::PreBuild),
+            Lifecycle::Description(Description {
+                name: NormalizedString::new("platform-integration-testing"),
+                description: Some(NormalizedString::new("Integration testing on the platform")),
+            }),
+        ]);
+        let bom = Bom {
+            spec_version: SpecVersion::V1_5,
+            metadata: Some(Metadata {
+                lifecycles: Some(lifecycles.clone()),
+                ..Metadata::default()
+            }),
+            ..Bom::default()
+        };
+
+        let mut json = Vec::new();
+        bom.clone()
+            .output_as_json_v1_5(&mut json)
+            .expect("Failed to write JSON");
+        let from_json = Bom::parse_from_json_v1_5(json.as_slice()).expect("Failed to parse JSON");
+
+        let mut xml = Vec::new();
+        bom.output_as_xml_v1_5(&mut xml)
+            .expect("Failed to write XML");
+        let from_xml = Bom::parse_from_xml_v1_5(xml.as_slice()).expect("Failed to parse XML");
+
+        for parsed in [from_json, from_xml] {
+            assert_eq!(
+                parsed.metadata.unwrap().lifecycles,
+                Some(lifecycles.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_fail_validation_for_repeated_lifecycle_phases() {
+        let validation_result = Metadata {
+            lifecycles: Some(Lifecycles(vec![
+                Lifecycle::Phase(Phase::Build),
+                Lifecycle::Phase(Phase::PostBuild),
+                Lifecycle::Phase(Phase::Build),
+            ])),
+            ..Metadata::default()
+        }
+        .validate_version(SpecVersion::V1_5);
+
+        assert_eq!(
+            validation_result,
+            validation::r#struct(
+                "lifecycles",
+                validation::list("inner", [(2, validation::custom("", ["repeated element"]))])
+            )
+        );
+    }
 }
@@ -21,12 +21,15 @@ pub mod annotation;
 pub mod attached_text;
 pub mod attachment;
 pub mod bom;
+pub mod canonical;
 pub mod code;
 pub mod component;
 pub mod component_data;
 pub mod composition;
 pub mod data_governance;
 pub mod dependency;
+pub mod diff;
+pub mod extension;
 pub mod external_reference;
 pub mod formulation;
 pub mod hash;
@@ -38,6 +41,7 @@ pub mod organization;
 pub mod property;
 pub mod service;
 pub mod signature;
+pub mod stream;
 pub mod tool;
 pub mod vulnerability;
 pub mod vulnerability_analysis;
@@ -103,7 +103,9 @@ impl ModelParametersApproach {
 /// Checks the given [`ApproachType`] is valid.
 pub fn validate_approach_type(approach_type: &ApproachType) -> Result<(), ValidationError> {
     if let ApproachType::Unknown(unknown) = approach_type {
-        return Err(format!("Unknown approach type '{unknown}'").into());
+        return Err(ValidationError::unknown_value(format!(
+            "Unknown approach type '{unknown}'"
+        )));
     }
     Ok(())
 }
@@ -312,7 +314,7 @@ mod test {
                     name: Some("dataset".to_string()),
                     contents: Some(DataContents {
                         attachment: Some(Attachment {
-                            content: "data content".to_string(),
+                            content: "ZGF0YSBjb250ZW50".to_string(),
                             content_type: Some("text/plain".to_string()),
                             encoding: Some("base64".to_string()),
                         }),
@@ -328,7 +330,7 @@ mod test {
                             image: Some(Attachment {
                                 content_type: Some("image/jpeg".to_string()),
                                 encoding: Some("base64".to_string()),
-                                content: "imagebytes".to_string(),
+                                content: "aW1hZ2VieXRlcw==".to_string(),
                             }),
                         }]),
                     }),
@@ -16,6 +16,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use std::collections::BTreeMap;
+
 use crate::{
     external_models::normalized_string::{validate_normalized_string, NormalizedString},
     validation::{Validate, ValidationContext, ValidationResult},
@@ -31,6 +33,70 @@ use super::bom::SpecVersion;
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Properties(pub Vec<Property>);
 
+impl Properties {
+    /// Returns the value of the first property with the given name.
+    /// ```
+    /// use cyclonedx_bom::models::property::{Properties, Property};
+    ///
+    /// let properties = Properties(vec![Property::new("cdx:cargo:source", "crates.io")]);
+    /// assert_eq!(properties.get("cdx:cargo:source"), Some("crates.io"));
+    /// assert_eq!(properties.get("cdx:cargo:features"), None);
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|property| property.name == name)
+            .map(|property| property.value.as_ref())
+    }
+
+    /// Returns the values of all properties with the given name, as names may repeat.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |property| property.name == name)
+            .map(|property| property.value.as_ref())
+    }
+
+    /// Sets the value of the property with the given name, replacing all properties with that
+    /// name. The property keeps the position of the first one, or is appended otherwise.
+    pub fn insert(&mut self, name: impl ToString, value: &str) {
+        let property = Property::new(name, value);
+        let mut found = false;
+        self.0.retain_mut(|other| {
+            if other.name != property.name {
+                return true;
+            }
+            if found {
+                return false;
+            }
+            found = true;
+            other.value = property.value.clone();
+            true
+        });
+        if !found {
+            self.0.push(property);
+        }
+    }
+
+    /// Removes all properties with the given name, returns `true` if there were any.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.0.len();
+        self.0.retain(|property| property.name != name);
+        self.0.len() != len
+    }
+}
+
+impl From<BTreeMap<String, String>> for Properties {
+    fn from(properties: BTreeMap<String, String>) -> Self {
+        Self(
+            properties
+                .into_iter()
+                .map(|(name, value)| Property::new(name, &value))
+                .collect(),
+        )
+    }
+}
+
 impl Validate for Properties {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
         ValidationContext::new()
@@ -75,6 +141,8 @@ impl Validate for Property {
 
 #[cfg(test)]
 mod test {
+    use std::collections::BTreeMap;
+
     use crate::{
         models::property::{Properties, Property},
         prelude::NormalizedString,
@@ -116,4 +184,58 @@ mod test {
             ),
         );
     }
+
+    #[test]
+    fn it_should_look_up_repeated_properties() {
+        let properties = Properties(vec![
+            Property::new("cdx:cargo:source", "crates.io"),
+            Property::new("cdx:cargo:features", "default"),
+            Property::new("cdx:cargo:source", "git"),
+        ]);
+
+        assert_eq!(properties.get("cdx:cargo:source"), Some("crates.io"));
+        assert_eq!(
+            properties.get_all("cdx:cargo:source").collect::<Vec<_>>(),
+            vec!["crates.io", "git"]
+        );
+        assert_eq!(properties.get_all("missing").count(), 0);
+    }
+
+    #[test]
+    fn it_should_insert_and_remove_properties() {
+        let mut properties = Properties(vec![
+            Property::new("cdx:cargo:source", "crates.io"),
+            Property::new("cdx:cargo:features", "default"),
+            Property::new("cdx:cargo:source", "git"),
+        ]);
+
+        properties.insert("cdx:cargo:source", "registry");
+        properties.insert("cdx:vcs:commit", "abc");
+        assert_eq!(
+            properties,
+            Properties(vec![
+                Property::new("cdx:cargo:source", "registry"),
+                Property::new("cdx:cargo:features", "default"),
+                Property::new("cdx:vcs:commit", "abc"),
+            ])
+        );
+
+        assert!(properties.remove("cdx:cargo:features"));
+        assert!(!properties.remove("cdx:cargo:features"));
+        assert_eq!(properties.get("cdx:cargo:features"), None);
+        assert_eq!(properties.0.len(), 2);
+    }
+
+    #[test]
+    fn it_should_convert_from_a_map() {
+        let properties = Properties::from(BTreeMap::from([
+            ("b".to_string(), "2".to_string()),
+            ("a".to_string(), "1".to_string()),
+        ]));
+
+        assert_eq!(
+            properties,
+            Properties(vec![Property::new("a", "1"), Property::new("b", "2")])
+        );
+    }
 }
@@ -23,7 +23,9 @@ use crate::models::external_reference::ExternalReferences;
 use crate::models::license::Licenses;
 use crate::models::organization::OrganizationalEntity;
 use crate::models::property::Properties;
-use crate::validation::{Validate, ValidationContext, ValidationError, ValidationResult};
+use crate::validation::{
+    validate_non_empty, Validate, ValidationContext, ValidationError, ValidationResult,
+};
 
 use super::bom::SpecVersion;
 use super::data_governance::DataGovernance;
@@ -175,7 +177,7 @@ impl Validate for ServiceData {
 
 pub fn validate_data_flow_type(data_flow_type: &DataFlowType) -> Result<(), ValidationError> {
     if matches!(data_flow_type, DataFlowType::UnknownDataFlow(_)) {
-        return Err(ValidationError::new("Unknown data flow type"));
+        return Err(ValidationError::unknown_value("Unknown data flow type"));
     }
     Ok(())
 }
@@ -196,12 +198,18 @@ impl Validate for DataClassification {
             .add_enum(
                 "classification",
                 &self.classification,
-                validate_normalized_string,
+                validate_classification,
             )
             .into()
     }
 }
 
+/// The classification is a required string, which must not be empty.
+fn validate_classification(classification: &NormalizedString) -> Result<(), ValidationError> {
+    validate_non_empty(classification)?;
+    validate_normalized_string(classification)
+}
+
 /// Represents the flow direction of the data
 ///
 /// Defined via the [XML schema](https://cyclonedx.org/docs/1.3/xml/#type_dataFlowType)
@@ -372,7 +380,9 @@ mod test {
                                         vec![
                                             validation::r#enum(
                                                 "flow",
-                                                "Unknown data flow type"
+                                                ValidationError::unknown_value(
+                                                    "Unknown data flow type"
+                                                )
                                             ),
                                             validation::r#enum(
                                                 "classification",
@@ -390,20 +400,7 @@ mod test {
                                         0,
                                         validation::r#enum(
                                             "expression",
-                                            "SPDX expression is not valid"
-                                        )
-                                    )]
-                                )
-                            ),
-                            validation::r#struct(
-                                "external_references",
-                                validation::list(
-                                    "inner",
-                                    [(
-                                        0,
-                                        validation::field(
-                                            "external_reference_type",
-                                            "Unknown external reference type"
+                                            "SPDX expression is not valid at position 0: unknown term"
                                         )
                                     )]
                                 )
@@ -436,8 +433,53 @@ mod test {
                             )
                         ]
                     )]
-                )
+                ),
+                validation::warning(
+                    "inner[0].external_references.inner[0].external_reference_type",
+                    [ValidationError::unknown_value("Unknown external reference type")]
+                ),
             ].into()
         );
     }
+
+    #[test]
+    fn empty_data_classifications_should_fail_validation() {
+        let classification = DataClassification {
+            flow: DataFlowType::Inbound,
+            classification: NormalizedString::new(" "),
+        };
+        let mut service = Service::new("name", None);
+        service.data = Some(Data::ServiceData(vec![ServiceData {
+            name: None,
+            description: None,
+            classification: classification.clone(),
+            governance: None,
+            source: None,
+            destination: None,
+        }]));
+        let mut legacy_service = Service::new("legacy", None);
+        legacy_service.data = Some(Data::Classification(vec![classification]));
+
+        let validation_result = Services(vec![service, legacy_service]).validate();
+
+        let empty_classification =
+            || validation::r#enum("classification", "Required string must not be empty");
+        let data = |errors| validation::r#struct("data", validation::list("inner", [(0, errors)]));
+        assert_eq!(
+            validation_result,
+            validation::list(
+                "inner",
+                [
+                    (
+                        0,
+                        data(validation::r#struct(
+                            "classification",
+                            empty_classification()
+                        ))
+                    ),
+                    (1, data(empty_classification())),
+                ]
+            )
+        );
+    }
 }
@@ -16,6 +16,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+#[cfg(feature = "signature-verify")]
+use crate::errors::SignatureError;
 use crate::{
     prelude::{SpecVersion, Validate, ValidationResult},
     validation::{ValidationContext, ValidationError},
@@ -49,11 +51,20 @@ impl Validate for Signature {
     }
 }
 
-/// For now the [`Signer`] struct only holds algorithm and value
+/// A single JSF signer, see the
+/// [signature core elements](https://cyberphone.github.io/doc/security/jsf.html#Signature_Core_Elements).
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Signer {
     /// Signature algorithm.
     pub algorithm: Algorithm,
+    /// Application specific string identifying the signature key.
+    pub key_id: Option<String>,
+    /// The public key the signature can be verified with.
+    pub public_key: Option<JsonWebKey>,
+    /// Sorted array of base64url encoded X.509 certificates, the signing certificate first.
+    pub certificate_path: Option<Vec<String>>,
+    /// Names of the properties of the signed object that are not covered by the signature.
+    pub excludes: Option<Vec<String>>,
     /// The signature data.
     pub value: String,
 }
@@ -62,11 +73,32 @@ impl Signer {
     pub fn new(algorithm: Algorithm, value: &str) -> Self {
         Self {
             algorithm,
+            key_id: None,
+            public_key: None,
+            certificate_path: None,
+            excludes: None,
             value: value.to_string(),
         }
     }
 }
 
+/// A public key in [JSON Web Key](https://www.rfc-editor.org/rfc/rfc7517) form, as embedded in a JSF signer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct JsonWebKey {
+    /// Key type, `EC`, `OKP` or `RSA`.
+    pub kty: String,
+    /// Curve of an `EC` or `OKP` key.
+    pub crv: Option<String>,
+    /// Base64url encoded x coordinate of an `EC` or `OKP` key.
+    pub x: Option<String>,
+    /// Base64url encoded y coordinate of an `EC` key.
+    pub y: Option<String>,
+    /// Base64url encoded modulus of an `RSA` key.
+    pub n: Option<String>,
+    /// Base64url encoded exponent of an `RSA` key.
+    pub e: Option<String>,
+}
+
 impl Validate for Signer {
     fn validate_version(&self, _version: SpecVersion) -> ValidationResult {
         ValidationContext::new()
@@ -102,6 +134,70 @@ impl Signature {
     }
 }
 
+/// A public key to verify a [`Signer`] with, requires the `signature-verify` feature.
+#[cfg(feature = "signature-verify")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PublicKey {
+    /// An RSA key as DER encoded PKCS#1 `RSAPublicKey`, used with [`Algorithm::RS256`].
+    Rsa(Vec<u8>),
+    /// A P-256 key as uncompressed SEC1 point, used with [`Algorithm::ES256`].
+    EcdsaP256(Vec<u8>),
+}
+
+#[cfg(feature = "signature-verify")]
+impl Signature {
+    /// Verifies a single signature against the canonical payload it was created for, e.g.
+    /// [`json_signature_payload`](crate::models::canonical::json_signature_payload).
+    pub fn verify(
+        &self,
+        canonical_payload: &[u8],
+        public_key: &PublicKey,
+    ) -> Result<(), SignatureError> {
+        match self {
+            Signature::Single(signer) => signer.verify(canonical_payload, public_key),
+            Signature::Signers(_) | Signature::Chain(_) => {
+                Err(SignatureError::UnsupportedSignatureType)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "signature-verify")]
+impl Signer {
+    /// Verifies the base64url encoded signature value against the canonical payload.
+    ///
+    /// Supports [`Algorithm::RS256`] and [`Algorithm::ES256`].
+    pub fn verify(
+        &self,
+        canonical_payload: &[u8],
+        public_key: &PublicKey,
+    ) -> Result<(), SignatureError> {
+        use base64::Engine;
+        use ring::signature::{self, UnparsedPublicKey, VerificationAlgorithm};
+
+        let (algorithm, key): (&'static dyn VerificationAlgorithm, &[u8]) =
+            match (&self.algorithm, public_key) {
+                (Algorithm::RS256, PublicKey::Rsa(key)) => {
+                    (&signature::RSA_PKCS1_2048_8192_SHA256, key)
+                }
+                (Algorithm::ES256, PublicKey::EcdsaP256(key)) => {
+                    (&signature::ECDSA_P256_SHA256_FIXED, key)
+                }
+                (Algorithm::RS256 | Algorithm::ES256, _) => {
+                    return Err(SignatureError::KeyMismatch(self.algorithm.to_string()))
+                }
+                (algorithm, _) => {
+                    return Err(SignatureError::UnsupportedAlgorithm(algorithm.to_string()))
+                }
+            };
+
+        let value = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(&self.value)?;
+        UnparsedPublicKey::new(algorithm, key)
+            .verify(canonical_payload, &value)
+            .map_err(|_| SignatureError::VerificationFailed)
+    }
+}
+
 /// Supported signature algorithms.
 #[derive(Clone, Debug, PartialEq, Eq, strum::Display, Hash)]
 pub enum Algorithm {
@@ -124,7 +220,9 @@ pub enum Algorithm {
 
 pub fn validate_algorithm(algorithm: &Algorithm) -> Result<(), ValidationError> {
     if let Algorithm::Unknown(unknown) = algorithm {
-        return Err(format!("Unknown algorithm '{unknown}'").into());
+        return Err(ValidationError::unknown_value(format!(
+            "Unknown algorithm '{unknown}'"
+        )));
     }
     Ok(())
 }
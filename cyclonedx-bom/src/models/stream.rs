@@ -0,0 +1,372 @@
+/*
+ * This file is part of CycloneDX Rust Cargo.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::io::Write;
+
+use serde::Serialize;
+use serde_json::ser::{Formatter, PrettyFormatter};
+
+use crate::errors::JsonWriteError;
+use crate::models::bom::{SpecVersion, UrnUuid};
+use crate::models::component::Component;
+use crate::models::metadata::Metadata;
+
+/// Writes a CycloneDX JSON document incrementally, so that only the component currently being
+/// written is held in memory.
+///
+/// The output is identical to [`Bom::output_as_json`](crate::models::bom::Bom::output_as_json)
+/// for a BOM with the same header fields and components.
+/// ```
+/// use cyclonedx_bom::models::component::Classification;
+/// use cyclonedx_bom::models::stream::BomStreamWriter;
+/// use cyclonedx_bom::prelude::*;
+///
+/// let mut writer = BomStreamWriter::new(Vec::new(), SpecVersion::V1_5);
+/// writer.write_header(1, Some(UrnUuid::generate()), None).unwrap();
+/// for name in ["serde", "serde_json"] {
+///     let component = Component::new(Classification::Library, name, "1.0.0", None);
+///     writer.write_component(&component).unwrap();
+/// }
+/// let json = writer.finish().unwrap();
+///
+/// let bom = Bom::parse_from_json(json.as_slice()).unwrap();
+/// assert_eq!(bom.components.unwrap().0.len(), 2);
+/// ```
+pub struct BomStreamWriter<W: Write> {
+    writer: W,
+    /// Lays out the document like [`serde_json::to_writer_pretty`], it keeps track of the
+    /// indentation of the open BOM object and components array between calls.
+    formatter: PrettyFormatter<'static>,
+    spec_version: SpecVersion,
+    state: StreamState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StreamState {
+    /// Nothing has been written yet.
+    Empty,
+    /// The header has been written, but no component yet.
+    Header,
+    /// At least one component has been written.
+    Components,
+}
+
+impl<W: Write> BomStreamWriter<W> {
+    /// Creates a writer producing a document for the given spec version.
+    pub fn new(writer: W, spec_version: SpecVersion) -> Self {
+        Self {
+            writer,
+            formatter: PrettyFormatter::new(),
+            spec_version,
+            state: StreamState::Empty,
+        }
+    }
+
+    /// Writes the fields preceding the components, this must be called exactly once before
+    /// any component is written.
+    pub fn write_header(
+        &mut self,
+        version: u32,
+        serial_number: Option<UrnUuid>,
+        metadata: Option<Metadata>,
+    ) -> Result<(), JsonWriteError> {
+        if self.state != StreamState::Empty {
+            return Err(order_error("The header has already been written"));
+        }
+
+        self.formatter
+            .begin_object(&mut self.writer)
+            .map_err(serde_json::Error::io)?;
+        self.write_field("bomFormat", &"CycloneDX", true)?;
+        let spec_version = self.spec_version;
+        self.write_field("specVersion", &spec_version, false)?;
+        self.write_field("version", &version, false)?;
+        self.write_field(
+            "serialNumber",
+            &serial_number.map(|serial_number| serial_number.0),
+            false,
+        )?;
+        if let Some(metadata) = metadata {
+            match self.spec_version {
+                SpecVersion::V1_3 => {
+                    let metadata: crate::specs::v1_3::metadata::Metadata = metadata.try_into()?;
+                    self.write_field("metadata", &metadata, false)?;
+                }
+                SpecVersion::V1_4 => {
+                    let metadata: crate::specs::v1_4::metadata::Metadata = metadata.try_into()?;
+                    self.write_field("metadata", &metadata, false)?;
+                }
+                SpecVersion::V1_5 => {
+                    let metadata: crate::specs::v1_5::metadata::Metadata = metadata.try_into()?;
+                    self.write_field("metadata", &metadata, false)?;
+                }
+            }
+        }
+
+        self.state = StreamState::Header;
+        Ok(())
+    }
+
+    /// Writes a single top level component, nested components included.
+    pub fn write_component(&mut self, component: &Component) -> Result<(), JsonWriteError> {
+        let first = match self.state {
+            StreamState::Empty => {
+                return Err(order_error(
+                    "The header must be written before any component",
+                ))
+            }
+            StreamState::Header => {
+                self.begin_components()?;
+                true
+            }
+            StreamState::Components => false,
+        };
+
+        self.formatter
+            .begin_array_value(&mut self.writer, first)
+            .map_err(serde_json::Error::io)?;
+        let component = component.clone();
+        match self.spec_version {
+            SpecVersion::V1_3 => {
+                let component: crate::specs::v1_3::component::Component = component.try_into()?;
+                self.write_value(&component)?;
+            }
+            SpecVersion::V1_4 => {
+                let component: crate::specs::v1_4::component::Component = component.try_into()?;
+                self.write_value(&component)?;
+            }
+            SpecVersion::V1_5 => {
+                let component: crate::specs::v1_5::component::Component = component.try_into()?;
+                self.write_value(&component)?;
+            }
+        }
+        self.formatter
+            .end_array_value(&mut self.writer)
+            .map_err(serde_json::Error::io)?;
+
+        self.state = StreamState::Components;
+        Ok(())
+    }
+
+    /// Closes the document and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, JsonWriteError> {
+        match self.state {
+            StreamState::Empty => {
+                return Err(order_error("The header must be written before finishing"))
+            }
+            StreamState::Header => self.begin_components()?,
+            StreamState::Components => {}
+        }
+
+        self.formatter
+            .end_array(&mut self.writer)
+            .and_then(|_| self.formatter.end_object_value(&mut self.writer))
+            .and_then(|_| self.formatter.end_object(&mut self.writer))
+            .and_then(|_| self.writer.flush())
+            .map_err(serde_json::Error::io)?;
+        Ok(self.writer)
+    }
+
+    /// Opens the components array, which stays open until the document is finished.
+    fn begin_components(&mut self) -> Result<(), JsonWriteError> {
+        self.begin_field("components", false)?;
+        self.formatter
+            .begin_array(&mut self.writer)
+            .map_err(serde_json::Error::io)?;
+        Ok(())
+    }
+
+    /// Writes a field of the BOM object.
+    fn write_field<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+        first: bool,
+    ) -> Result<(), JsonWriteError> {
+        self.begin_field(key, first)?;
+        self.write_value(value)?;
+        self.formatter
+            .end_object_value(&mut self.writer)
+            .map_err(serde_json::Error::io)?;
+        Ok(())
+    }
+
+    /// Writes the key of a field of the BOM object, leaving the writer at its value.
+    fn begin_field(&mut self, key: &str, first: bool) -> Result<(), JsonWriteError> {
+        self.formatter
+            .begin_object_key(&mut self.writer, first)
+            .map_err(serde_json::Error::io)?;
+        self.write_value(key)?;
+        self.formatter
+            .end_object_key(&mut self.writer)
+            .and_then(|_| self.formatter.begin_object_value(&mut self.writer))
+            .map_err(serde_json::Error::io)?;
+        Ok(())
+    }
+
+    /// Serializes a value at the current position and indentation of the document.
+    fn write_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonWriteError> {
+        let formatter = SharedFormatter(&mut self.formatter);
+        let mut serializer = serde_json::Serializer::with_formatter(&mut self.writer, formatter);
+        value.serialize(&mut serializer)?;
+        Ok(())
+    }
+}
+
+fn order_error(message: &'static str) -> JsonWriteError {
+    JsonWriteError::StreamOrderError { message }
+}
+
+/// Lets a [`serde_json::Serializer`] continue with the indentation of the stream's formatter.
+///
+/// Only the methods that [`PrettyFormatter`] overrides need to be forwarded.
+struct SharedFormatter<'a>(&'a mut PrettyFormatter<'static>);
+
+impl Formatter for SharedFormatter<'_> {
+    fn begin_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.0.begin_array(writer)
+    }
+
+    fn end_array<W: ?Sized + Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.0.end_array(writer)
+    }
+
+    fn begin_array_value<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> std::io::Result<()> {
+        self.0.begin_array_value(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.0.end_array_value(writer)
+    }
+
+    fn begin_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.0.begin_object(writer)
+    }
+
+    fn end_object<W: ?Sized + Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.0.end_object(writer)
+    }
+
+    fn begin_object_key<W: ?Sized + Write>(
+        &mut self,
+        writer: &mut W,
+        first: bool,
+    ) -> std::io::Result<()> {
+        self.0.begin_object_key(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.0.begin_object_value(writer)
+    }
+
+    fn end_object_value<W: ?Sized + Write>(&mut self, writer: &mut W) -> std::io::Result<()> {
+        self.0.end_object_value(writer)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::bom::Bom;
+    use crate::models::component::{Classification, Components};
+    use crate::prelude::{DateTime, Purl};
+
+    fn components() -> Vec<Component> {
+        let mut serde = Component::new(Classification::Library, "serde", "1.0.193", None);
+        serde.purl = Some(Purl::cargo("serde", "1.0.193").unwrap());
+        serde.components = Some(Components(vec![Component::new(
+            Classification::Library,
+            "serde_derive",
+            "1.0.193",
+            Some("serde_derive".to_string()),
+        )]));
+        vec![
+            serde,
+            Component::new(Classification::Application, "app", "0.1.0", None),
+        ]
+    }
+
+    #[test]
+    fn it_should_stream_the_same_json_as_a_bom() {
+        let metadata = Metadata {
+            timestamp: Some(DateTime::now().unwrap()),
+            ..Metadata::default()
+        };
+        let serial_number = UrnUuid::generate();
+
+        for spec_version in [SpecVersion::V1_3, SpecVersion::V1_4, SpecVersion::V1_5] {
+            let mut writer = BomStreamWriter::new(Vec::new(), spec_version);
+            writer
+                .write_header(2, Some(serial_number.clone()), Some(metadata.clone()))
+                .unwrap();
+            for component in components() {
+                writer.write_component(&component).unwrap();
+            }
+            let streamed = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+            let bom = Bom {
+                version: 2,
+                serial_number: Some(serial_number.clone()),
+                metadata: Some(metadata.clone()),
+                components: Some(Components(components())),
+                ..Bom::default()
+            };
+            let mut expected = Vec::new();
+            bom.output_as_json(&mut expected, spec_version).unwrap();
+
+            assert_eq!(streamed, String::from_utf8(expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn it_should_stream_a_bom_without_components() {
+        let mut writer = BomStreamWriter::new(Vec::new(), SpecVersion::V1_5);
+        writer.write_header(1, None, None).unwrap();
+        let streamed = String::from_utf8(writer.finish().unwrap()).unwrap();
+
+        let bom = Bom {
+            serial_number: None,
+            components: Some(Components(Vec::new())),
+            ..Bom::default()
+        };
+        let mut expected = Vec::new();
+        bom.output_as_json_v1_5(&mut expected).unwrap();
+
+        assert_eq!(streamed, String::from_utf8(expected).unwrap());
+    }
+
+    #[test]
+    fn it_should_require_the_header_first() {
+        let component = Component::new(Classification::Library, "serde", "1.0.193", None);
+        let mut writer = BomStreamWriter::new(Vec::new(), SpecVersion::V1_5);
+
+        assert!(matches!(
+            writer.write_component(&component),
+            Err(JsonWriteError::StreamOrderError { .. })
+        ));
+        writer.write_header(1, None, None).unwrap();
+        assert!(matches!(
+            writer.write_header(1, None, None),
+            Err(JsonWriteError::StreamOrderError { .. })
+        ));
+    }
+}
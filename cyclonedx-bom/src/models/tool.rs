@@ -21,7 +21,7 @@ use crate::models::hash::Hashes;
 use crate::validation::{Validate, ValidationContext, ValidationResult};
 
 use super::bom::SpecVersion;
-use super::component::Components;
+use super::component::{Classification, Component, Components};
 use super::external_reference::ExternalReferences;
 use super::service::Services;
 
@@ -41,6 +41,66 @@ pub enum Tools {
     },
 }
 
+impl Tools {
+    /// Converts the legacy list into the object form added in 1.5, where every tool becomes an
+    /// `application` component. The vendor of a tool is stored as the group of its component.
+    pub fn into_components(self) -> Self {
+        match self {
+            Tools::List(tools) => {
+                let components: Vec<Component> = tools
+                    .into_iter()
+                    .map(|tool| {
+                        let mut component =
+                            Component::new(Classification::Application, "", "", None);
+                        component.group = tool.vendor;
+                        component.name = tool.name.unwrap_or_default();
+                        component.version = tool.version;
+                        component.hashes = tool.hashes;
+                        component.external_references = tool.external_references;
+                        component
+                    })
+                    .collect();
+                Tools::Object {
+                    services: None,
+                    components: (!components.is_empty()).then_some(Components(components)),
+                }
+            }
+            object => object,
+        }
+    }
+
+    /// Converts the object form into the legacy list understood by versions before 1.5.
+    /// Components come first, followed by services, and their group becomes the vendor of a tool.
+    pub fn into_list(self) -> Self {
+        match self {
+            Tools::Object {
+                services,
+                components,
+            } => {
+                let components = components
+                    .into_iter()
+                    .flat_map(|c| c.0)
+                    .map(|component| Tool {
+                        vendor: component.group,
+                        name: Some(component.name),
+                        version: component.version,
+                        hashes: component.hashes,
+                        external_references: component.external_references,
+                    });
+                let services = services.into_iter().flat_map(|s| s.0).map(|service| Tool {
+                    vendor: service.group,
+                    name: Some(service.name),
+                    version: service.version,
+                    hashes: None,
+                    external_references: service.external_references,
+                });
+                Tools::List(components.chain(services).collect())
+            }
+            list => list,
+        }
+    }
+}
+
 impl Validate for Tools {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
         let mut context = ValidationContext::new();
@@ -223,6 +283,66 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_should_convert_a_list_into_components() {
+        let tools = Tools::List(vec![Tool::new("CycloneDX", "cargo-cyclonedx", "0.5.0")]);
+
+        let mut expected = Component::new(
+            Classification::Application,
+            "cargo-cyclonedx",
+            "0.5.0",
+            None,
+        );
+        expected.group = Some(NormalizedString::new("CycloneDX"));
+
+        assert_eq!(
+            tools.into_components(),
+            Tools::Object {
+                services: None,
+                components: Some(Components(vec![expected])),
+            }
+        );
+    }
+
+    #[test]
+    fn it_should_convert_components_and_services_into_a_list() {
+        let mut component = Component::new(
+            Classification::Application,
+            "cargo-cyclonedx",
+            "0.5.0",
+            None,
+        );
+        component.group = Some(NormalizedString::new("CycloneDX"));
+        let mut service = Service::new("scanner", None);
+        service.version = Some(NormalizedString::new("2.0"));
+
+        let tools = Tools::Object {
+            services: Some(Services(vec![service])),
+            components: Some(Components(vec![component])),
+        };
+
+        assert_eq!(
+            tools.clone().into_list(),
+            Tools::List(vec![
+                Tool::new("CycloneDX", "cargo-cyclonedx", "0.5.0"),
+                Tool {
+                    vendor: None,
+                    name: Some(NormalizedString::new("scanner")),
+                    version: Some(NormalizedString::new("2.0")),
+                    hashes: None,
+                    external_references: None,
+                },
+            ])
+        );
+        assert_eq!(
+            Tools::List(vec![Tool::new("CycloneDX", "cargo-cyclonedx", "0.5.0")])
+                .into_components()
+                .into_list(),
+            Tools::List(vec![Tool::new("CycloneDX", "cargo-cyclonedx", "0.5.0")])
+        );
+        assert_eq!(tools.clone().into_components(), tools);
+    }
+
     #[test]
     fn it_should_handle_different_tools() {
         let tool = Tool::new("A vendor", "cargo-cyclonedx", "0.1");
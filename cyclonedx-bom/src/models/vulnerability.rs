@@ -28,7 +28,9 @@ use crate::models::vulnerability_rating::VulnerabilityRatings;
 use crate::models::vulnerability_reference::VulnerabilityReferences;
 use crate::models::vulnerability_source::VulnerabilitySource;
 use crate::models::vulnerability_target::VulnerabilityTargets;
-use crate::validation::{Validate, ValidationContext, ValidationResult};
+use crate::validation::{
+    validate_non_empty, Validate, ValidationContext, ValidationError, ValidationResult,
+};
 
 use super::attachment::Attachment;
 use super::bom::SpecVersion;
@@ -98,7 +100,7 @@ impl Vulnerability {
 impl Validate for Vulnerability {
     fn validate_version(&self, version: SpecVersion) -> ValidationResult {
         ValidationContext::new()
-            .add_field_option("id", self.id.as_ref(), validate_normalized_string)
+            .add_field("id", self.id.as_ref(), validate_vulnerability_id)
             .add_struct_option(
                 "vulnerability_source",
                 self.vulnerability_source.as_ref(),
@@ -114,6 +116,7 @@ impl Validate for Vulnerability {
                 self.vulnerability_ratings.as_ref(),
                 |ratings| ratings.validate_version(version),
             )
+            .add_struct_option("proof_of_concept", self.proof_of_concept.as_ref(), version)
             .add_list("advisories", self.advisories.as_ref(), |advisories| {
                 advisories.validate_version(version)
             })
@@ -141,6 +144,13 @@ impl Validate for Vulnerability {
     }
 }
 
+/// A vulnerability cannot be identified without an `id`, even though the schema allows to omit it.
+fn validate_vulnerability_id(id: Option<&NormalizedString>) -> Result<(), ValidationError> {
+    let id = id.ok_or_else(|| ValidationError::new("Vulnerability id is required"))?;
+    validate_non_empty(id)?;
+    validate_normalized_string(id)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Vulnerabilities(pub Vec<Vulnerability>);
 
@@ -161,6 +171,18 @@ pub struct VulnerabilityProofOfConcept {
     pub supporting_material: Option<Vec<Attachment>>,
 }
 
+impl Validate for VulnerabilityProofOfConcept {
+    fn validate_version(&self, version: SpecVersion) -> ValidationResult {
+        ValidationContext::new()
+            .add_list_option(
+                "supporting_material",
+                self.supporting_material.as_ref(),
+                |attachment| attachment.validate_version(version),
+            )
+            .into()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -385,7 +407,9 @@ mod test {
                                             validation::r#enum("severity", "Undefined severity"),
                                             validation::field(
                                                 "score_method",
-                                                "Unknown score method 'other method'",
+                                                ValidationError::unknown_value(
+                                                    "Unknown score method 'other method'"
+                                                ),
                                             ),
                                             validation::field(
                                                 "vector",
@@ -455,4 +479,32 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn vulnerabilities_without_an_id_should_fail_validation() {
+        let vulnerability = |id: Option<&str>| Vulnerability {
+            id: id.map(NormalizedString::new),
+            ..Vulnerability::new(None)
+        };
+        let validation_result = Vulnerabilities(vec![
+            vulnerability(None),
+            vulnerability(Some(" ")),
+            vulnerability(Some("CVE-2021-44228")),
+        ])
+        .validate();
+
+        assert_eq!(
+            validation_result,
+            validation::list(
+                "inner",
+                [
+                    (0, validation::field("id", "Vulnerability id is required")),
+                    (
+                        1,
+                        validation::field("id", "Required string must not be empty")
+                    ),
+                ]
+            )
+        );
+    }
 }
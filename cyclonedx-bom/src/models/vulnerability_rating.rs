@@ -16,6 +16,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use std::cmp::Ordering;
+
 use ordered_float::OrderedFloat;
 
 use crate::external_models::normalized_string::{validate_normalized_string, NormalizedString};
@@ -134,8 +136,11 @@ pub fn validate_severity(severity: &Severity) -> Result<(), ValidationError> {
 
 /// Specifies a vulnerability's severity adopted by the analysis method.
 ///
+/// Severities are ordered from the least to the most severe, so `Severity::Critical` compares as
+/// the greatest value. `Severity::Unknown` ranks below `Severity::None`.
+///
 /// Defined via the [XML schema](https://cyclonedx.org/docs/1.4/xml/#type_severityType)
-#[derive(Clone, Debug, PartialEq, Eq, strum::Display)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, strum::Display)]
 #[strum(serialize_all = "kebab-case")]
 pub enum Severity {
     Critical,
@@ -163,20 +168,50 @@ impl Severity {
             undefined => Self::UndefinedSeverity(undefined.to_string()),
         }
     }
+
+    /// Ranks the severity for ordering, undefined severities rank lowest.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::UndefinedSeverity(_) => 0,
+            Self::Unknown => 1,
+            Self::None => 2,
+            Self::Info => 3,
+            Self::Low => 4,
+            Self::Medium => 5,
+            Self::High => 6,
+            Self::Critical => 7,
+        }
+    }
+}
+
+impl Ord for Severity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank()
+            .cmp(&other.rank())
+            .then_with(|| match (self, other) {
+                (Self::UndefinedSeverity(a), Self::UndefinedSeverity(b)) => a.cmp(b),
+                _ => Ordering::Equal,
+            })
+    }
+}
+
+impl PartialOrd for Severity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 pub fn validate_score_method(
     method: &ScoreMethod,
     version: SpecVersion,
 ) -> Result<(), ValidationError> {
-    if version <= SpecVersion::V1_4 {
-        if ScoreMethod::OWASP < *method {
-            return Err(format!("Unknown score method '{method}'").into());
-        }
-    } else if version <= SpecVersion::V1_5 {
-        if let ScoreMethod::Unknown(unknown) = method {
-            return Err(format!("Unknown score method '{unknown}'").into());
-        }
+    if let ScoreMethod::Unknown(unknown) = method {
+        return Err(ValidationError::unknown_value(format!(
+            "Unknown score method '{unknown}'"
+        )));
+    }
+    if version <= SpecVersion::V1_4 && ScoreMethod::OWASP < *method {
+        return Err(format!("Unknown score method '{method}'").into());
     }
     Ok(())
 }
@@ -222,6 +257,35 @@ mod test {
 
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn it_should_order_severities_from_least_to_most_severe() {
+        let mut severities = vec![
+            Severity::High,
+            Severity::UndefinedSeverity("bogus".to_string()),
+            Severity::Info,
+            Severity::Critical,
+            Severity::None,
+            Severity::Unknown,
+            Severity::Medium,
+            Severity::Low,
+        ];
+        severities.sort();
+
+        assert_eq!(
+            severities,
+            vec![
+                Severity::UndefinedSeverity("bogus".to_string()),
+                Severity::Unknown,
+                Severity::None,
+                Severity::Info,
+                Severity::Low,
+                Severity::Medium,
+                Severity::High,
+                Severity::Critical,
+            ]
+        );
+    }
+
     #[test]
     fn valid_vulnerability_score_method() {
         assert!(
@@ -16,6 +16,8 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use std::cmp::Ordering;
+
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -46,6 +48,29 @@ impl VulnerabilityTarget {
             versions: None,
         }
     }
+
+    /// Returns `true` if the target is affected in the given version of the referenced component
+    /// or service.
+    ///
+    /// Without any versions listed, or if the version is not known, the target is considered
+    /// affected. Otherwise `version` has to match one of the listed versions, and none of the
+    /// matching versions may have the status [`Status::Unaffected`].
+    pub fn affects(&self, version: Option<&str>) -> bool {
+        let (Some(versions), Some(version)) = (&self.versions, version) else {
+            return true;
+        };
+        if versions.0.is_empty() {
+            return true;
+        }
+
+        let mut matching = versions
+            .0
+            .iter()
+            .filter(|candidate| candidate.version_range.contains(version))
+            .peekable();
+        matching.peek().is_some()
+            && matching.all(|candidate| candidate.status != Status::Unaffected)
+    }
 }
 
 impl Validate for VulnerabilityTarget {
@@ -137,6 +162,138 @@ impl VersionRange {
             false => VersionRange::Version(NormalizedString::new(value)),
         }
     }
+
+    /// Returns `true` if `version` is this version or lies within this range.
+    ///
+    /// Ranges are evaluated as described in the `vers` specification, with the constraints
+    /// expected in ascending order. Versions are compared by their dot separated parts, numeric
+    /// parts by value, and a pre-release sorts before its release, as in semantic versioning.
+    /// Undefined and malformed ranges contain no version.
+    /// ```
+    /// use cyclonedx_bom::models::vulnerability_target::VersionRange;
+    ///
+    /// let range = VersionRange::new("vers:cargo/>=1.0.0|<1.2.0");
+    /// assert!(range.contains("1.1.5"));
+    /// assert!(!range.contains("1.2.0"));
+    /// ```
+    pub fn contains(&self, version: &str) -> bool {
+        match self {
+            VersionRange::Version(expected) => compare_versions(expected, version).is_eq(),
+            VersionRange::Range(range) => vers_range_contains(range, version).unwrap_or(false),
+            VersionRange::UndefinedVersionRange(_) => false,
+        }
+    }
+}
+
+/// Evaluates a `vers:<scheme>/<constraints>` range, returns `None` if it cannot be parsed.
+fn vers_range_contains(range: &str, version: &str) -> Option<bool> {
+    let (_scheme, constraints) = range.strip_prefix("vers:")?.split_once('/')?;
+    if constraints.trim() == "*" {
+        return Some(true);
+    }
+
+    let constraints: Vec<_> = constraints
+        .split('|')
+        .map(|constraint| {
+            let constraint = constraint.trim();
+            let split = constraint
+                .find(|c: char| !matches!(c, '<' | '>' | '=' | '!'))
+                .unwrap_or(constraint.len());
+            let (comparator, bound) = constraint.split_at(split);
+            (comparator, bound.trim())
+        })
+        .collect();
+
+    // exact matches and exclusions take precedence over the intervals
+    for (comparator, bound) in &constraints {
+        match *comparator {
+            "" | "=" if compare_versions(version, bound).is_eq() => return Some(true),
+            "!=" if compare_versions(version, bound).is_eq() => return Some(false),
+            _ => (),
+        }
+    }
+
+    let mut lower_bound: Option<(&str, &str)> = None;
+    for (comparator, bound) in constraints {
+        let ordering = compare_versions(version, bound);
+
+        match comparator {
+            "" | "=" | "!=" => (),
+            ">" | ">=" => lower_bound = Some((comparator, bound)),
+            "<" | "<=" => {
+                let above_lower = lower_bound.map_or(true, |(comparator, bound)| {
+                    satisfies(comparator, compare_versions(version, bound))
+                });
+                if above_lower && satisfies(comparator, ordering) {
+                    return Some(true);
+                }
+                lower_bound = None;
+            }
+            _ => return None,
+        }
+    }
+
+    Some(
+        lower_bound.is_some_and(|(comparator, bound)| {
+            satisfies(comparator, compare_versions(version, bound))
+        }),
+    )
+}
+
+/// Checks the result of comparing a version to a bound against a `vers` comparator.
+fn satisfies(comparator: &str, ordering: Ordering) -> bool {
+    match comparator {
+        "<" => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        ">=" => ordering.is_ge(),
+        _ => ordering.is_eq(),
+    }
+}
+
+/// Compares two versions by their dot separated parts, ignoring build metadata after a `+`.
+fn compare_versions(left: &str, right: &str) -> Ordering {
+    fn split(version: &str) -> (&str, Option<&str>) {
+        let version = version
+            .split_once('+')
+            .map_or(version, |(version, _)| version);
+        match version.split_once('-') {
+            Some((release, pre_release)) => (release, Some(pre_release)),
+            None => (version, None),
+        }
+    }
+
+    fn compare_parts(left: &str, right: &str) -> Ordering {
+        let mut left = left.split('.');
+        let mut right = right.split('.');
+        loop {
+            let ordering = match (left.next(), right.next()) {
+                (None, None) => return Ordering::Equal,
+                (left, right) => {
+                    let (left, right) = (left.unwrap_or("0"), right.unwrap_or("0"));
+                    match (left.parse::<u64>(), right.parse::<u64>()) {
+                        (Ok(left), Ok(right)) => left.cmp(&right),
+                        _ => left.cmp(right),
+                    }
+                }
+            };
+            if ordering.is_ne() {
+                return ordering;
+            }
+        }
+    }
+
+    let (left_release, left_pre_release) = split(left.trim());
+    let (right_release, right_pre_release) = split(right.trim());
+
+    compare_parts(left_release, right_release).then_with(|| {
+        match (left_pre_release, right_pre_release) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(left), Some(right)) => compare_parts(left, right),
+        }
+    })
 }
 
 fn matches_purl_version_range_regex(value: &str) -> bool {
@@ -193,6 +350,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn it_should_check_if_a_version_range_contains_a_version() {
+        let range = VersionRange::new("vers:cargo/>=1.0.0|<1.2.0|!=1.1.1|>=2.0.0-rc.1");
+        assert!(range.contains("1.0.0"));
+        assert!(range.contains("1.1.10"));
+        assert!(!range.contains("1.1.1"));
+        assert!(!range.contains("1.2.0"));
+        assert!(!range.contains("0.9"));
+        assert!(!range.contains("2.0.0-beta"));
+        assert!(range.contains("2.0.0"));
+
+        assert!(VersionRange::new("vers:cargo/<=0.5|=1.0").contains("1.0.0"));
+        assert!(VersionRange::new("vers:cargo/*").contains("42"));
+        assert!(VersionRange::new("1.0").contains("1.0.0"));
+        assert!(!VersionRange::new("vers:cargo").contains("1.0"));
+    }
+
+    #[test]
+    fn it_should_check_if_a_target_is_affected_in_a_version() {
+        let mut target = VulnerabilityTarget::new("component".to_string());
+        assert!(target.affects(Some("1.0")));
+
+        target.versions = Some(Versions(vec![
+            Version::new("vers:cargo/<1.2", "affected"),
+            Version::new("1.1.3", "unaffected"),
+        ]));
+        assert!(target.affects(Some("1.1.2")));
+        assert!(!target.affects(Some("1.1.3")));
+        assert!(!target.affects(Some("1.2.0")));
+        assert!(target.affects(None));
+    }
+
     #[test]
     fn valid_vulnerability_targets_should_pass_validation() {
         let validation_result = VulnerabilityTargets(vec![VulnerabilityTarget {
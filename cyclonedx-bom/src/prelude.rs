@@ -16,6 +16,23 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+//! Re-exports the types needed to build, parse and validate a BOM, including the builders.
+//!
+//! ```
+//! use cyclonedx_bom::prelude::*;
+//!
+//! let component = ComponentBuilder::new(Classification::Application, "app")
+//!     .version("1.0.0")
+//!     .purl(Purl::cargo("app", "1.0.0").unwrap())
+//!     .build();
+//! let bom = Bom {
+//!     metadata: Some(MetadataBuilder::new().component(component).build()),
+//!     ..Bom::default()
+//! };
+//!
+//! assert!(bom.validate().passed());
+//! ```
+
 pub use crate::external_models::{
     date_time::DateTime,
     normalized_string::NormalizedString,
@@ -23,8 +40,8 @@ pub use crate::external_models::{
     uri::{Purl, Uri},
 };
 pub use crate::models::{
-    bom::{Bom, SpecVersion, UrnUuid},
-    component::{Component, Components},
-    metadata::Metadata,
+    bom::{Bom, JsonOutputOptions, OutputOptions, SpecVersion, UrnUuid},
+    component::{Classification, Component, ComponentBuilder, Components},
+    metadata::{Metadata, MetadataBuilder},
 };
 pub use crate::validation::{Validate, ValidationResult};
@@ -0,0 +1,507 @@
+/*
+ * This file is part of CycloneDX Rust Cargo.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Conversion of a [`Bom`] into a [SPDX 2.3](https://spdx.github.io/spdx-spec/v2.3/) JSON document.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+use time::{format_description::well_known::Iso8601, OffsetDateTime, UtcOffset};
+
+use crate::{
+    errors::ConvertError,
+    models::{
+        bom::{Bom, UrnUuid},
+        component::Component,
+        license::{LicenseChoice, LicenseIdentifier, Licenses},
+    },
+};
+
+const SPDX_VERSION: &str = "SPDX-2.3";
+const DOCUMENT_ID: &str = "SPDXRef-DOCUMENT";
+const NO_ASSERTION: &str = "NOASSERTION";
+
+pub(crate) fn bom_to_spdx_json(bom: &Bom) -> Result<Value, ConvertError> {
+    let root = bom
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.component.as_ref());
+    let top_level = bom
+        .components
+        .as_ref()
+        .map(|components| components.0.as_slice())
+        .unwrap_or_default();
+
+    let mut components = Vec::new();
+    if let Some(root) = root {
+        collect_components(root, &mut components);
+    }
+    for component in top_level {
+        collect_components(component, &mut components);
+    }
+
+    let spdx_ids = (1..=components.len())
+        .map(|index| format!("SPDXRef-Package-{index}"))
+        .collect::<Vec<_>>();
+    let ref_to_spdx_id = components
+        .iter()
+        .zip(&spdx_ids)
+        .filter_map(|(component, spdx_id)| {
+            component
+                .bom_ref
+                .as_deref()
+                .map(|bom_ref| (bom_ref, spdx_id.as_str()))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let packages = components
+        .iter()
+        .zip(&spdx_ids)
+        .map(|(component, spdx_id)| convert_component(component, spdx_id))
+        .collect::<Vec<_>>();
+
+    // The document describes the root component if there is one, otherwise all top level components.
+    let described = match root {
+        Some(_) => spdx_ids.iter().take(1).collect::<Vec<_>>(),
+        None => {
+            let mut index = 0;
+            top_level
+                .iter()
+                .map(|component| {
+                    let spdx_id = &spdx_ids[index];
+                    index += count_components(component);
+                    spdx_id
+                })
+                .collect()
+        }
+    };
+    let mut relationships = described
+        .into_iter()
+        .map(|spdx_id| relationship(DOCUMENT_ID, "DESCRIBES", spdx_id))
+        .collect::<Vec<_>>();
+
+    if let Some(dependencies) = &bom.dependencies {
+        let lookup = |bom_ref: &str| {
+            ref_to_spdx_id
+                .get(bom_ref)
+                .copied()
+                .ok_or_else(|| ConvertError::UnknownBomRef(bom_ref.to_string()))
+        };
+        for dependency in &dependencies.0 {
+            let element = lookup(&dependency.dependency_ref)?;
+            for sub_dependency in &dependency.dependencies {
+                relationships.push(relationship(element, "DEPENDS_ON", lookup(sub_dependency)?));
+            }
+        }
+    }
+
+    let created = match bom
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.timestamp.as_ref())
+    {
+        Some(timestamp) => format_timestamp(
+            OffsetDateTime::parse(timestamp.as_ref(), &Iso8601::DEFAULT).map_err(|error| {
+                ConvertError::InvalidTimestamp(timestamp.to_string(), error.to_string())
+            })?,
+        ),
+        None => format_timestamp(OffsetDateTime::now_utc()),
+    };
+
+    let name = root
+        .map(|component| component.name.to_string())
+        .unwrap_or_else(|| "cyclonedx-bom".to_string());
+    let namespace = bom
+        .serial_number
+        .clone()
+        .unwrap_or_else(UrnUuid::generate)
+        .to_string();
+
+    Ok(json!({
+        "spdxVersion": SPDX_VERSION,
+        "dataLicense": "CC0-1.0",
+        "SPDXID": DOCUMENT_ID,
+        "name": name,
+        "documentNamespace": namespace,
+        "creationInfo": {
+            "created": created,
+            "creators": [format!("Tool: cyclonedx-bom-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    }))
+}
+
+/// Flattens a component and all of its nested components in depth-first order.
+fn collect_components<'a>(component: &'a Component, collected: &mut Vec<&'a Component>) {
+    collected.push(component);
+    if let Some(nested) = &component.components {
+        for component in &nested.0 {
+            collect_components(component, collected);
+        }
+    }
+}
+
+fn count_components(component: &Component) -> usize {
+    1 + component
+        .components
+        .as_ref()
+        .map(|nested| nested.0.iter().map(count_components).sum::<usize>())
+        .unwrap_or_default()
+}
+
+fn convert_component(component: &Component, spdx_id: &str) -> Value {
+    let mut package = Map::new();
+    package.insert("SPDXID".to_string(), json!(spdx_id));
+    package.insert("name".to_string(), json!(component.name.to_string()));
+    if let Some(version) = &component.version {
+        package.insert("versionInfo".to_string(), json!(version.to_string()));
+    }
+    if let Some(description) = &component.description {
+        package.insert("description".to_string(), json!(description.to_string()));
+    }
+    package.insert("downloadLocation".to_string(), json!(NO_ASSERTION));
+    package.insert("filesAnalyzed".to_string(), json!(false));
+    package.insert("licenseConcluded".to_string(), json!(NO_ASSERTION));
+    package.insert(
+        "licenseDeclared".to_string(),
+        json!(component
+            .licenses
+            .as_ref()
+            .and_then(convert_licenses)
+            .unwrap_or_else(|| NO_ASSERTION.to_string())),
+    );
+    package.insert(
+        "copyrightText".to_string(),
+        json!(component
+            .copyright
+            .as_ref()
+            .map(|copyright| copyright.to_string())
+            .unwrap_or_else(|| NO_ASSERTION.to_string())),
+    );
+
+    let mut external_refs = Vec::new();
+    if let Some(purl) = &component.purl {
+        external_refs.push(json!({
+            "referenceCategory": "PACKAGE-MANAGER",
+            "referenceType": "purl",
+            "referenceLocator": purl.to_string(),
+        }));
+    }
+    if let Some(cpe) = &component.cpe {
+        let cpe = cpe.to_string();
+        let reference_type = if cpe.starts_with("cpe:2.3:") {
+            "cpe23Type"
+        } else {
+            "cpe22Type"
+        };
+        external_refs.push(json!({
+            "referenceCategory": "SECURITY",
+            "referenceType": reference_type,
+            "referenceLocator": cpe,
+        }));
+    }
+    if !external_refs.is_empty() {
+        package.insert("externalRefs".to_string(), Value::Array(external_refs));
+    }
+
+    Value::Object(package)
+}
+
+/// Combines all licenses into a single SPDX license expression.
+///
+/// Returns `None` if a license is only known by name, as SPDX cannot express it without a
+/// matching `LicenseRef` definition.
+fn convert_licenses(licenses: &Licenses) -> Option<String> {
+    let expressions = licenses
+        .0
+        .iter()
+        .map(|license| match license {
+            LicenseChoice::Expression(expression) => Some(expression.to_string()),
+            LicenseChoice::License(license) => match &license.license_identifier {
+                LicenseIdentifier::SpdxId(id) => Some(id.to_string()),
+                LicenseIdentifier::Name(_) => None,
+            },
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match expressions.as_slice() {
+        [] => None,
+        [expression] => Some(expression.clone()),
+        expressions => Some(
+            expressions
+                .iter()
+                .map(|expression| {
+                    if expression.contains(' ') {
+                        format!("({expression})")
+                    } else {
+                        expression.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" AND "),
+        ),
+    }
+}
+
+fn relationship(element: &str, relationship_type: &str, related_element: &str) -> Value {
+    json!({
+        "spdxElementId": element,
+        "relationshipType": relationship_type,
+        "relatedSpdxElement": related_element,
+    })
+}
+
+/// SPDX requires timestamps in UTC, formatted as `YYYY-MM-DDThh:mm:ssZ`.
+fn format_timestamp(timestamp: OffsetDateTime) -> String {
+    let utc = timestamp.to_offset(UtcOffset::UTC);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        utc.year(),
+        u8::from(utc.month()),
+        utc.day(),
+        utc.hour(),
+        utc.minute(),
+        utc.second()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use crate::{errors::ConvertError, models::bom::Bom};
+
+    const BOM: &str = r#"{
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "serialNumber": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+        "version": 1,
+        "metadata": {
+            "timestamp": "2020-04-13T20:20:39+02:00",
+            "component": {
+                "type": "application",
+                "bom-ref": "app",
+                "name": "app",
+                "version": "1.0.0",
+                "purl": "pkg:cargo/app@1.0.0"
+            }
+        },
+        "components": [
+            {
+                "type": "library",
+                "bom-ref": "lib-a",
+                "name": "lib-a",
+                "version": "0.1.0",
+                "purl": "pkg:cargo/lib-a@0.1.0",
+                "licenses": [{ "expression": "MIT OR Apache-2.0" }]
+            },
+            {
+                "type": "library",
+                "bom-ref": "lib-b",
+                "name": "lib-b",
+                "version": "2.0.0",
+                "licenses": [{ "license": { "id": "MIT" } }]
+            }
+        ],
+        "dependencies": [
+            { "ref": "app", "dependsOn": ["lib-a"] },
+            { "ref": "lib-a", "dependsOn": ["lib-b"] },
+            { "ref": "lib-b", "dependsOn": [] }
+        ]
+    }"#;
+
+    #[test]
+    fn it_should_convert_a_bom_into_an_spdx_document() {
+        let bom = Bom::parse_from_json_v1_5(BOM.as_bytes()).expect("Failed to parse BOM");
+
+        let actual = bom.to_spdx_json().expect("Failed to convert BOM");
+
+        assert_eq!(
+            actual,
+            json!({
+                "spdxVersion": "SPDX-2.3",
+                "dataLicense": "CC0-1.0",
+                "SPDXID": "SPDXRef-DOCUMENT",
+                "name": "app",
+                "documentNamespace": "urn:uuid:3e671687-395b-41f5-a30f-a58921a69b79",
+                "creationInfo": {
+                    "created": "2020-04-13T18:20:39Z",
+                    "creators": [format!("Tool: cyclonedx-bom-{}", env!("CARGO_PKG_VERSION"))],
+                },
+                "packages": [
+                    {
+                        "SPDXID": "SPDXRef-Package-1",
+                        "name": "app",
+                        "versionInfo": "1.0.0",
+                        "downloadLocation": "NOASSERTION",
+                        "filesAnalyzed": false,
+                        "licenseConcluded": "NOASSERTION",
+                        "licenseDeclared": "NOASSERTION",
+                        "copyrightText": "NOASSERTION",
+                        "externalRefs": [{
+                            "referenceCategory": "PACKAGE-MANAGER",
+                            "referenceType": "purl",
+                            "referenceLocator": "pkg:cargo/app@1.0.0",
+                        }],
+                    },
+                    {
+                        "SPDXID": "SPDXRef-Package-2",
+                        "name": "lib-a",
+                        "versionInfo": "0.1.0",
+                        "downloadLocation": "NOASSERTION",
+                        "filesAnalyzed": false,
+                        "licenseConcluded": "NOASSERTION",
+                        "licenseDeclared": "MIT OR Apache-2.0",
+                        "copyrightText": "NOASSERTION",
+                        "externalRefs": [{
+                            "referenceCategory": "PACKAGE-MANAGER",
+                            "referenceType": "purl",
+                            "referenceLocator": "pkg:cargo/lib-a@0.1.0",
+                        }],
+                    },
+                    {
+                        "SPDXID": "SPDXRef-Package-3",
+                        "name": "lib-b",
+                        "versionInfo": "2.0.0",
+                        "downloadLocation": "NOASSERTION",
+                        "filesAnalyzed": false,
+                        "licenseConcluded": "NOASSERTION",
+                        "licenseDeclared": "MIT",
+                        "copyrightText": "NOASSERTION",
+                    },
+                ],
+                "relationships": [
+                    {
+                        "spdxElementId": "SPDXRef-DOCUMENT",
+                        "relationshipType": "DESCRIBES",
+                        "relatedSpdxElement": "SPDXRef-Package-1",
+                    },
+                    {
+                        "spdxElementId": "SPDXRef-Package-1",
+                        "relationshipType": "DEPENDS_ON",
+                        "relatedSpdxElement": "SPDXRef-Package-2",
+                    },
+                    {
+                        "spdxElementId": "SPDXRef-Package-2",
+                        "relationshipType": "DEPENDS_ON",
+                        "relatedSpdxElement": "SPDXRef-Package-3",
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn it_should_only_declare_licenses_from_the_spdx_license_list() {
+        let bom = Bom::parse_from_json_v1_5(BOM.as_bytes()).expect("Failed to parse BOM");
+
+        let actual = bom.to_spdx_json().expect("Failed to convert BOM");
+
+        let packages = actual["packages"].as_array().expect("Missing packages");
+        let license_ids = packages
+            .iter()
+            .map(|package| &package["licenseDeclared"])
+            .filter(|license| !license.as_str().unwrap().contains(' '))
+            .filter(|license| license.as_str() != Some("NOASSERTION"))
+            .collect::<Vec<_>>();
+        assert!(!license_ids.is_empty());
+        for license_id in license_ids {
+            test_utils::validate_spdx_license_id(license_id)
+                .expect("License id is not part of the SPDX license list");
+        }
+    }
+
+    /// Checks the fields the SPDX 2.3 specification requires of a document, its packages and
+    /// relationships, as the bundled schema only covers license ids.
+    #[test]
+    fn it_should_set_the_required_fields_of_an_spdx_document() {
+        let bom = Bom::parse_from_json_v1_5(BOM.as_bytes()).expect("Failed to parse BOM");
+
+        let actual = bom.to_spdx_json().expect("Failed to convert BOM");
+
+        assert_eq!(actual["spdxVersion"], "SPDX-2.3");
+        assert_eq!(actual["dataLicense"], "CC0-1.0");
+        assert_eq!(actual["SPDXID"], "SPDXRef-DOCUMENT");
+        for field in ["name", "documentNamespace", "creationInfo/created"] {
+            let value = actual
+                .pointer(&format!("/{field}"))
+                .and_then(|v| v.as_str());
+            assert!(
+                value.is_some_and(|value| !value.is_empty()),
+                "{field} is missing"
+            );
+        }
+        let creators = actual["creationInfo"]["creators"].as_array().unwrap();
+        assert!(!creators.is_empty());
+
+        let is_spdx_id = |id: &serde_json::Value| {
+            id.as_str()
+                .and_then(|id| id.strip_prefix("SPDXRef-"))
+                .is_some_and(|id| {
+                    !id.is_empty()
+                        && id
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+                })
+        };
+        for package in actual["packages"].as_array().unwrap() {
+            assert!(is_spdx_id(&package["SPDXID"]), "{package}");
+            for field in ["name", "downloadLocation"] {
+                assert!(
+                    package[field].is_string(),
+                    "{field} is missing in {package}"
+                );
+            }
+        }
+        for relationship in actual["relationships"].as_array().unwrap() {
+            assert!(is_spdx_id(&relationship["spdxElementId"]), "{relationship}");
+            assert!(
+                is_spdx_id(&relationship["relatedSpdxElement"]),
+                "{relationship}"
+            );
+            assert!(
+                relationship["relationshipType"].is_string(),
+                "{relationship}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_should_fail_to_convert_a_dependency_on_an_unknown_ref() {
+        let input = r#"{
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.5",
+            "version": 1,
+            "components": [
+                { "type": "library", "bom-ref": "lib-a", "name": "lib-a" }
+            ],
+            "dependencies": [
+                { "ref": "lib-a", "dependsOn": ["missing"] }
+            ]
+        }"#;
+        let bom = Bom::parse_from_json_v1_5(input.as_bytes()).expect("Failed to parse BOM");
+
+        let actual = bom.to_spdx_json();
+
+        assert!(
+            matches!(actual, Err(ConvertError::UnknownBomRef(bom_ref)) if bom_ref == "missing")
+        );
+    }
+}
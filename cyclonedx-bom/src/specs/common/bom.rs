@@ -35,12 +35,12 @@ pub(crate) mod base {
     };
     use crate::{
         errors::BomError,
-        models::{self, bom::SpecVersion},
+        models::{self, bom::SpecVersion, extension::RawXml},
         utilities::{convert_optional, try_convert_optional},
         xml::{
-            expected_namespace_or_error, optional_attribute, read_lax_validation_tag,
-            to_xml_read_error, to_xml_write_error, unexpected_element_error, FromXml,
-            FromXmlDocument, FromXmlType,
+            expected_namespace_or_error, is_foreign_element, optional_attribute,
+            raw_xml_namespaces, read_lax_validation_tag, read_raw_xml_tag, to_xml_read_error,
+            to_xml_write_error, unexpected_element_error, FromXml, FromXmlDocument, FromXmlType,
         },
     };
     #[versioned("1.5")]
@@ -60,6 +60,8 @@ pub(crate) mod base {
 
     use crate::{specs::common::dependency::Dependencies, xml::ToXml};
     use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use std::collections::BTreeMap;
     use xml::{reader, writer::XmlEvent};
 
     #[versioned("1.3")]
@@ -76,6 +78,58 @@ pub(crate) mod base {
     #[versioned("1.5")]
     const NS: &str = "http://cyclonedx.org/schema/bom/1.5";
 
+    /// The top level JSON properties defined by the schema, other fields end up in `extra`.
+    #[versioned("1.3")]
+    pub(crate) const SCHEMA_FIELDS: &[&str] = &[
+        "bomFormat",
+        "specVersion",
+        "serialNumber",
+        "version",
+        "metadata",
+        "components",
+        "services",
+        "externalReferences",
+        "dependencies",
+        "compositions",
+    ];
+
+    /// The top level JSON properties defined by the schema, other fields end up in `extra`.
+    #[versioned("1.4")]
+    pub(crate) const SCHEMA_FIELDS: &[&str] = &[
+        "bomFormat",
+        "specVersion",
+        "serialNumber",
+        "version",
+        "metadata",
+        "components",
+        "services",
+        "externalReferences",
+        "dependencies",
+        "compositions",
+        "vulnerabilities",
+        "signature",
+    ];
+
+    /// The top level JSON properties defined by the schema, other fields end up in `extra`.
+    #[versioned("1.5")]
+    pub(crate) const SCHEMA_FIELDS: &[&str] = &[
+        "bomFormat",
+        "specVersion",
+        "serialNumber",
+        "version",
+        "metadata",
+        "components",
+        "services",
+        "externalReferences",
+        "dependencies",
+        "compositions",
+        "vulnerabilities",
+        "annotations",
+        "formulation",
+        "properties",
+        "signature",
+    ];
+
     #[derive(Debug, Deserialize, Serialize, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub(crate) struct Bom {
@@ -110,12 +164,38 @@ pub(crate) mod base {
         #[versioned("1.5")]
         #[serde(skip_serializing_if = "Option::is_none")]
         formulation: Option<Vec<Formula>>,
+        /// The `$schema` keyword is part of the JSON schema, it must not end up in `extra`.
+        #[serde(rename = "$schema", default, skip_serializing)]
+        schema: Option<String>,
+        /// Elements from other namespaces are only supported in XML.
+        #[serde(skip)]
+        foreign_elements: Vec<RawXml>,
+        #[serde(flatten)]
+        extra: BTreeMap<String, Value>,
     }
 
     impl TryFrom<models::bom::Bom> for Bom {
         type Error = BomError;
 
         fn try_from(other: models::bom::Bom) -> Result<Self, Self::Error> {
+            #[versioned("1.3", "1.4")]
+            if other.formulation.is_some() {
+                return Err(BomError::UnsupportedFieldError {
+                    spec_version: SPEC_VERSION,
+                    field: "formulation",
+                    model: "Bom",
+                });
+            }
+
+            // `extra` may hold fields of the newer spec version the BOM was read from.
+            #[versioned("1.3", "1.4")]
+            let mut other = other;
+            #[versioned("1.3", "1.4")]
+            if other.spec_version > SPEC_VERSION {
+                other.extra = Default::default();
+                other.all_components_mut(|component| component.extra = Default::default());
+            }
+
             Ok(Self {
                 bom_format: BomFormat::CycloneDX,
                 spec_version: SPEC_VERSION,
@@ -145,6 +225,9 @@ pub(crate) mod base {
                             .collect::<Result<Vec<_>, _>>()
                     })
                     .transpose()?,
+                schema: None,
+                foreign_elements: other.foreign_elements,
+                extra: other.extra.into(),
             })
         }
     }
@@ -181,6 +264,8 @@ pub(crate) mod base {
                 #[versioned("1.5")]
                 formulation: convert_optional_vec(other.formulation),
                 spec_version: other.spec_version,
+                foreign_elements: other.foreign_elements,
+                extra: other.extra.into(),
             }
         }
     }
@@ -197,6 +282,10 @@ pub(crate) mod base {
             let version = format!("{}", self.version);
             let mut bom_start_element = XmlEvent::start_element(BOM_TAG).default_ns(NS);
 
+            for (prefix, uri) in &raw_xml_namespaces(&self.foreign_elements)? {
+                bom_start_element = bom_start_element.ns(prefix, uri);
+            }
+
             if let Some(serial_number) = &self.serial_number {
                 bom_start_element = bom_start_element.attr(SERIAL_NUMBER_ATTR, &serial_number.0);
             }
@@ -246,6 +335,10 @@ pub(crate) mod base {
                 write_list_tag(writer, FORMULATION_TAG, formulation)?;
             }
 
+            for foreign_element in &self.foreign_elements {
+                foreign_element.write_xml_element(writer)?;
+            }
+
             writer
                 .write(XmlEvent::end_element())
                 .map_err(to_xml_write_error(BOM_TAG))?;
@@ -332,6 +425,7 @@ pub(crate) mod base {
             let mut properties: Option<Properties> = None;
             #[versioned("1.5")]
             let mut formulation: Option<Vec<Formula>> = None;
+            let mut foreign_elements = Vec::new();
 
             let mut got_end_tag = false;
             while !got_end_tag {
@@ -439,7 +533,18 @@ pub(crate) mod base {
                             Some(crate::xml::read_list_tag(event_reader, &name, FORMULA_TAG)?)
                     }
 
-                    // lax validation of any elements from a different schema
+                    // lax validation of any elements from a different schema, which are kept
+                    reader::XmlEvent::StartElement {
+                        name,
+                        attributes,
+                        namespace,
+                    } if is_foreign_element(&name) => foreign_elements.push(read_raw_xml_tag(
+                        event_reader,
+                        &name,
+                        &attributes,
+                        &namespace,
+                    )?),
+                    // elements of the CycloneDX schema not supported in this version are skipped
                     reader::XmlEvent::StartElement { name, .. } => {
                         read_lax_validation_tag(event_reader, &name)?
                     }
@@ -479,6 +584,9 @@ pub(crate) mod base {
                 properties,
                 #[versioned("1.5")]
                 formulation,
+                schema: None,
+                foreign_elements,
+                extra: BTreeMap::new(),
             })
         }
     }
@@ -576,6 +684,9 @@ pub(crate) mod base {
                 properties: None,
                 #[versioned("1.5")]
                 formulation: None,
+                schema: None,
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             }
         }
 
@@ -601,9 +712,18 @@ pub(crate) mod base {
                 properties: Some(example_properties()),
                 #[versioned("1.5")]
                 formulation: Some(vec![example_formula()]),
+                schema: None,
+                foreign_elements: vec![example_foreign_element()],
+                extra: Default::default(),
             }
         }
 
+        fn example_foreign_element() -> RawXml {
+            RawXml::new(
+                r#"<example:laxValidation xmlns:example="https://example.com"><example:innerElement id="test" /></example:laxValidation>"#,
+            )
+        }
+
         pub(crate) fn corresponding_internal_model() -> models::bom::Bom {
             models::bom::Bom {
                 version: 1,
@@ -635,6 +755,8 @@ pub(crate) mod base {
                 formulation: None,
                 #[versioned("1.5")]
                 formulation: Some(vec![corresponding_formula()]),
+                foreign_elements: vec![example_foreign_element()],
+                extra: Default::default(),
             }
         }
 
@@ -676,6 +798,30 @@ pub(crate) mod base {
             assert_eq!(spec, full_bom_example());
         }
 
+        #[versioned("1.3", "1.4")]
+        #[test]
+        fn it_should_fail_to_convert_a_bom_with_formulation() {
+            let model = models::bom::Bom {
+                formulation: Some(vec![models::formulation::Formula {
+                    bom_ref: None,
+                    components: None,
+                    services: None,
+                    workflows: None,
+                    properties: None,
+                }]),
+                ..Default::default()
+            };
+            let result: Result<Bom, _> = model.try_into();
+            assert!(matches!(
+                result,
+                Err(BomError::UnsupportedFieldError {
+                    spec_version: SPEC_VERSION,
+                    field: "formulation",
+                    model: "Bom",
+                })
+            ));
+        }
+
         #[test]
         fn it_should_deserialize_from_xml() {
             let input = format!(
@@ -689,6 +835,26 @@ pub(crate) mod base {
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn it_should_round_trip_foreign_elements() {
+            let foreign_element = r#"
+  <example:laxValidation>
+    <example:innerElement id="test" />
+  </example:laxValidation>
+"#;
+            let input = format!(
+                r#"
+<?xml version="1.0" encoding="utf-8"?>
+<bom xmlns="{NS}" xmlns:example="https://example.com" serialNumber="fake-uuid" version="1">{foreign_element}</bom>"#
+            );
+            let bom: Bom = read_document_from_string(input.trim_start());
+            assert_eq!(bom.foreign_elements, vec![example_foreign_element()]);
+
+            let output = write_element_to_string(bom);
+            assert_eq!(output, input.trim_start());
+            assert!(output.contains(foreign_element));
+        }
+
         #[test]
         fn it_should_deserialize_a_complex_example_from_xml() {
             #[versioned("1.3")]
@@ -44,7 +44,7 @@ pub(crate) mod base {
             normalized_string::NormalizedString,
             uri::{Purl, Uri},
         },
-        models::{self},
+        models::{self, extension::RawXml},
         specs::common::{
             attached_text::AttachedText,
             code::{Commits, Patches},
@@ -54,13 +54,16 @@ pub(crate) mod base {
         },
         utilities::{convert_optional, convert_vec, try_convert_optional, try_convert_vec},
         xml::{
-            attribute_or_error, optional_attribute, read_boolean_tag, read_lax_validation_list_tag,
-            read_lax_validation_tag, read_list_tag, read_simple_tag, to_xml_read_error,
-            to_xml_write_error, unexpected_element_error, write_close_tag, write_simple_tag,
-            write_start_tag, FromXml, FromXmlType, ToInnerXml, ToXml,
+            attribute_or_error, is_foreign_element, optional_attribute, read_boolean_tag,
+            read_lax_validation_list_tag, read_lax_validation_tag, read_list_tag, read_raw_xml_tag,
+            read_simple_tag, to_xml_read_error, to_xml_write_error, unexpected_element_error,
+            write_close_tag, write_simple_tag, write_start_tag, FromXml, FromXmlType, ToInnerXml,
+            ToXml,
         },
     };
     use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use std::collections::BTreeMap;
     use xml::{reader, writer::XmlEvent};
 
     #[derive(Debug, Deserialize, Serialize, PartialEq)]
@@ -123,6 +126,96 @@ pub(crate) mod base {
         }
     }
 
+    /// The JSON properties of a component defined by the schema, other fields end up in `extra`.
+    #[versioned("1.3")]
+    pub(crate) const SCHEMA_FIELDS: &[&str] = &[
+        "type",
+        "mime-type",
+        "bom-ref",
+        "supplier",
+        "author",
+        "publisher",
+        "group",
+        "name",
+        "version",
+        "description",
+        "scope",
+        "hashes",
+        "licenses",
+        "copyright",
+        "cpe",
+        "purl",
+        "swid",
+        "modified",
+        "pedigree",
+        "externalReferences",
+        "components",
+        "evidence",
+        "properties",
+    ];
+
+    /// The JSON properties of a component defined by the schema, other fields end up in `extra`.
+    #[versioned("1.4")]
+    pub(crate) const SCHEMA_FIELDS: &[&str] = &[
+        "type",
+        "mime-type",
+        "bom-ref",
+        "supplier",
+        "author",
+        "publisher",
+        "group",
+        "name",
+        "version",
+        "description",
+        "scope",
+        "hashes",
+        "licenses",
+        "copyright",
+        "cpe",
+        "purl",
+        "swid",
+        "modified",
+        "pedigree",
+        "externalReferences",
+        "components",
+        "evidence",
+        "releaseNotes",
+        "properties",
+        "signature",
+    ];
+
+    /// The JSON properties of a component defined by the schema, other fields end up in `extra`.
+    #[versioned("1.5")]
+    pub(crate) const SCHEMA_FIELDS: &[&str] = &[
+        "type",
+        "mime-type",
+        "bom-ref",
+        "supplier",
+        "author",
+        "publisher",
+        "group",
+        "name",
+        "version",
+        "description",
+        "scope",
+        "hashes",
+        "licenses",
+        "copyright",
+        "cpe",
+        "purl",
+        "swid",
+        "modified",
+        "pedigree",
+        "externalReferences",
+        "components",
+        "evidence",
+        "releaseNotes",
+        "modelCard",
+        "data",
+        "properties",
+        "signature",
+    ];
+
     #[derive(Debug, Deserialize, Serialize, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub(crate) struct Component {
@@ -183,6 +276,11 @@ pub(crate) mod base {
         #[versioned("1.5")]
         #[serde(skip_serializing_if = "Option::is_none")]
         pub(crate) data: Option<crate::specs::v1_5::component_data::ComponentData>,
+        /// Elements from other namespaces are only supported in XML.
+        #[serde(skip)]
+        pub(crate) foreign_elements: Vec<RawXml>,
+        #[serde(flatten)]
+        pub(crate) extra: BTreeMap<String, Value>,
     }
 
     impl TryFrom<models::component::Component> for Component {
@@ -228,6 +326,8 @@ pub(crate) mod base {
                 model_card: convert_optional(other.model_card),
                 #[versioned("1.5")]
                 data: convert_optional(other.data),
+                foreign_elements: other.foreign_elements,
+                extra: other.extra.into(),
             })
         }
     }
@@ -275,6 +375,8 @@ pub(crate) mod base {
                 data: None,
                 #[versioned("1.5")]
                 data: convert_optional(other.data),
+                foreign_elements: other.foreign_elements,
+                extra: other.extra.into(),
             }
         }
     }
@@ -419,6 +521,10 @@ pub(crate) mod base {
                 data.write_xml_named_element(writer, COMPONENT_DATA_TAG)?;
             }
 
+            for foreign_element in &self.foreign_elements {
+                foreign_element.write_xml_element(writer)?;
+            }
+
             writer
                 .write(XmlEvent::end_element())
                 .map_err(to_xml_write_error(COMPONENT_TAG))?;
@@ -462,6 +568,7 @@ pub(crate) mod base {
             let mut purl: Option<String> = None;
             let mut swid: Option<Swid> = None;
             let mut modified: Option<bool> = None;
+            let mut foreign_elements = Vec::new();
             let mut pedigree: Option<Pedigree> = None;
             let mut external_references: Option<ExternalReferences> = None;
             let mut properties: Option<Properties> = None;
@@ -634,7 +741,17 @@ pub(crate) mod base {
                         )
                     }
 
-                    // lax validation of any elements from a different schema
+                    // lax validation of any elements from a different schema, which are kept
+                    reader::XmlEvent::StartElement {
+                        name,
+                        attributes,
+                        namespace,
+                    } if is_foreign_element(&name) => foreign_elements.push(read_raw_xml_tag(
+                        event_reader,
+                        &name,
+                        &attributes,
+                        &namespace,
+                    )?),
                     reader::XmlEvent::StartElement { name, .. } => {
                         read_lax_validation_tag(event_reader, &name)?
                     }
@@ -687,6 +804,8 @@ pub(crate) mod base {
                 model_card,
                 #[versioned("1.5")]
                 data,
+                foreign_elements,
+                extra: BTreeMap::new(),
             })
         }
     }
@@ -1466,6 +1585,8 @@ pub(crate) mod base {
                 model_card: Some(example_modelcard()),
                 #[versioned("1.5")]
                 data: Some(example_component_data()),
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             }
         }
 
@@ -1508,6 +1629,8 @@ pub(crate) mod base {
                 data: None,
                 #[versioned("1.5")]
                 data: Some(corresponding_component_data()),
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             }
         }
 
@@ -2062,6 +2185,36 @@ pub(crate) mod base {
             assert_eq!(actual, expected);
         }
 
+        #[test]
+        fn it_should_round_trip_foreign_elements() {
+            let input = r#"
+<component type="library">
+  <name>name</name>
+  <version>1.0.0</version>
+  <vendor:build xmlns:vendor="https://example.com/vendor" id="42">
+    <vendor:pipeline>release</vendor:pipeline>
+  </vendor:build>
+</component>
+"#
+            .trim();
+            let component: Component = read_element_from_string(input);
+            assert_eq!(
+                component.foreign_elements,
+                vec![RawXml::new(
+                    r#"<vendor:build xmlns:vendor="https://example.com/vendor" id="42"><vendor:pipeline>release</vendor:pipeline></vendor:build>"#
+                )]
+            );
+
+            let model: models::component::Component = component.into();
+            let output = write_element_to_string(Component::try_from(model).unwrap());
+            assert_eq!(
+                output
+                    .trim_start_matches(r#"<?xml version="1.0" encoding="utf-8"?>"#)
+                    .trim(),
+                input
+            );
+        }
+
         #[test]
         #[versioned("1.3")]
         fn it_should_fail_conversion_without_version_field() {
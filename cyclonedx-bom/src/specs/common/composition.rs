@@ -21,7 +21,6 @@ use cyclonedx_bom_macros::versioned;
 #[versioned("1.3", "1.4", "1.5")]
 pub(crate) mod base {
     use crate::{
-        errors::XmlReadError,
         models,
         specs::common::bom_reference::BomReference,
         utilities::{convert_optional_vec, convert_vec},
@@ -91,6 +90,8 @@ pub(crate) mod base {
         #[versioned("1.5")]
         #[serde(skip_serializing_if = "Option::is_none")]
         bom_ref: Option<String>,
+        /// Required by the spec, but kept lenient so that a missing value is reported by validation
+        #[serde(default)]
         aggregate: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         assemblies: Option<Vec<BomReference>>,
@@ -294,10 +295,8 @@ pub(crate) mod base {
                 }
             }
 
-            let aggregate = aggregate.ok_or_else(|| XmlReadError::RequiredDataMissing {
-                required_field: AGGREGATE_TAG.to_string(),
-                element: COMPOSITION_TAG.to_string(),
-            })?;
+            // A missing aggregate is reported by validation instead of failing to parse
+            let aggregate = aggregate.unwrap_or_default();
 
             Ok(Self {
                 #[versioned("1.5")]
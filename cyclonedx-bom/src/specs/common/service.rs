@@ -541,10 +541,11 @@ pub(crate) mod base {
     #[derive(Debug, Deserialize, Serialize, PartialEq)]
     #[serde(rename_all = "camelCase", untagged)]
     pub(crate) enum Data {
-        /// Legacy entry type until version 1.4
-        Classification(Vec<DataClassification>),
+        /// Tried first, as a legacy classification would otherwise swallow its extra fields
         #[versioned("1.5")]
         ServiceData(Vec<ServiceData>),
+        /// Legacy entry type until version 1.4
+        Classification(Vec<DataClassification>),
     }
 
     impl From<models::service::Data> for Data {
@@ -28,11 +28,24 @@ use crate::{
     },
 };
 
-/// For now the [`Signer`] struct only holds algorithm and value
+/// A single JSF signer, XML only holds algorithm and value
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct Signer {
     /// Signature algorithm.
     pub algorithm: Algorithm,
+    /// Application specific string identifying the signature key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    /// The public key the signature can be verified with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<JsonWebKey>,
+    /// Base64url encoded X.509 certificates, the signing certificate first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate_path: Option<Vec<String>>,
+    /// Properties of the signed object not covered by the signature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excludes: Option<Vec<String>>,
     /// The signature data.
     pub value: String,
 }
@@ -41,11 +54,56 @@ impl Signer {
     pub fn new(algorithm: &str, value: &str) -> Self {
         Self {
             algorithm: Algorithm::new_unchecked(algorithm),
+            key_id: None,
+            public_key: None,
+            certificate_path: None,
+            excludes: None,
             value: value.to_string(),
         }
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct JsonWebKey {
+    pub kty: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+}
+
+impl From<models::signature::JsonWebKey> for JsonWebKey {
+    fn from(other: models::signature::JsonWebKey) -> Self {
+        Self {
+            kty: other.kty,
+            crv: other.crv,
+            x: other.x,
+            y: other.y,
+            n: other.n,
+            e: other.e,
+        }
+    }
+}
+
+impl From<JsonWebKey> for models::signature::JsonWebKey {
+    fn from(other: JsonWebKey) -> Self {
+        Self {
+            kty: other.kty,
+            crv: other.crv,
+            x: other.x,
+            y: other.y,
+            n: other.n,
+            e: other.e,
+        }
+    }
+}
+
 impl ToXml for Signer {
     fn write_xml_element<W: std::io::prelude::Write>(
         &self,
@@ -101,10 +159,7 @@ impl FromXml for Signer {
             element: element_name.local_name.to_string(),
         })?;
 
-        Ok(Self {
-            algorithm: Algorithm::new_unchecked(algorithm.as_str()),
-            value,
-        })
+        Ok(Self::new(&algorithm, &value))
     }
 }
 
@@ -165,6 +220,10 @@ impl From<models::signature::Signer> for Signer {
     fn from(signer: models::signature::Signer) -> Self {
         Self {
             algorithm: signer.algorithm.into(),
+            key_id: signer.key_id,
+            public_key: signer.public_key.map(Into::into),
+            certificate_path: signer.certificate_path,
+            excludes: signer.excludes,
             value: signer.value,
         }
     }
@@ -174,6 +233,10 @@ impl From<Signer> for models::signature::Signer {
     fn from(signer: Signer) -> Self {
         Self {
             algorithm: signer.algorithm.into(),
+            key_id: signer.key_id,
+            public_key: signer.public_key.map(Into::into),
+            certificate_path: signer.certificate_path,
+            excludes: signer.excludes,
             value: signer.value,
         }
     }
@@ -581,6 +581,8 @@ pub(crate) mod base {
                 signature: None,
                 model_card: None,
                 data: None,
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             };
             let expected = Tools::Object {
                 services: Some(Services(vec![service])),
@@ -165,6 +165,8 @@ pub(crate) mod test {
                 signature: None,
                 model_card: None,
                 data: None,
+                foreign_elements: Vec::new(),
+                extra: Default::default(),
             }])),
             services: None,
             workflows: None,
@@ -203,6 +205,8 @@ pub(crate) mod test {
                     signature: None,
                     model_card: None,
                     data: None,
+                    foreign_elements: Vec::new(),
+                    extra: Default::default(),
                 },
             ])),
             services: None,
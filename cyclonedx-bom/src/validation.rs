@@ -16,6 +16,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 use std::{
+    cell::Cell,
     collections::{BTreeMap, HashSet},
     fmt::Display,
     hash::Hash,
@@ -28,11 +29,79 @@ use indexmap::{
 
 use crate::models::bom::SpecVersion;
 
+thread_local! {
+    /// The errors that may still be collected on this thread, set while [`validate_capped`] runs.
+    static ERROR_BUDGET: Cell<Option<ErrorBudget>> = const { Cell::new(None) };
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ErrorBudget {
+    remaining: usize,
+    exhausted: bool,
+}
+
+/// Restores the error budget of an enclosing [`validate_capped`] call, even on panics.
+struct ErrorBudgetGuard(Option<ErrorBudget>);
+
+impl Drop for ErrorBudgetGuard {
+    fn drop(&mut self) {
+        ERROR_BUDGET.with(|budget| budget.set(self.0));
+    }
+}
+
+/// Runs `validate`, collecting at most `max_errors` errors on this thread.
+///
+/// Validation stops once another error would exceed the cap, the result is then marked as
+/// truncated. Warnings are only collected until then as well.
+pub(crate) fn validate_capped(
+    max_errors: usize,
+    validate: impl FnOnce() -> ValidationResult,
+) -> ValidationResult {
+    let budget = ErrorBudget {
+        remaining: max_errors,
+        exhausted: false,
+    };
+    let _guard = ErrorBudgetGuard(ERROR_BUDGET.with(|cell| cell.replace(Some(budget))));
+    let mut result = validate();
+    result.truncated |= is_error_budget_exhausted();
+    result
+}
+
+/// Returns `true` if a capped validation already had to leave out an error.
+fn is_error_budget_exhausted() -> bool {
+    ERROR_BUDGET.with(|cell| cell.get().is_some_and(|budget| budget.exhausted))
+}
+
+/// Takes one error from the budget, returns `false` if the error has to be left out.
+fn take_error_budget() -> bool {
+    ERROR_BUDGET.with(|cell| match cell.get() {
+        None => true,
+        Some(budget) if budget.remaining > 0 => {
+            cell.set(Some(ErrorBudget {
+                remaining: budget.remaining - 1,
+                ..budget
+            }));
+            true
+        }
+        Some(budget) => {
+            cell.set(Some(ErrorBudget {
+                exhausted: true,
+                ..budget
+            }));
+            false
+        }
+    })
+}
+
 /// Contains all collected validation errors.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValidationResult {
     /// Maps names to validation errors.
     pub(crate) inner: IndexMap<String, ValidationErrorsKind>,
+    /// Maps the paths of elements to warnings, these do not fail the validation.
+    pub(crate) warnings: IndexMap<String, Vec<ValidationError>>,
+    /// Whether errors were left out because of a cap, see [`validate_capped`].
+    pub(crate) truncated: bool,
 }
 
 impl Default for ValidationResult {
@@ -45,7 +114,9 @@ impl From<Vec<ValidationResult>> for ValidationResult {
     fn from(errors: Vec<ValidationResult>) -> Self {
         // merge all errors into one struct.
         let mut result = ValidationResult::new();
-        for error in errors.into_iter() {
+        for mut error in errors.into_iter() {
+            result.merge_warnings("", &mut error);
+            result.truncated |= error.truncated;
             for (key, value) in error.inner.into_iter() {
                 result.inner.insert(key, value);
             }
@@ -71,6 +142,8 @@ impl ValidationResult {
     pub fn new() -> Self {
         Self {
             inner: IndexMap::new(),
+            warnings: IndexMap::new(),
+            truncated: false,
         }
     }
 
@@ -98,6 +171,115 @@ impl ValidationResult {
         self.inner.into_iter()
     }
 
+    /// Returns the number of individual errors, including those of nested elements.
+    pub fn error_count(&self) -> usize {
+        self.inner
+            .values()
+            .map(|kind| match kind {
+                ValidationErrorsKind::Struct(result) => result.error_count(),
+                ValidationErrorsKind::List(results) => {
+                    results.values().map(ValidationResult::error_count).sum()
+                }
+                ValidationErrorsKind::Field(errors) | ValidationErrorsKind::Custom(errors) => {
+                    errors.len()
+                }
+                ValidationErrorsKind::Enum(_) => 1,
+            })
+            .sum()
+    }
+
+    /// Returns `true` if errors were left out because the number of errors was capped.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Moves all errors about values unknown to the spec, e.g. an unknown component type, to the
+    /// warnings.
+    pub(crate) fn downgrade_unknown_values(&mut self) {
+        let mut downgraded = Vec::new();
+        self.take_errors("", &ValidationError::is_unknown_value, &mut downgraded);
+        for (path, error) in downgraded {
+            self.add_warning(&path, error);
+        }
+    }
+
+    /// Removes all errors matching `predicate`, collecting them with the path of their element.
+    fn take_errors(
+        &mut self,
+        prefix: &str,
+        predicate: &impl Fn(&ValidationError) -> bool,
+        taken: &mut Vec<(String, ValidationError)>,
+    ) {
+        let path = |name: &str| match prefix.is_empty() {
+            true => name.to_string(),
+            false => format!("{prefix}.{name}"),
+        };
+        self.inner.retain(|name, kind| match kind {
+            ValidationErrorsKind::Struct(result) => {
+                result.take_errors(&path(name), predicate, taken);
+                result.has_errors()
+            }
+            ValidationErrorsKind::List(results) => {
+                results.retain(|index, result| {
+                    result.take_errors(&format!("{}[{index}]", path(name)), predicate, taken);
+                    result.has_errors()
+                });
+                !results.is_empty()
+            }
+            ValidationErrorsKind::Field(errors) | ValidationErrorsKind::Custom(errors) => {
+                errors.retain(|error| {
+                    if predicate(error) {
+                        taken.push((path(name), error.clone()));
+                    }
+                    !predicate(error)
+                });
+                !errors.is_empty()
+            }
+            ValidationErrorsKind::Enum(error) => {
+                if predicate(error) {
+                    taken.push((path(name), error.clone()));
+                }
+                !predicate(error)
+            }
+        });
+    }
+
+    /// Returns `true` if there are warnings.
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Returns an Iterator over all warnings together with the path of the element they refer to.
+    ///
+    /// Warnings point out questionable content, but do not fail the validation.
+    pub fn warnings(&self) -> impl Iterator<Item = (&str, &ValidationError)> {
+        self.warnings
+            .iter()
+            .flat_map(|(path, warnings)| warnings.iter().map(move |w| (path.as_str(), w)))
+    }
+
+    /// Adds a warning for the element at the given path.
+    fn add_warning(&mut self, path: &str, warning: ValidationError) {
+        self.warnings
+            .entry(path.to_string())
+            .or_default()
+            .push(warning);
+    }
+
+    /// Moves all warnings of a nested result into this one, prefixing their paths.
+    fn merge_warnings(&mut self, prefix: &str, nested: &mut ValidationResult) {
+        for (path, warnings) in nested.warnings.drain(..) {
+            let path = match (prefix.is_empty(), path.is_empty()) {
+                (true, _) => path,
+                (false, true) => prefix.to_string(),
+                (false, false) => format!("{prefix}.{path}"),
+            };
+            for warning in warnings {
+                self.add_warning(&path, warning);
+            }
+        }
+    }
+
     /// Adds a nested object kind
     fn add_nested(&mut self, nested_name: &str, errors_kind: ValidationErrorsKind) {
         if let Vacant(entry) = self.inner.entry(nested_name.to_string()) {
@@ -109,6 +291,9 @@ impl ValidationResult {
 
     /// Adds a single [`ValidationError`] for an enum variant.
     fn add_enum(&mut self, enum_name: &str, validation_error: ValidationError) {
+        if !take_error_budget() {
+            return;
+        }
         if let Vacant(entry) = self.inner.entry(enum_name.to_string()) {
             entry.insert(ValidationErrorsKind::Enum(validation_error));
         } else {
@@ -118,6 +303,9 @@ impl ValidationResult {
 
     /// Adds a single field [`ValidationError`].
     fn add_field(&mut self, field_name: &str, validation_error: ValidationError) {
+        if !take_error_budget() {
+            return;
+        }
         if let ValidationErrorsKind::Field(ref mut vec) = self
             .inner
             .entry(field_name.to_string())
@@ -131,6 +319,9 @@ impl ValidationResult {
 
     /// Adds a list of validation errors for a custom entry.
     fn add_custom(&mut self, custom_name: &str, validation_error: ValidationError) {
+        if !take_error_budget() {
+            return;
+        }
         if let ValidationErrorsKind::Custom(ref mut vec) = self
             .inner
             .entry(custom_name.to_string())
@@ -143,6 +334,25 @@ impl ValidationResult {
     }
 }
 
+/// Options to adjust how strictly a BOM is validated, see
+/// [`Bom::validate_with_options`](crate::models::bom::Bom::validate_with_options).
+///
+/// The defaults match [`Validate::validate`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValidationOptions {
+    /// Report values unknown to the spec version, e.g. an unknown component type, as warnings
+    /// instead of errors.
+    pub treat_unknown_enums_as_warnings: bool,
+    /// Report a missing serial number as an error.
+    pub require_serial_number: bool,
+    /// Warn about external references linking to the same serial number with a version that is
+    /// not older than the BOM's own version.
+    pub check_bom_link_versions: bool,
+    /// Warn about timestamps in the metadata and formulation that lie in the future.
+    pub disallow_future_timestamps: bool,
+}
+
 /// Collects validation results in a hierarchy, recommended to use in `Validate` implementations.
 #[derive(Debug)]
 pub struct ValidationContext {
@@ -168,6 +378,9 @@ impl ValidationContext {
         field: T,
         validation: impl FnOnce(T) -> Result<(), ValidationError>,
     ) -> &mut Self {
+        if is_error_budget_exhausted() {
+            return self;
+        }
         if let Err(validation_error) = validation(field) {
             self.state.add_field(field_name, validation_error);
         }
@@ -192,6 +405,9 @@ impl ValidationContext {
         enum_type: &T,
         validation: impl FnOnce(&T) -> Result<(), ValidationError>,
     ) -> &mut Self {
+        if is_error_budget_exhausted() {
+            return self;
+        }
         if let Err(error) = validation(enum_type) {
             self.state.add_enum(enum_name, error);
         }
@@ -223,9 +439,12 @@ impl ValidationContext {
     {
         let child_errors = list
             .into_iter()
+            .take_while(|_| !is_error_budget_exhausted())
             .map(|item| validation(item).into())
             .enumerate()
-            .filter_map(|(index, result)| {
+            .filter_map(|(index, mut result)| {
+                self.state
+                    .merge_warnings(&format!("{field_name}[{index}]"), &mut result);
                 if result.has_errors() {
                     Some((index, result))
                 } else {
@@ -255,10 +474,15 @@ impl ValidationContext {
         let mut child_errors = BTreeMap::new();
 
         for (index, item) in list.into_iter().enumerate() {
+            if is_error_budget_exhausted() {
+                break;
+            }
             if !set.insert(item) {
                 child_errors.insert(index, Err(ValidationError::new("repeated element")).into());
             } else {
-                let result = validation(item).into();
+                let mut result = validation(item).into();
+                self.state
+                    .merge_warnings(&format!("{field_name}[{index}]"), &mut result);
                 if result.has_errors() {
                     child_errors.insert(index, result);
                 }
@@ -315,7 +539,11 @@ impl ValidationContext {
     where
         T: Validate,
     {
-        let result = r#struct.validate_version(version);
+        if is_error_budget_exhausted() {
+            return self;
+        }
+        let mut result = r#struct.validate_version(version);
+        self.state.merge_warnings(struct_name, &mut result);
         if result.has_errors() {
             self.state
                 .add_nested(struct_name, ValidationErrorsKind::Struct(result));
@@ -347,6 +575,12 @@ impl ValidationContext {
         self.state.add_custom(custom_name, error.into());
         self
     }
+
+    /// Adds a warning, which points out questionable content without failing the validation.
+    pub fn add_warning(&mut self, path: &str, warning: impl Into<ValidationError>) -> &mut Self {
+        self.state.add_warning(path, warning.into());
+        self
+    }
 }
 
 impl From<ValidationContext> for ValidationResult {
@@ -371,14 +605,19 @@ pub trait Validate {
 }
 
 /// A single validation error with a message, useful to log / display for user.
+///
+/// Create it with [`ValidationError::new`] or [`ValidationError::unknown_value`], it cannot be
+/// built as a struct literal.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ValidationError {
     pub message: String,
+    /// Set for errors about values unknown to the spec, see [`ValidationError::unknown_value`].
+    unknown_value: bool,
 }
 
 impl From<String> for ValidationError {
     fn from(message: String) -> Self {
-        ValidationError { message }
+        ValidationError::new(message)
     }
 }
 
@@ -392,8 +631,32 @@ impl ValidationError {
     pub fn new<D: Display>(message: D) -> Self {
         Self {
             message: message.to_string(),
+            unknown_value: false,
         }
     }
+
+    /// Creates an error about a value that is unknown to the spec, e.g. an unknown component
+    /// type. These are reported as warnings with
+    /// [`ValidationOptions::treat_unknown_enums_as_warnings`].
+    pub fn unknown_value<D: Display>(message: D) -> Self {
+        Self {
+            message: message.to_string(),
+            unknown_value: true,
+        }
+    }
+
+    /// Returns `true` if the error is about a value that is unknown to the spec.
+    pub fn is_unknown_value(&self) -> bool {
+        self.unknown_value
+    }
+}
+
+/// Checks that a required string field contains more than whitespace.
+pub(crate) fn validate_non_empty(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("Required string must not be empty"));
+    }
+    Ok(())
 }
 
 /// Implements possible hierarchy of a structured SBOM to collect all [`ValidationError`] in.
@@ -470,11 +733,26 @@ where
     result
 }
 
+#[cfg(test)]
+pub(crate) fn warning<I, T>(path: &str, warnings: I) -> ValidationResult
+where
+    I: IntoIterator<Item = T>,
+    T: Into<ValidationError>,
+{
+    let mut result = ValidationResult::default();
+    for warning in warnings {
+        result.add_warning(path, warning.into());
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         models::bom::SpecVersion,
-        validation::{field, r#enum, r#struct, Validate, ValidationErrorsKind, ValidationResult},
+        validation::{
+            field, r#enum, r#struct, warning, Validate, ValidationErrorsKind, ValidationResult,
+        },
     };
 
     use super::{ValidationContext, ValidationError};
@@ -542,4 +820,61 @@ mod tests {
             .into()
         );
     }
+
+    #[test]
+    fn nested_warnings_keep_their_path_without_failing() {
+        struct Nested;
+
+        impl Validate for Nested {
+            fn validate_version(&self, _version: SpecVersion) -> ValidationResult {
+                ValidationContext::new()
+                    .add_warning("name", "questionable")
+                    .into()
+            }
+        }
+
+        let validation_result: ValidationResult = ValidationContext::new()
+            .add_struct("nested", &Nested, SpecVersion::V1_3)
+            .add_list("items", &[Nested, Nested], |item| {
+                item.validate_version(SpecVersion::V1_3)
+            })
+            .into();
+
+        assert!(validation_result.passed());
+        assert!(validation_result.has_warnings());
+        assert_eq!(
+            validation_result,
+            vec![
+                warning("nested.name", ["questionable"]),
+                warning("items[0].name", ["questionable"]),
+                warning("items[1].name", ["questionable"]),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn capped_validation_stops_collecting_errors() {
+        let validated = std::cell::Cell::new(0);
+        let validate = |_: &usize| {
+            validated.set(validated.get() + 1);
+            Err(ValidationError::new("invalid"))
+        };
+
+        let validation_result = super::validate_capped(2, || {
+            ValidationContext::new()
+                .add_list("items", &[0, 1, 2, 3, 4], |item| -> ValidationResult {
+                    ValidationContext::new()
+                        .add_field("value", item, validate)
+                        .into()
+                })
+                .add_field("name", &0, validate)
+                .into()
+        });
+
+        assert!(validation_result.is_truncated());
+        assert_eq!(validation_result.error_count(), 2);
+        assert_eq!(validated.get(), 3);
+        assert!(!super::is_error_budget_exhausted());
+    }
 }
@@ -1,12 +1,14 @@
 use crate::errors::{XmlReadError, XmlWriteError};
-use std::io::{Read, Write};
+use crate::models::extension::RawXml;
+use std::borrow::Cow;
+use std::io::{BufRead, Read, Write};
 use xml::{
     attribute::OwnedAttribute,
     name::OwnedName,
-    namespace::{Namespace, NS_NO_PREFIX},
+    namespace::{Namespace, NS_NO_PREFIX, NS_XMLNS_PREFIX, NS_XML_PREFIX},
     reader::{self},
     writer::{self, EventWriter, XmlEvent},
-    EventReader,
+    EmitterConfig, EventReader, ParserConfig,
 };
 
 pub(crate) trait ToXml {
@@ -674,6 +676,153 @@ pub(crate) fn read_lax_validation_tag<R: Read>(
     Ok(())
 }
 
+/// Reads an element from a different schema, including all of its children, into a raw XML
+/// fragment. Namespaces in scope are declared on the fragment as needed, except for the CycloneDX
+/// namespaces, so that the fragment can be written into a document of another version.
+pub(crate) fn read_raw_xml_tag<R: Read>(
+    event_reader: &mut EventReader<R>,
+    element: &OwnedName,
+    attributes: &[OwnedAttribute],
+    namespace: &Namespace,
+) -> Result<RawXml, XmlReadError> {
+    let to_read_error = |error: writer::Error| XmlReadError::UnexpectedElementReadError {
+        error: error.to_string(),
+        element: element.local_name.clone(),
+    };
+
+    let config = EmitterConfig::default()
+        .write_document_declaration(false)
+        .perform_indent(false);
+    let mut fragment_writer = EventWriter::new_with_config(Vec::new(), config);
+
+    write_raw_start_element(&mut fragment_writer, element, attributes, namespace)
+        .map_err(to_read_error)?;
+
+    let mut depth = 0;
+    loop {
+        let next_element = event_reader
+            .next()
+            .map_err(to_xml_read_error(&element.local_name))?;
+
+        match next_element {
+            reader::XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                depth += 1;
+                write_raw_start_element(&mut fragment_writer, &name, &attributes, &namespace)
+                    .map_err(to_read_error)?;
+            }
+            reader::XmlEvent::EndElement { name } => {
+                fragment_writer
+                    .write(XmlEvent::end_element().name(name.borrow()))
+                    .map_err(to_read_error)?;
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            reader::XmlEvent::Whitespace(_) => (),
+            unexpected @ (reader::XmlEvent::StartDocument { .. }
+            | reader::XmlEvent::EndDocument) => {
+                return Err(unexpected_element_error(element, unexpected))
+            }
+            other => {
+                if let Some(event) = other.as_writer_event() {
+                    fragment_writer.write(event).map_err(to_read_error)?;
+                }
+            }
+        }
+    }
+
+    Ok(RawXml(
+        String::from_utf8_lossy(&fragment_writer.into_inner()).into_owned(),
+    ))
+}
+
+fn write_raw_start_element<W: Write>(
+    writer: &mut EventWriter<W>,
+    element: &OwnedName,
+    attributes: &[OwnedAttribute],
+    namespace: &Namespace,
+) -> Result<(), writer::Error> {
+    let mut foreign_namespace = Namespace::empty();
+    for (prefix, uri) in namespace {
+        if !uri.starts_with(CYCLONEDX_NAMESPACE_PREFIX) {
+            foreign_namespace.put(prefix, uri);
+        }
+    }
+
+    writer.write(XmlEvent::StartElement {
+        name: element.borrow(),
+        attributes: attributes.iter().map(OwnedAttribute::borrow).collect(),
+        namespace: Cow::Owned(foreign_namespace),
+    })
+}
+
+const CYCLONEDX_NAMESPACE_PREFIX: &str = "http://cyclonedx.org/schema/";
+
+/// Returns `true` if the element belongs to a namespace other than one of the CycloneDX schemas.
+/// Elements without any namespace are considered part of the CycloneDX schema.
+pub(crate) fn is_foreign_element(name: &OwnedName) -> bool {
+    name.namespace
+        .as_deref()
+        .is_some_and(|namespace| !namespace.starts_with(CYCLONEDX_NAMESPACE_PREFIX))
+}
+
+fn read_raw_xml_events(fragment: &RawXml) -> Result<Vec<reader::XmlEvent>, XmlWriteError> {
+    let config = ParserConfig::default().trim_whitespace(true);
+    EventReader::new_with_config(fragment.0.as_bytes(), config)
+        .into_iter()
+        .filter(|event| {
+            !matches!(
+                event,
+                Ok(reader::XmlEvent::StartDocument { .. } | reader::XmlEvent::EndDocument)
+            )
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|error| XmlWriteError::RawXmlReadError { error })
+}
+
+/// Collects the namespaces declared on the root elements of the given fragments, so that they can
+/// be declared once on the enclosing element.
+pub(crate) fn raw_xml_namespaces(fragments: &[RawXml]) -> Result<Namespace, XmlWriteError> {
+    let mut namespaces = Namespace::empty();
+    for fragment in fragments {
+        if let Some(reader::XmlEvent::StartElement { namespace, .. }) =
+            read_raw_xml_events(fragment)?.first()
+        {
+            for (prefix, uri) in namespace {
+                if !matches!(prefix, NS_NO_PREFIX | NS_XML_PREFIX | NS_XMLNS_PREFIX) {
+                    namespaces.put(prefix, uri);
+                }
+            }
+        }
+    }
+
+    Ok(namespaces)
+}
+
+impl ToXml for RawXml {
+    fn write_xml_element<W: Write>(
+        &self,
+        writer: &mut EventWriter<W>,
+    ) -> Result<(), XmlWriteError> {
+        for event in read_raw_xml_events(self)? {
+            if let Some(event) = event.as_writer_event() {
+                writer
+                    .write(event)
+                    .map_err(to_xml_write_error(RAW_XML_ELEMENT))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const RAW_XML_ELEMENT: &str = "raw XML";
+
 pub(crate) fn read_lax_validation_list_tag<R: Read, X: FromXml>(
     event_reader: &mut EventReader<R>,
     element_name: &OwnedName,
@@ -715,6 +864,118 @@ pub(crate) fn unexpected_element_error(
     }
 }
 
+/// Reads the whole document and checks that its elements are nested at most `max_depth` levels
+/// deep, before handing it to the recursive `FromXml` implementations.
+///
+/// Malformed documents pass the check, so that the actual parser reports the error.
+pub(crate) fn read_with_max_depth<R: Read>(
+    mut reader: R,
+    max_depth: usize,
+) -> Result<Vec<u8>, XmlReadError> {
+    let mut content = Vec::new();
+    reader
+        .read_to_end(&mut content)
+        .map_err(|error| XmlReadError::ElementReadError {
+            error: error.into(),
+            element: "bom".to_string(),
+        })?;
+
+    let mut depth = 0usize;
+    for event in EventReader::new(content.as_slice()) {
+        match event {
+            Ok(reader::XmlEvent::StartElement { .. }) => {
+                depth += 1;
+                if depth > max_depth {
+                    return Err(XmlReadError::MaxDepthExceeded { max_depth });
+                }
+            }
+            Ok(reader::XmlEvent::EndElement { .. }) => depth -= 1,
+            Ok(_) => (),
+            Err(_) => break,
+        }
+    }
+
+    Ok(content)
+}
+
+/// Translates `\r\n` and lone `\r` line breaks to `\n` while reading, as required by
+/// [the XML spec](https://www.w3.org/TR/xml/#sec-line-ends). `xml-rs` keeps them as is.
+///
+/// This works on bytes, so it must only wrap documents in an ASCII-compatible encoding, see
+/// [`normalize_utf16_line_endings`] for UTF-16.
+pub(crate) struct NormalizeLineEndings<R> {
+    inner: R,
+}
+
+impl<R: BufRead> NormalizeLineEndings<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+impl<R: BufRead> Read for NormalizeLineEndings<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            let Some(&byte) = self.inner.fill_buf()?.first() else {
+                break;
+            };
+            self.inner.consume(1);
+            if byte == b'\r' {
+                // Peeking at the next byte keeps a `\r\n` split across reads a single line break
+                if self.inner.fill_buf()?.first() == Some(&b'\n') {
+                    self.inner.consume(1);
+                }
+                buf[written] = b'\n';
+            } else {
+                buf[written] = byte;
+            }
+            written += 1;
+        }
+        Ok(written)
+    }
+}
+
+/// Normalizes the line breaks of a UTF-16 document like [`NormalizeLineEndings`] does for
+/// ASCII-compatible encodings, or returns `None` if the document is not UTF-16.
+///
+/// The encoding is detected from the byte order mark or the encoded `<?` of the declaration, see
+/// [the XML spec](https://www.w3.org/TR/xml/#sec-guessing).
+pub(crate) fn normalize_utf16_line_endings(content: &[u8]) -> Option<Vec<u8>> {
+    let big_endian = match content {
+        [0xFE, 0xFF, ..] | [0x00, b'<', 0x00, b'?', ..] => true,
+        [0xFF, 0xFE, ..] | [b'<', 0x00, b'?', 0x00, ..] => false,
+        _ => return None,
+    };
+    let decode = |pair: &[u8]| {
+        let pair = [pair[0], pair[1]];
+        if big_endian {
+            u16::from_be_bytes(pair)
+        } else {
+            u16::from_le_bytes(pair)
+        }
+    };
+
+    // `\r` and `\n` are single code units, which never occur within a surrogate pair
+    let chunks = content.chunks_exact(2);
+    let remainder = chunks.remainder();
+    let mut units = chunks.map(decode).peekable();
+    let mut output = Vec::with_capacity(content.len());
+    while let Some(mut unit) = units.next() {
+        if unit == u16::from(b'\r') {
+            units.next_if_eq(&u16::from(b'\n'));
+            unit = u16::from(b'\n');
+        }
+        output.extend(if big_endian {
+            unit.to_be_bytes()
+        } else {
+            unit.to_le_bytes()
+        });
+    }
+    output.extend(remainder);
+    Some(output)
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use xml::{EmitterConfig, ParserConfig};
@@ -828,4 +1089,69 @@ pub(crate) mod test {
 
         // no end document, because it returns an error during the read_lax_validation_tag call
     }
+
+    #[test]
+    fn it_should_limit_the_nesting_depth() {
+        let input = "<a><b><c/></b><b/></a>";
+        assert_eq!(
+            read_with_max_depth(input.as_bytes(), 3).unwrap(),
+            input.as_bytes()
+        );
+        assert!(matches!(
+            read_with_max_depth(input.as_bytes(), 2),
+            Err(XmlReadError::MaxDepthExceeded { max_depth: 2 })
+        ));
+    }
+
+    #[test]
+    fn it_should_normalize_line_endings() {
+        let input = "a\r\nb\rc\n\r\r\nd\r";
+        let mut output = String::new();
+        NormalizeLineEndings::new(input.as_bytes())
+            .read_to_string(&mut output)
+            .unwrap();
+        assert_eq!(output, "a\nb\nc\n\n\nd\n");
+
+        // Line breaks split across reads are normalized too.
+        let mut output = String::new();
+        NormalizeLineEndings::new(input.as_bytes().chain("\n".as_bytes()))
+            .read_to_string(&mut output)
+            .unwrap();
+        assert_eq!(output, "a\nb\nc\n\n\nd\n");
+
+        // A lone `\r` at the end of a read is followed by the next byte without overflowing.
+        let mut reader = NormalizeLineEndings::new("a\rb\r".as_bytes());
+        let mut output = Vec::new();
+        let mut buf = [0u8; 1];
+        while reader.read(&mut buf).unwrap() > 0 {
+            output.push(buf[0]);
+        }
+        assert_eq!(output, b"a\nb\n");
+    }
+
+    #[test]
+    fn it_should_normalize_line_endings_in_utf16_documents() {
+        let input = "<?x\r\na\rb\n\u{1F600}\r";
+        let expected = "<?x\na\nb\n\u{1F600}\n";
+        let le =
+            |text: &str| -> Vec<u8> { text.encode_utf16().flat_map(u16::to_le_bytes).collect() };
+        let be =
+            |text: &str| -> Vec<u8> { text.encode_utf16().flat_map(u16::to_be_bytes).collect() };
+
+        assert_eq!(normalize_utf16_line_endings(&le(input)), Some(le(expected)));
+        assert_eq!(normalize_utf16_line_endings(&be(input)), Some(be(expected)));
+        assert_eq!(
+            normalize_utf16_line_endings(&le("\u{FEFF}a\r\nb")),
+            Some(le("\u{FEFF}a\nb"))
+        );
+        assert_eq!(
+            normalize_utf16_line_endings(&be("\u{FEFF}a\r\nb")),
+            Some(be("\u{FEFF}a\nb"))
+        );
+        assert_eq!(normalize_utf16_line_endings(input.as_bytes()), None);
+        assert_eq!(
+            normalize_utf16_line_endings("\u{FEFF}<?x\r\n".as_bytes()),
+            None
+        );
+    }
 }
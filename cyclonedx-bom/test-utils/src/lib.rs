@@ -17,6 +17,7 @@
  */
 
 use jsonschema::{error::ValidationErrorKind, paths::JSONPointer, JSONSchema};
+use once_cell::sync::Lazy;
 
 use cyclonedx_bom::models::bom::SpecVersion;
 
@@ -68,35 +69,11 @@ pub fn validate_json_with_schema(
     json: &serde_json::Value,
     version: SpecVersion,
 ) -> Result<(), Vec<ValidationError>> {
-    let spdx_schema = include_str!("../schema/spdx.schema.json");
-    let spdx_schema: serde_json::Value =
-        serde_json::from_str(spdx_schema).expect("Failed to read spdx.schema.json");
-    let jsf_schema = include_str!("../schema/jsf-0.82.schema.json");
-    let jsf_schema: serde_json::Value =
-        serde_json::from_str(jsf_schema).expect("Failed to load jsf-0.82.schema.json");
-
-    let schema = match version {
-        SpecVersion::V1_3 => include_str!("../schema/bom-1.3.schema.json"),
-        SpecVersion::V1_4 => include_str!("../schema/bom-1.4.schema.json"),
-        SpecVersion::V1_5 => include_str!("../schema/bom-1.5.schema.json"),
+    let compiled_schema = match version {
+        SpecVersion::V1_3 => &*BOM_SCHEMA_V1_3,
+        SpecVersion::V1_4 => &*BOM_SCHEMA_V1_4,
+        SpecVersion::V1_5 => &*BOM_SCHEMA_V1_5,
     };
-    let schema: serde_json::Value =
-        serde_json::from_str(schema).expect("Failed to parse JSON schema file");
-
-    // Fill in external schema files, handle unknown format(s)
-    let compiled_schema = JSONSchema::options()
-        .with_draft(jsonschema::Draft::Draft7)
-        .with_document(
-            "http://cyclonedx.org/schema/spdx.schema.json".to_string(),
-            spdx_schema,
-        )
-        .with_document(
-            "http://cyclonedx.org/schema/jsf-0.82.schema.json".to_string(),
-            jsf_schema,
-        )
-        .with_format("idn-email", with_idn_email)
-        .compile(&schema)
-        .expect("Failed to compile JSON schema file");
 
     let result = compiled_schema.validate(json);
     if let Err(errors) = result {
@@ -109,15 +86,84 @@ pub fn validate_json_with_schema(
     })
 }
 
+// Compiling a schema is expensive, so every schema is compiled once and shared between calls.
+static BOM_SCHEMA_V1_3: Lazy<JSONSchema> =
+    Lazy::new(|| compile_bom_schema(include_str!("../schema/bom-1.3.schema.json")));
+static BOM_SCHEMA_V1_4: Lazy<JSONSchema> = Lazy::new(|| {
+    #[cfg(test)]
+    BOM_SCHEMA_V1_4_COMPILATIONS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    compile_bom_schema(include_str!("../schema/bom-1.4.schema.json"))
+});
+#[cfg(test)]
+static BOM_SCHEMA_V1_4_COMPILATIONS: std::sync::atomic::AtomicUsize =
+    std::sync::atomic::AtomicUsize::new(0);
+static BOM_SCHEMA_V1_5: Lazy<JSONSchema> =
+    Lazy::new(|| compile_bom_schema(include_str!("../schema/bom-1.5.schema.json")));
+static SPDX_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
+    compile_schema(
+        JSONSchema::options().with_draft(jsonschema::Draft::Draft7),
+        &spdx_schema(),
+    )
+});
+
+fn spdx_schema() -> serde_json::Value {
+    let spdx_schema = include_str!("../schema/spdx.schema.json");
+    serde_json::from_str(spdx_schema).expect("Failed to read spdx.schema.json")
+}
+
+fn compile_bom_schema(schema: &str) -> JSONSchema {
+    let jsf_schema = include_str!("../schema/jsf-0.82.schema.json");
+    let jsf_schema: serde_json::Value =
+        serde_json::from_str(jsf_schema).expect("Failed to load jsf-0.82.schema.json");
+    let schema: serde_json::Value =
+        serde_json::from_str(schema).expect("Failed to parse JSON schema file");
+
+    // Fill in external schema files, handle unknown format(s)
+    compile_schema(
+        JSONSchema::options()
+            .with_draft(jsonschema::Draft::Draft7)
+            .with_document(
+                "http://cyclonedx.org/schema/spdx.schema.json".to_string(),
+                spdx_schema(),
+            )
+            .with_document(
+                "http://cyclonedx.org/schema/jsf-0.82.schema.json".to_string(),
+                jsf_schema,
+            )
+            .with_format("idn-email", with_idn_email),
+        &schema,
+    )
+}
+
+fn compile_schema(
+    options: &mut jsonschema::CompilationOptions,
+    schema: &serde_json::Value,
+) -> JSONSchema {
+    options
+        .compile(schema)
+        .expect("Failed to compile JSON schema file")
+}
+
 /// For now ignore the content of the given email string.
 fn with_idn_email(_s: &str) -> bool {
     true
 }
 
+/// Validates a single license id against the bundled SPDX license list schema.
+pub fn validate_spdx_license_id(
+    license_id: &serde_json::Value,
+) -> Result<(), Vec<ValidationError>> {
+    SPDX_SCHEMA.validate(license_id).map_err(|iter| {
+        iter.map(|err| ValidationError::new(err.instance.to_string(), err.kind, err.instance_path))
+            .collect::<Vec<_>>()
+    })
+}
+
 #[cfg(test)]
 mod test {
-    use super::validate_json_with_schema;
+    use super::{validate_json_with_schema, BOM_SCHEMA_V1_4_COMPILATIONS};
     use cyclonedx_bom::models::bom::SpecVersion;
+    use std::sync::atomic::Ordering;
 
     #[test]
     fn it_should_validate_version_13() {
@@ -155,4 +201,19 @@ mod test {
         let json = serde_json::from_str(input).expect("Failed to parse JSON");
         assert!(validate_json_with_schema(&json, SpecVersion::V1_3).is_ok());
     }
+
+    #[test]
+    fn it_should_compile_each_schema_once() {
+        let json = serde_json::json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": "1.4",
+            "version": 1
+        });
+
+        for _ in 0..3 {
+            assert!(validate_json_with_schema(&json, SpecVersion::V1_4).is_ok());
+        }
+
+        assert_eq!(BOM_SCHEMA_V1_4_COMPILATIONS.load(Ordering::SeqCst), 1);
+    }
 }
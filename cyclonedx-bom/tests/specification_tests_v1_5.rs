@@ -63,6 +63,59 @@ mod v1_5 {
         });
     }
 
+    /// Converts all line endings of a document to CRLF.
+    fn with_crlf_line_endings(document: &str) -> String {
+        document.replace("\r\n", "\n").replace('\n', "\r\n")
+    }
+
+    #[test]
+    fn it_should_parse_xml_specifications_with_crlf_line_endings_identically() {
+        insta::glob!("spec/1.5/valid*.xml", |path| {
+            let document = std::fs::read_to_string(path)
+                .unwrap_or_else(|_| panic!("Failed to read file: {path:?}"));
+            let lf = Bom::parse_from_xml_v1_5(document.replace("\r\n", "\n").as_bytes())
+                .unwrap_or_else(|e| {
+                    panic!("Failed to parse the document as an BOM: {path:?} {:#?}", e)
+                });
+            let crlf = Bom::parse_from_xml_v1_5(with_crlf_line_endings(&document).as_bytes())
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Failed to parse the CRLF document as an BOM: {path:?} {:#?}",
+                        e
+                    )
+                });
+
+            assert_eq!(
+                lf, crlf,
+                "{path:?} parsed differently with CRLF line endings"
+            );
+        });
+    }
+
+    #[test]
+    fn it_should_parse_json_specifications_with_crlf_line_endings_identically() {
+        insta::glob!("spec/1.5/valid*.json", |path| {
+            let document = std::fs::read_to_string(path)
+                .unwrap_or_else(|_| panic!("Failed to read file: {path:?}"));
+            let lf = Bom::parse_from_json_v1_5(document.replace("\r\n", "\n").as_bytes())
+                .unwrap_or_else(|e| {
+                    panic!("Failed to parse the document as an BOM: {path:?} {:#?}", e)
+                });
+            let crlf = Bom::parse_from_json_v1_5(with_crlf_line_endings(&document).as_bytes())
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Failed to parse the CRLF document as an BOM: {path:?} {:#?}",
+                        e
+                    )
+                });
+
+            assert_eq!(
+                lf, crlf,
+                "{path:?} parsed differently with CRLF line endings"
+            );
+        });
+    }
+
     #[test]
     fn it_should_fail_to_parse_all_of_the_invalid_xml_specifications() {
         insta::with_settings!({